@@ -1,11 +1,52 @@
+use std::ops::Range;
+
 pub trait LineDebugInfo {
     fn line(&self) -> usize;
     fn column(&self) -> usize;
     fn file_name<'a>(&'a self) -> &'a str;
 }
 
-pub trait SpanDebugInfo {
-    fn begin_line(&self) -> usize;
-    fn end_line(&self) -> usize;
-    fn file_name<'a>(&'a self) -> &'a str;
+/// Source range covered by a token or an AST node. The beginning line and
+/// column are given by `LineDebugInfo`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset right after the last character
+    pub end: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Span going from the beginning of `self` to the end of `other`
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+            end_line: other.end_line,
+            end_column: other.end_column,
+        }
+    }
+}
+
+pub trait SpanDebugInfo: LineDebugInfo {
+    fn span(&self) -> Span;
+
+    fn begin_line(&self) -> usize {
+        self.line()
+    }
+
+    fn end_line(&self) -> usize {
+        self.span().end_line
+    }
+
+    fn end_column(&self) -> usize {
+        self.span().end_column
+    }
+
+    fn byte_range(&self) -> Range<usize> {
+        let span = self.span();
+        span.start..span.end
+    }
 }
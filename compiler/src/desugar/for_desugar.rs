@@ -1,7 +1,8 @@
 use crate::parser::visitors::{
-    BlockStatement, BreakStatement, Expression, ForStatement, FunctionStatement, IfStatement,
-    ImportStatement, MutableStatementVisitor, ReturnStatement, Statement, StructStatement,
-    VariableAssignment, VariableDeclaration, WhileStatement,
+    BlockStatement, BreakStatement, ContinueStatement, EnumStatement, Expression, ForStatement,
+    FunctionStatement, IfStatement, ImplStatement, ImportStatement, InterfaceStatement,
+    MatchStatement, MutableStatementVisitor, ReturnStatement, Statement, StructStatement,
+    UnionStatement, VariableAssignment, VariableDeclaration, WhileStatement,
 };
 
 pub struct ForDesugar;
@@ -10,9 +11,11 @@ impl ForDesugar {
     pub fn visit_stmt(&mut self, stmt: &mut Statement) -> Option<Statement> {
         match stmt {
             Statement::Function(f) => self.visit_function_statement(f),
+            Statement::Impl(i) => self.visit_impl_statement(i),
             Statement::Block(b) => self.visit_block_statement(b),
             Statement::IfStatement(i) => self.visit_if_statement(i),
             Statement::WhileStatement(w) => self.visit_while_statement(w),
+            Statement::MatchStatement(m) => self.visit_match_statement(m),
             Statement::ForStatement(f) => return Some(Self::desugar_for(f)),
             _ => {}
         };
@@ -26,6 +29,7 @@ impl ForDesugar {
             line: for_stmt.line,
             column: for_stmt.column,
             filename: for_stmt.filename.clone(),
+            span: for_stmt.span,
         };
 
         let mut while_block = BlockStatement {
@@ -33,13 +37,13 @@ impl ForDesugar {
             line: for_stmt.line,
             column: for_stmt.column,
             filename: for_stmt.filename.clone(),
+            span: for_stmt.span,
         };
 
         while_block
             .statements
             .append(&mut for_stmt.block_stmt.statements);
 
-        while_block.statements.push((*for_stmt.next_expr).clone());
         block
             .statements
             .push(Statement::VariableDeclaration(for_stmt.init_expr.clone()));
@@ -47,9 +51,12 @@ impl ForDesugar {
         let desugar_while = WhileStatement {
             condition: for_stmt.loop_condition.clone(),
             loop_block: while_block,
+            // Not appended to the body, a continue statement must still run it
+            next_expr: Some(for_stmt.next_expr.clone()),
             line: for_stmt.line,
             column: for_stmt.column,
             filename: for_stmt.filename.clone(),
+            span: for_stmt.span,
         };
 
         block
@@ -91,6 +98,7 @@ impl MutableStatementVisitor<()> for ForDesugar {
                 Statement::Block(b) => self.visit_block_statement(b),
                 Statement::IfStatement(i) => self.visit_if_statement(i),
                 Statement::WhileStatement(w) => self.visit_while_statement(w),
+                Statement::MatchStatement(m) => self.visit_match_statement(m),
                 _ => { /* Does nothing ... */ }
             }
         }
@@ -121,11 +129,39 @@ impl MutableStatementVisitor<()> for ForDesugar {
         unreachable!()
     }
 
+    fn visit_match_statement(&mut self, match_stmt: &mut MatchStatement) -> () {
+        for arm in &mut match_stmt.arms {
+            self.visit_block_statement(&mut arm.block);
+        }
+    }
+
     fn visit_break_statement(&mut self, _for_stmt: &mut BreakStatement) {
         unreachable!()
     }
 
-    fn visit_struct_statement(&mut self, _stct: &StructStatement) -> () {
+    fn visit_continue_statement(&mut self, _continue_stmt: &mut ContinueStatement) {
+        unreachable!()
+    }
+
+    fn visit_struct_statement(&mut self, _stct: &mut StructStatement) -> () {
+        unreachable!()
+    }
+
+    fn visit_enum_statement(&mut self, _enm: &mut EnumStatement) -> () {
+        unreachable!()
+    }
+
+    fn visit_union_statement(&mut self, _union_stmt: &mut UnionStatement) -> () {
+        unreachable!()
+    }
+
+    fn visit_impl_statement(&mut self, impl_stmt: &mut ImplStatement) -> () {
+        for method in impl_stmt.methods.iter_mut() {
+            self.visit_function_statement(method);
+        }
+    }
+
+    fn visit_interface_statement(&mut self, _interface: &mut InterfaceStatement) -> () {
         unreachable!()
     }
 
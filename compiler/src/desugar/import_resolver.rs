@@ -1,11 +1,83 @@
-use std::{fs, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
 
-use crate::parser::visitors::{FunctionStatement, ImportStatement, Statement, StructStatement};
+use crate::lint::{Lint, Warning};
+use crate::parser::visitors::{
+    Binary, BinaryLogic, EnumStatement, Expression, FunctionStatement, ImplStatement,
+    ImportStatement, InterfaceStatement, Literal, Pattern, Statement, StructStatement, Unary,
+    UnionStatement, VariableDeclaration,
+};
+use crate::type_system::value_type::{StaticArray, ValueType};
 use crate::{lexer::Token, parser::parser::Parser};
 
+/// Forward declares the items exported by the imported modules. They're
+/// declared in the namespace of their module `module::item` and keep the
+/// symbol of their definition, mangled with the name of the defining module.
+/// The items of `import { item } from "module";` are declared without
+/// namespace and `export import` adds the items of a module to the exported
+/// items of its importer.
+///
+/// The modules imported by an imported module are resolved too, their
+/// structs, enums, unions and interfaces are declared in a nested namespace
+/// `module::dependency::Type`
+/// unless the importer imports them directly. Each module is parsed once
+/// and kept for the next files resolved with the same resolver.
+///
+/// The imports whose namespace or items aren't used by the resolved file are
+/// reported as warnings.
 pub struct ImportResolver {
+    /// Directories searched in order when a module isn't next to its importer
+    search_paths: Vec<PathBuf>,
+    /// Parsed modules by canonical path
+    modules: HashMap<PathBuf, ImportedModule>,
     declared_functions: Vec<FunctionStatement>,
     declared_structs: Vec<StructStatement>,
+    declared_enums: Vec<EnumStatement>,
+    declared_unions: Vec<UnionStatement>,
+    declared_impls: Vec<ImplStatement>,
+    declared_interfaces: Vec<InterfaceStatement>,
+    declared_globals: Vec<VariableDeclaration>,
+    warnings: Vec<Warning>,
+}
+
+/// Module of the import graph
+struct ImportedModule {
+    /// Path without extension relative to the search root the module was
+    /// found in, used to mangle the symbols
+    name: String,
+    statements: Vec<Statement>,
+    imports: Vec<ModuleImport>,
+    /// Exported structs, enums and unions
+    exported_types: HashSet<String>,
+}
+
+/// Import statement of a module, resolved to the imported module path
+#[derive(Clone, PartialEq)]
+struct ModuleImport {
+    path: PathBuf,
+    namespace: String,
+    items: Option<Vec<String>>,
+    is_exported: bool,
+}
+
+/// How the items of a module are named in the module being resolved: the
+/// selected items are declared as is, the others in the namespace.
+#[derive(Clone)]
+struct ModuleScope {
+    namespace: String,
+    items: Option<Vec<String>>,
+}
+
+impl ModuleScope {
+    fn declared_name(&self, name: &str) -> String {
+        match &self.items {
+            Some(items) if items.iter().any(|item| item == name) => name.to_string(),
+            _ => format!("{}::{}", self.namespace, name),
+        }
+    }
 }
 
 type ImportResolverReturn = Result<(), String>;
@@ -13,24 +85,130 @@ type ImportResolverReturn = Result<(), String>;
 impl ImportResolver {
     pub fn new() -> Self {
         ImportResolver {
+            search_paths: Vec::new(),
+            modules: HashMap::new(),
             declared_functions: Vec::new(),
             declared_structs: Vec::new(),
+            declared_enums: Vec::new(),
+            declared_unions: Vec::new(),
+            declared_impls: Vec::new(),
+            declared_interfaces: Vec::new(),
+            declared_globals: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Unused imports of the last resolved file
+    pub fn get_warnings(&self) -> &Vec<Warning> {
+        &self.warnings
+    }
+
+    /// Appends a directory to the search paths, the first added ones are
+    /// searched first.
+    pub fn add_search_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.search_paths.push(path.into());
+    }
+
     pub fn resolve_imports(
         &mut self,
         stmts: &Vec<Statement>,
         file_name: &str,
     ) -> Result<Vec<Statement>, String> {
+        self.declared_functions.clear();
+        self.declared_structs.clear();
+        self.declared_enums.clear();
+        self.declared_unions.clear();
+        self.declared_impls.clear();
+        self.declared_interfaces.clear();
+        self.declared_globals.clear();
+        self.warnings.clear();
+
+        let module_name = self.root_module_name(Path::new(file_name));
+
+        // Scope of every module of the graph in this module
+        let mut scopes: HashMap<PathBuf, ModuleScope> = HashMap::new();
+        let mut direct_imports: Vec<ModuleImport> = Vec::new();
+
         for stmt in stmts {
             if let Statement::Import(is) = stmt {
-                self.resolve_statement(is, file_name)?;
+                // The importing module is the root of the import chain
+                let mut chain: Vec<PathBuf> = Self::root_path(is, file_name).into_iter().collect();
+                let import = self.load_import(is, &mut chain, &module_name)?;
+
+                if let Some(previous) = direct_imports.iter().find(|i| i.path == import.path) {
+                    if previous == &import {
+                        continue;
+                    }
+
+                    return Err(format!(
+                        "{}:{}:{} Error: Module '{}' is already imported as '{}'.",
+                        is.filename, is.line, is.column, is.module_path, previous.namespace
+                    ));
+                }
+
+                if direct_imports
+                    .iter()
+                    .any(|i| i.namespace == import.namespace)
+                {
+                    return Err(format!(
+                        "{}:{}:{} Error: Namespace '{}' is already used by another import.",
+                        is.filename, is.line, is.column, import.namespace
+                    ));
+                }
+
+                self.check_selected_items(&import, is)?;
+
+                scopes.insert(
+                    import.path.clone(),
+                    ModuleScope {
+                        namespace: import.namespace.clone(),
+                        items: import.items.clone(),
+                    },
+                );
+                direct_imports.push(import);
             }
         }
 
+        self.check_unused_imports(stmts);
+
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        for import in &direct_imports {
+            self.declare_types(&import.path, &mut scopes, &mut visited, file_name);
+        }
+
+        for import in &direct_imports {
+            self.declare_items(import, &scopes, file_name)?;
+        }
+
         let mut result: Vec<Statement> = Vec::new();
 
+        result.extend(
+            self.declared_enums
+                .iter()
+                .map(|e| Statement::Enum(e.clone())),
+        );
+
+        result.extend(
+            self.declared_unions
+                .iter()
+                .map(|u| Statement::Union(u.clone())),
+        );
+
+        // Before the functions and impl blocks using them
+        result.extend(
+            self.declared_interfaces
+                .iter()
+                .map(|i| Statement::Interface(i.clone())),
+        );
+
+        // Before the functions, the scopes of the parameters of a forward
+        // declaration stay open and would hide them from the generic instances
+        result.extend(
+            self.declared_globals
+                .iter()
+                .map(|g| Statement::VariableDeclaration(g.clone())),
+        );
+
         result.extend(
             self.declared_functions
                 .iter()
@@ -43,7 +221,14 @@ impl ImportResolver {
                 .map(|s| Statement::Struct(s.clone())),
         );
 
+        result.extend(
+            self.declared_impls
+                .iter()
+                .map(|i| Statement::Impl(i.clone())),
+        );
+
         // Add everything else except Import statements since they're resolved now.
+        let generic_dependencies = Self::generic_dependencies(stmts);
         result.extend(
             stmts
                 .iter()
@@ -51,75 +236,1349 @@ impl ImportResolver {
                     Statement::Import(_) => false,
                     _ => true,
                 })
-                .map(|s| s.clone()),
+                .map(|s| Self::mangle_definition(s.clone(), &module_name, &generic_dependencies)),
         );
 
         Ok(result)
     }
 
-    fn parse_file(path: &str) -> Result<Vec<Statement>, String> {
-        let file_path = format!("{}.lht", path);
-        let file_content = match fs::read_to_string(file_path) {
+    /// Symbol of `name` defined in `module`, `::` can't appear in C symbols
+    /// so they can't clash.
+    fn mangle(module: &str, name: &str) -> String {
+        format!("{}::{}", module, name)
+    }
+
+    /// Symbol of the method `Type::method` defined in `module`
+    fn method_symbol(module: &str, type_name: &str, method: &str) -> String {
+        Self::mangle(module, &format!("{}::{}", type_name, method))
+    }
+
+    /// Name of the module in the symbols, it's its path without extension
+    /// relative to the search root it was found in so that two modules with
+    /// the same file name don't clash and the symbols don't depend on where
+    /// the sources are.
+    fn module_name(path: &Path) -> String {
+        let mut name = PathBuf::new();
+        for component in path.with_extension("").components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir
+                    if matches!(name.components().last(), Some(Component::Normal(_))) =>
+                {
+                    name.pop();
+                }
+                component => name.push(component),
+            }
+        }
+
+        name.to_string_lossy().to_string()
+    }
+
+    /// Name of a module given to the compiler, its root is the first search
+    /// path holding it or else the current directory, as for the modules
+    /// importing it.
+    fn root_module_name(&self, path: &Path) -> String {
+        let canonical_path = fs::canonicalize(path).ok();
+        let relative_path = canonical_path.as_ref().and_then(|canonical_path| {
+            self.search_paths
+                .iter()
+                .cloned()
+                .chain(std::env::current_dir())
+                .filter_map(|root| fs::canonicalize(root).ok())
+                .find_map(|root| canonical_path.strip_prefix(root).ok())
+        });
+
+        Self::module_name(relative_path.unwrap_or(path))
+    }
+
+    /// `import "module" as alias;` is in the `alias` namespace, `module` otherwise.
+    fn namespace(import_stmt: &ImportStatement) -> String {
+        import_stmt.alias.clone().unwrap_or_else(|| {
+            Path::new(&import_stmt.module_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Path of the module importing `import_stmt`, it's in the directory of
+    /// the import.
+    fn root_path(import_stmt: &ImportStatement, file_name: &str) -> Option<PathBuf> {
+        let file = Path::new(file_name).file_name()?;
+        fs::canonicalize(Path::new(&import_stmt.file_path).join(file)).ok()
+    }
+
+    /// Functions and exported globals defined in the module are emitted with
+    /// a mangled symbol. `main` is the entry point called by the C runtime.
+    /// The `generic_dependencies` are exported for the importers.
+    fn mangle_definition(
+        stmt: Statement,
+        module_name: &str,
+        generic_dependencies: &HashSet<String>,
+    ) -> Statement {
+        match stmt {
+            Statement::Function(f) if f.block.is_some() && f.callee != "main" => {
+                Statement::Function(FunctionStatement {
+                    mangled_name: Some(Self::mangle(module_name, &f.callee)),
+                    is_exported: f.is_exported || generic_dependencies.contains(&f.callee),
+                    ..f
+                })
+            }
+            Statement::VariableDeclaration(v)
+                if (v.is_exported || generic_dependencies.contains(&v.identifier))
+                    && v.init_expr.is_some() =>
+            {
+                Statement::VariableDeclaration(VariableDeclaration {
+                    mangled_name: Some(Self::mangle(module_name, &v.identifier)),
+                    is_exported: true,
+                    ..v
+                })
+            }
+            Statement::Impl(i) => Statement::Impl(ImplStatement {
+                methods: i
+                    .methods
+                    .into_iter()
+                    .map(|method| FunctionStatement {
+                        mangled_name: Some(Self::method_symbol(
+                            module_name,
+                            &i.type_name,
+                            &method.callee,
+                        )),
+                        ..method
+                    })
+                    .collect(),
+                ..i
+            }),
+            _ => stmt,
+        }
+    }
+
+    fn parse_file(path: &Path) -> Result<Vec<Statement>, String> {
+        let file_path = path.to_string_lossy().to_string();
+        let file_content = match fs::read_to_string(path) {
             Ok(content) => content,
             Err(msg) => {
                 return Err(format!(
                     "Error while reading imported file {}: {}",
-                    path, msg
+                    file_path, msg
                 ))
             }
         };
 
         let tokens = Token::lex_string(file_content.as_str());
-        let p = Path::new(path).parent().unwrap().to_str().unwrap();
-        let mut parser = Parser::new(tokens, p, p);
+        let p = path.parent().unwrap().to_str().unwrap();
+        let mut parser = Parser::new(tokens, p, &file_path);
+
+        parser.parse().map_err(|errors| {
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+    }
+
+    /// Canonical path and name of the module imported by `import_stmt` from
+    /// the module `importer`. It's looked up in the directory of the importing
+    /// module, then in the search paths.
+    fn find_module(
+        &self,
+        import_stmt: &ImportStatement,
+        importer: &str,
+    ) -> Result<(PathBuf, String), String> {
+        let file_name = format!("{}.lht", import_stmt.module_path);
+        // (directory, its path relative to the root), a module next to its
+        // importer has the same root
+        let importer_dir = (
+            Path::new(&import_stmt.file_path),
+            Path::new(importer).parent().unwrap_or(Path::new("")),
+        );
+        let search_paths = self
+            .search_paths
+            .iter()
+            .map(|p| (p.as_path(), Path::new("")));
+        let candidates: Vec<(PathBuf, PathBuf)> = std::iter::once(importer_dir)
+            .chain(search_paths)
+            .map(|(dir, relative_dir)| (dir.join(&file_name), relative_dir.join(&file_name)))
+            .collect();
+
+        if let Some((path, relative_path)) = candidates.iter().find(|(path, _)| path.is_file()) {
+            let canonical_path = fs::canonicalize(path).map_err(|msg| {
+                format!(
+                    "{}:{}:{} Error: Error while reading imported file {}: {}",
+                    import_stmt.filename,
+                    import_stmt.line,
+                    import_stmt.column,
+                    path.display(),
+                    msg
+                )
+            })?;
 
-        match parser.parse() {
-            Some(stmts) => Ok(stmts),
-            None => Err(format!(
-                "Error while parsing imported file '{}' check errors above.",
-                path
-            )),
+            return Ok((canonical_path, Self::module_name(relative_path)));
         }
+
+        Err(format!(
+            "{}:{}:{} Error: Module '{}' not found, tried:\n{}",
+            import_stmt.filename,
+            import_stmt.line,
+            import_stmt.column,
+            import_stmt.module_path,
+            candidates
+                .iter()
+                .map(|(path, _)| format!("    {}", path.display()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ))
     }
 
-    fn resolve_statement(
+    /// Parses the module imported by `import_stmt` and the modules it imports.
+    /// `chain` holds the modules being resolved, from the root module to the
+    /// importer, an import of one of them is a cycle. `importer` is the name
+    /// of the importing module.
+    fn load_import(
         &mut self,
         import_stmt: &ImportStatement,
-        file_name: &str,
+        chain: &mut Vec<PathBuf>,
+        importer: &str,
+    ) -> Result<ModuleImport, String> {
+        let (path, name) = self.find_module(import_stmt, importer)?;
+        let import = ModuleImport {
+            path: path.clone(),
+            namespace: Self::namespace(import_stmt),
+            items: import_stmt.items.clone(),
+            is_exported: import_stmt.is_exported,
+        };
+
+        if let Some(position) = chain.iter().position(|p| p == &path) {
+            let cycle = chain[position..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<String>>()
+                .join(" -> ");
+
+            return Err(format!(
+                "{}:{}:{} Error: Import cycle detected: {}",
+                import_stmt.filename, import_stmt.line, import_stmt.column, cycle
+            ));
+        }
+
+        if self.modules.contains_key(&path) {
+            return Ok(import);
+        }
+
+        let statements = Self::parse_file(&path)?;
+
+        chain.push(path.clone());
+        let mut imports = Vec::new();
+        for stmt in &statements {
+            if let Statement::Import(is) = stmt {
+                imports.push(self.load_import(is, chain, &name)?);
+            }
+        }
+        chain.pop();
+
+        let exported_types = statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Struct(s) if s.exported => Some(s.type_name.clone()),
+                Statement::Enum(e) if e.exported => Some(e.type_name.clone()),
+                Statement::Union(u) if u.exported => Some(u.type_name.clone()),
+                Statement::Interface(i) if i.exported => Some(i.type_name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        self.modules.insert(
+            path.clone(),
+            ImportedModule {
+                name,
+                statements,
+                imports,
+                exported_types,
+            },
+        );
+
+        Ok(import)
+    }
+
+    /// Name of an exported item, `None` for statements that can't be exported.
+    fn item_name(stmt: &Statement) -> Option<&String> {
+        match stmt {
+            Statement::Function(f) if f.is_exported => Some(&f.callee),
+            Statement::Struct(s) if s.exported => Some(&s.type_name),
+            Statement::Enum(e) if e.exported => Some(&e.type_name),
+            Statement::Union(u) if u.exported => Some(&u.type_name),
+            Statement::Interface(i) if i.exported => Some(&i.type_name),
+            Statement::VariableDeclaration(v) if v.is_exported => Some(&v.identifier),
+            _ => None,
+        }
+    }
+
+    /// Items exported by the module at `path` with the path of the module
+    /// defining them, the items of its `export import` included.
+    fn exported_items(&self, path: &PathBuf) -> Vec<(PathBuf, &Statement)> {
+        let module = &self.modules[path];
+        let mut items: Vec<(PathBuf, &Statement)> = module
+            .statements
+            .iter()
+            .filter(|stmt| Self::item_name(stmt).is_some())
+            .map(|stmt| (path.clone(), stmt))
+            .collect();
+
+        for import in module.imports.iter().filter(|i| i.is_exported) {
+            items.extend(
+                self.exported_items(&import.path)
+                    .into_iter()
+                    .filter(|(_, stmt)| match (&import.items, Self::item_name(stmt)) {
+                        (Some(selected), Some(name)) => selected.contains(name),
+                        _ => true,
+                    }),
+            );
+        }
+
+        items
+    }
+
+    /// Every item of `import { item, ... } from "module";` must be exported
+    /// by the module.
+    fn check_selected_items(
+        &self,
+        import: &ModuleImport,
+        import_stmt: &ImportStatement,
     ) -> ImportResolverReturn {
-        // the file_path is relative to the module path so we need
-        // to concat the path.
-        let path = Path::new(&import_stmt.file_path).join(import_stmt.module_path.as_str());
-        let stmts = Self::parse_file(path.to_str().unwrap())?;
+        let exported = self.exported_items(&import.path);
+
+        for item in import.items.iter().flatten() {
+            if !exported
+                .iter()
+                .any(|(_, stmt)| Self::item_name(stmt) == Some(item))
+            {
+                return Err(format!(
+                    "{}:{}:{} Error: Module '{}' doesn't export '{}'.",
+                    import_stmt.filename,
+                    import_stmt.line,
+                    import_stmt.column,
+                    import_stmt.module_path,
+                    item
+                ));
+            }
+        }
+
+        Ok(())
+    }
 
+    /// Module defining the item exported as `name` by the module at `path`.
+    fn defining_module(&self, path: &PathBuf, name: &str) -> Option<PathBuf> {
+        self.exported_items(path)
+            .into_iter()
+            .find(|(_, stmt)| Self::item_name(stmt).map(|n| n.as_str()) == Some(name))
+            .map(|(defining_path, _)| defining_path)
+    }
+
+    /// Qualifies a struct, enum or union type seen from the module at `path`
+    /// with the name it's declared with in the module being resolved. They're
+    /// all parsed as struct types, except the type parameters of the generic
+    /// item declaring it.
+    fn qualify_type(
+        &self,
+        ty: &ValueType,
+        path: &PathBuf,
+        scopes: &HashMap<PathBuf, ModuleScope>,
+        type_params: &[String],
+    ) -> ValueType {
+        match ty {
+            ValueType::Struct(name) if type_params.contains(name) => ty.clone(),
+            ValueType::Struct(name) => {
+                let module = &self.modules[path];
+                let definition = match name.split_once("::") {
+                    // Struct of a module imported by `module`
+                    Some((namespace, type_name)) => module
+                        .imports
+                        .iter()
+                        .find(|i| i.namespace == namespace && i.items.is_none())
+                        .and_then(|i| self.defining_module(&i.path, type_name))
+                        .map(|p| (p, type_name)),
+                    None if module.exported_types.contains(name) => {
+                        Some((path.clone(), name.as_str()))
+                    }
+                    // Selected by an `import { Type } from`
+                    None => module
+                        .imports
+                        .iter()
+                        .filter(|i| i.items.iter().flatten().any(|item| item == name))
+                        .find_map(|i| self.defining_module(&i.path, name))
+                        .map(|p| (p, name.as_str())),
+                };
+
+                ValueType::Struct(
+                    definition
+                        .and_then(|(p, type_name)| {
+                            scopes.get(&p).map(|scope| scope.declared_name(type_name))
+                        })
+                        .unwrap_or_else(|| name.clone()),
+                )
+            }
+            ValueType::Generic(name, type_args) => {
+                let qualified =
+                    self.qualify_type(&ValueType::Struct(name.clone()), path, scopes, type_params);
+
+                ValueType::Generic(
+                    qualified.into_struct_type(),
+                    type_args
+                        .iter()
+                        .map(|ty| self.qualify_type(ty, path, scopes, type_params))
+                        .collect(),
+                )
+            }
+            ValueType::Dyn(name) => ValueType::Dyn(
+                self.qualify_type(&ValueType::Struct(name.clone()), path, scopes, type_params)
+                    .into_struct_type(),
+            ),
+            ValueType::Pointer(inner) => ValueType::Pointer(Box::new(self.qualify_type(
+                inner,
+                path,
+                scopes,
+                type_params,
+            ))),
+            ValueType::Array(array) => ValueType::Array(StaticArray {
+                size: array.size,
+                array_type: Box::new(self.qualify_type(
+                    &array.array_type,
+                    path,
+                    scopes,
+                    type_params,
+                )),
+            }),
+            _ => ty.clone(),
+        }
+    }
+
+    /// Qualifies the types named in the body of a generic function, it's
+    /// checked in the modules instantiating it. The functions and globals of
+    /// its module are renamed with their name in `items`, the locals hiding
+    /// them are removed from it.
+    fn qualify_body(
+        stmts: &mut [Statement],
+        qualify: &dyn Fn(&ValueType) -> ValueType,
+        items: &mut HashMap<String, String>,
+    ) {
         for stmt in stmts {
+            match stmt {
+                Statement::Expression(expr) => Self::qualify_expr(expr, qualify, items),
+                Statement::VariableDeclaration(v) => Self::qualify_declaration(v, qualify, items),
+                Statement::VariableAssignment(a) => {
+                    Self::qualify_expr(&mut a.identifier, qualify, items);
+                    Self::qualify_expr(&mut a.new_value, qualify, items);
+                }
+                Statement::Block(b) => Self::qualify_block(&mut b.statements, qualify, items),
+                Statement::Return(r) => Self::qualify_expr(&mut r.expr, qualify, items),
+                Statement::IfStatement(i) => {
+                    Self::qualify_expr(&mut i.condition, qualify, items);
+                    Self::qualify_block(&mut i.then_branch.statements, qualify, items);
+                    if let Some(else_branch) = &mut i.else_branch {
+                        Self::qualify_block(&mut else_branch.statements, qualify, items);
+                    }
+                }
+                Statement::WhileStatement(w) => {
+                    Self::qualify_expr(&mut w.condition, qualify, items);
+                    Self::qualify_block(&mut w.loop_block.statements, qualify, items);
+                    if let Some(next_expr) = &mut w.next_expr {
+                        Self::qualify_body(std::slice::from_mut(next_expr), qualify, items);
+                    }
+                }
+                Statement::ForStatement(f) => {
+                    // The loop variable is scoped to the loop
+                    let items = &mut items.clone();
+                    Self::qualify_declaration(&mut f.init_expr, qualify, items);
+                    Self::qualify_expr(&mut f.loop_condition, qualify, items);
+                    Self::qualify_body(std::slice::from_mut(&mut *f.next_expr), qualify, items);
+                    Self::qualify_block(&mut f.block_stmt.statements, qualify, items);
+                }
+                Statement::MatchStatement(m) => {
+                    Self::qualify_expr(&mut m.scrutinee, qualify, items);
+                    for arm in &mut m.arms {
+                        // The bindings are scoped to the arm
+                        let items = &mut items.clone();
+                        for pattern in &mut arm.patterns {
+                            if let Pattern::EnumVariant(variant) = pattern {
+                                variant.enum_name =
+                                    qualify(&ValueType::Struct(variant.enum_name.clone()))
+                                        .into_struct_type();
+                                variant.bindings.iter().flatten().for_each(|binding| {
+                                    items.remove(binding);
+                                });
+                            }
+                        }
+                        Self::qualify_body(&mut arm.block.statements, qualify, items);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Qualifies a nested block, the locals declared in it only hide the
+    /// items until its end.
+    fn qualify_block(
+        stmts: &mut [Statement],
+        qualify: &dyn Fn(&ValueType) -> ValueType,
+        items: &HashMap<String, String>,
+    ) {
+        Self::qualify_body(stmts, qualify, &mut items.clone());
+    }
+
+    fn qualify_declaration(
+        declaration: &mut VariableDeclaration,
+        qualify: &dyn Fn(&ValueType) -> ValueType,
+        items: &mut HashMap<String, String>,
+    ) {
+        declaration.variable_type = declaration.variable_type.as_ref().map(qualify);
+        declaration
+            .init_expr
+            .iter_mut()
+            .for_each(|expr| Self::qualify_expr(expr, qualify, items));
+        items.remove(&declaration.identifier);
+    }
+
+    fn qualify_expr(
+        expr: &mut Expression,
+        qualify: &dyn Fn(&ValueType) -> ValueType,
+        items: &mut HashMap<String, String>,
+    ) {
+        let mut qualify_boxed = |exprs: &mut [&mut Box<Expression>]| {
+            for expr in exprs {
+                Self::qualify_expr(expr, qualify, items);
+            }
+        };
+
+        match expr {
+            Expression::Literal(Literal::StructLiteral(literal)) => {
+                literal.type_name =
+                    qualify(&ValueType::Struct(literal.type_name.clone())).into_struct_type();
+                literal.type_args = literal.type_args.iter().map(qualify).collect();
+                literal
+                    .expressions
+                    .iter_mut()
+                    .for_each(|expr| Self::qualify_expr(expr, qualify, items));
+            }
+            Expression::Literal(Literal::Identifier(identifier)) => {
+                if let Some(name) = items.get(&identifier.name) {
+                    identifier.name = name.clone();
+                }
+            }
+            Expression::Literal(_) | Expression::Null(_) => {}
+            Expression::Binary(
+                Binary::Plus(l, r)
+                | Binary::Minus(l, r)
+                | Binary::Multiply(l, r)
+                | Binary::Divide(l, r)
+                | Binary::Modulo(l, r),
+            )
+            | Expression::BinaryLogic(
+                BinaryLogic::And(l, r)
+                | BinaryLogic::Or(l, r)
+                | BinaryLogic::Equal(l, r)
+                | BinaryLogic::NotEqual(l, r)
+                | BinaryLogic::More(l, r)
+                | BinaryLogic::Less(l, r)
+                | BinaryLogic::MoreEqual(l, r)
+                | BinaryLogic::LessEqual(l, r),
+            ) => qualify_boxed(&mut [l, r]),
+            Expression::Unary(Unary::Not(_, e) | Unary::Negate(_, e)) => qualify_boxed(&mut [e]),
+            Expression::Group(g) => qualify_boxed(&mut [&mut g.inner_expression]),
+            Expression::Call(call) => {
+                if let Some(name) = items.get(&call.name) {
+                    call.name = name.clone();
+                }
+                call.type_args = call.type_args.iter().map(qualify).collect();
+                call.args
+                    .iter_mut()
+                    .flatten()
+                    .for_each(|expr| Self::qualify_expr(expr, qualify, items));
+            }
+            Expression::ArrayAccess(a) => qualify_boxed(&mut [&mut a.identifier, &mut a.index]),
+            Expression::AddressOf(a) => qualify_boxed(&mut [&mut a.identifier]),
+            Expression::DeReference(d) => qualify_boxed(&mut [&mut d.identifier]),
+            Expression::MemberAccess(m) => {
+                qualify_boxed(&mut [&mut m.object]);
+                m.args
+                    .iter_mut()
+                    .flatten()
+                    .for_each(|expr| Self::qualify_expr(expr, qualify, items));
+            }
+            Expression::Conditional(c) => {
+                qualify_boxed(&mut [&mut c.condition, &mut c.then_expr, &mut c.else_expr])
+            }
+            Expression::ArrayLiteral(a) => a
+                .elements
+                .iter_mut()
+                .for_each(|expr| Self::qualify_expr(expr, qualify, items)),
+            Expression::ArrayRepeat(a) => qualify_boxed(&mut [&mut a.value]),
+            Expression::ModuleAccess(access) => {
+                // `Enum::Variant` or `Union::Variant(payload)` of an exported type
+                access.module =
+                    qualify(&ValueType::Struct(access.module.clone())).into_struct_type();
+
+                match &mut *access.item {
+                    // Already named by the module access
+                    Expression::Literal(Literal::StructLiteral(literal)) => literal
+                        .expressions
+                        .iter_mut()
+                        .for_each(|expr| Self::qualify_expr(expr, qualify, items)),
+                    Expression::Call(call) => call
+                        .args
+                        .iter_mut()
+                        .flatten()
+                        .for_each(|expr| Self::qualify_expr(expr, qualify, items)),
+                    Expression::Literal(Literal::Identifier(_)) => {}
+                    item => Self::qualify_expr(item, qualify, items),
+                }
+            }
+            Expression::Cast(c) => {
+                c.target = qualify(&c.target);
+                qualify_boxed(&mut [&mut c.expr]);
+            }
+            // Created by the type checker
+            Expression::EnumVariant(_) | Expression::UnionVariant(_) => {}
+        }
+    }
+
+    /// Reports the imports not used by the statements of the module, the
+    /// items of an `export import` are used by its importers.
+    fn check_unused_imports(&mut self, stmts: &[Statement]) {
+        let mut names = HashSet::new();
+        Self::used_names(stmts, &mut names);
+
+        for stmt in stmts {
+            let import_stmt = match stmt {
+                Statement::Import(is) if !is.is_exported => is,
+                _ => continue,
+            };
+
+            match &import_stmt.items {
+                Some(items) => {
+                    for item in items.iter().filter(|item| !names.contains(*item)) {
+                        self.warnings.push(Warning::new(
+                            Lint::UnusedImports,
+                            format!(
+                                "Unused import '{}' from '{}'.",
+                                item, import_stmt.module_path
+                            )
+                            .as_str(),
+                            import_stmt,
+                        ));
+                    }
+                }
+                None => {
+                    let namespace = Self::namespace(import_stmt);
+                    let prefix = format!("{}::", namespace);
+
+                    if !names
+                        .iter()
+                        .any(|name| name == &namespace || name.starts_with(&prefix))
+                    {
+                        self.warnings.push(Warning::new(
+                            Lint::UnusedImports,
+                            format!("Unused import '{}'.", namespace).as_str(),
+                            import_stmt,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collects the names of the types, functions, globals and namespaces
+    /// used by `stmts`.
+    fn used_names(stmts: &[Statement], names: &mut HashSet<String>) {
+        for stmt in stmts {
+            match stmt {
+                Statement::Expression(expr) => Self::used_expr_names(expr, names),
+                Statement::VariableDeclaration(v) => Self::used_declaration_names(v, names),
+                Statement::VariableAssignment(a) => {
+                    Self::used_expr_names(&a.identifier, names);
+                    Self::used_expr_names(&a.new_value, names);
+                }
+                Statement::Function(f) => Self::used_function_names(f, names),
+                Statement::Struct(s) => {
+                    for (_, ty, _, default) in &s.fields {
+                        Self::used_type_names(ty, names);
+                        default
+                            .iter()
+                            .for_each(|expr| Self::used_expr_names(expr, names));
+                    }
+                }
+                Statement::Union(u) => u
+                    .variants
+                    .iter()
+                    .flat_map(|(_, payload, _)| payload)
+                    .for_each(|ty| Self::used_type_names(ty, names)),
+                Statement::Impl(i) => {
+                    names.insert(i.type_name.clone());
+                    names.extend(i.interface.clone());
+                    i.methods
+                        .iter()
+                        .for_each(|method| Self::used_function_names(method, names));
+                }
+                Statement::Interface(i) => i
+                    .methods
+                    .iter()
+                    .for_each(|method| Self::used_function_names(method, names)),
+                Statement::Block(b) => Self::used_names(&b.statements, names),
+                Statement::Return(r) => Self::used_expr_names(&r.expr, names),
+                Statement::IfStatement(i) => {
+                    Self::used_expr_names(&i.condition, names);
+                    Self::used_names(&i.then_branch.statements, names);
+                    if let Some(else_branch) = &i.else_branch {
+                        Self::used_names(&else_branch.statements, names);
+                    }
+                }
+                Statement::WhileStatement(w) => {
+                    Self::used_expr_names(&w.condition, names);
+                    Self::used_names(&w.loop_block.statements, names);
+                    if let Some(next_expr) = &w.next_expr {
+                        Self::used_names(std::slice::from_ref(next_expr), names);
+                    }
+                }
+                Statement::ForStatement(f) => {
+                    Self::used_declaration_names(&f.init_expr, names);
+                    Self::used_expr_names(&f.loop_condition, names);
+                    Self::used_names(std::slice::from_ref(&f.next_expr), names);
+                    Self::used_names(&f.block_stmt.statements, names);
+                }
+                Statement::MatchStatement(m) => {
+                    Self::used_expr_names(&m.scrutinee, names);
+                    for arm in &m.arms {
+                        for pattern in &arm.patterns {
+                            if let Pattern::EnumVariant(variant) = pattern {
+                                names.insert(variant.enum_name.clone());
+                            }
+                        }
+                        Self::used_names(&arm.block.statements, names);
+                    }
+                }
+                Statement::Enum(_)
+                | Statement::BreakStatement(_)
+                | Statement::ContinueStatement(_)
+                | Statement::Import(_) => {}
+            }
+        }
+    }
+
+    fn used_declaration_names(declaration: &VariableDeclaration, names: &mut HashSet<String>) {
+        declaration
+            .variable_type
+            .iter()
+            .for_each(|ty| Self::used_type_names(ty, names));
+        declaration
+            .init_expr
+            .iter()
+            .for_each(|expr| Self::used_expr_names(expr, names));
+    }
+
+    fn used_function_names(function: &FunctionStatement, names: &mut HashSet<String>) {
+        function
+            .args
+            .iter()
+            .flatten()
+            .for_each(|(_, ty)| Self::used_type_names(ty, names));
+        Self::used_type_names(&function.return_type, names);
+        names.extend(
+            function
+                .type_bounds
+                .iter()
+                .map(|(_, interface)| interface.clone()),
+        );
+
+        if let Some(block) = &function.block {
+            Self::used_names(&block.statements, names);
+        }
+    }
+
+    fn used_expr_names(expr: &Expression, names: &mut HashSet<String>) {
+        match expr {
+            Expression::Literal(Literal::StructLiteral(literal)) => {
+                names.insert(literal.type_name.clone());
+                literal
+                    .type_args
+                    .iter()
+                    .for_each(|ty| Self::used_type_names(ty, names));
+                literal
+                    .expressions
+                    .iter()
+                    .for_each(|expr| Self::used_expr_names(expr, names));
+            }
+            Expression::Literal(Literal::Identifier(identifier)) => {
+                names.insert(identifier.name.clone());
+            }
+            Expression::Literal(_) | Expression::Null(_) => {}
+            Expression::Binary(
+                Binary::Plus(l, r)
+                | Binary::Minus(l, r)
+                | Binary::Multiply(l, r)
+                | Binary::Divide(l, r)
+                | Binary::Modulo(l, r),
+            )
+            | Expression::BinaryLogic(
+                BinaryLogic::And(l, r)
+                | BinaryLogic::Or(l, r)
+                | BinaryLogic::Equal(l, r)
+                | BinaryLogic::NotEqual(l, r)
+                | BinaryLogic::More(l, r)
+                | BinaryLogic::Less(l, r)
+                | BinaryLogic::MoreEqual(l, r)
+                | BinaryLogic::LessEqual(l, r),
+            ) => {
+                Self::used_expr_names(l, names);
+                Self::used_expr_names(r, names);
+            }
+            Expression::Unary(Unary::Not(_, e) | Unary::Negate(_, e)) => {
+                Self::used_expr_names(e, names)
+            }
+            Expression::Group(g) => Self::used_expr_names(&g.inner_expression, names),
+            Expression::Call(call) => {
+                names.insert(call.name.clone());
+                call.type_args
+                    .iter()
+                    .for_each(|ty| Self::used_type_names(ty, names));
+                call.args
+                    .iter()
+                    .flatten()
+                    .for_each(|expr| Self::used_expr_names(expr, names));
+            }
+            Expression::ArrayAccess(a) => {
+                Self::used_expr_names(&a.identifier, names);
+                Self::used_expr_names(&a.index, names);
+            }
+            Expression::AddressOf(a) => Self::used_expr_names(&a.identifier, names),
+            Expression::DeReference(d) => Self::used_expr_names(&d.identifier, names),
+            Expression::MemberAccess(m) => {
+                Self::used_expr_names(&m.object, names);
+                m.args
+                    .iter()
+                    .flatten()
+                    .for_each(|expr| Self::used_expr_names(expr, names));
+            }
+            Expression::Conditional(c) => {
+                Self::used_expr_names(&c.condition, names);
+                Self::used_expr_names(&c.then_expr, names);
+                Self::used_expr_names(&c.else_expr, names);
+            }
+            Expression::ArrayLiteral(a) => a
+                .elements
+                .iter()
+                .for_each(|expr| Self::used_expr_names(expr, names)),
+            Expression::ArrayRepeat(a) => Self::used_expr_names(&a.value, names),
+            // `module::item`, `Enum::Variant` or `Union::Variant(payload)`
+            Expression::ModuleAccess(access) => {
+                names.insert(access.module.clone());
+                Self::used_expr_names(&access.item, names);
+            }
+            Expression::Cast(c) => {
+                Self::used_type_names(&c.target, names);
+                Self::used_expr_names(&c.expr, names);
+            }
+            // Created by the type checker
+            Expression::EnumVariant(_) | Expression::UnionVariant(_) => {}
+        }
+    }
+
+    /// The structs, enums, unions and interfaces are all parsed as struct
+    /// types
+    fn used_type_names(ty: &ValueType, names: &mut HashSet<String>) {
+        match ty {
+            ValueType::Struct(name) | ValueType::Dyn(name) => {
+                names.insert(name.clone());
+            }
+            ValueType::Generic(name, type_args) => {
+                names.insert(name.clone());
+                type_args
+                    .iter()
+                    .for_each(|ty| Self::used_type_names(ty, names));
+            }
+            ValueType::Pointer(inner) => Self::used_type_names(inner, names),
+            ValueType::Array(array) => Self::used_type_names(&array.array_type, names),
+            _ => {}
+        }
+    }
+
+    /// Declares the structs, enums, unions and interfaces exported by the
+    /// module at `path` and by the modules it imports, the dependencies are
+    /// declared first.
+    /// Modules not imported directly get the namespace of their importer
+    /// followed by theirs, re-exported ones get the scope of their importer.
+    fn declare_types(
+        &mut self,
+        path: &PathBuf,
+        scopes: &mut HashMap<PathBuf, ModuleScope>,
+        visited: &mut HashSet<PathBuf>,
+        file_name: &str,
+    ) {
+        if !visited.insert(path.clone()) {
+            return;
+        }
+
+        let dependencies = self.modules[path].imports.clone();
+
+        for dependency in &dependencies {
+            if !scopes.contains_key(&dependency.path) {
+                let scope = if dependency.is_exported {
+                    scopes[path].clone()
+                } else {
+                    ModuleScope {
+                        namespace: format!("{}::{}", scopes[path].namespace, dependency.namespace),
+                        items: None,
+                    }
+                };
+
+                scopes.insert(dependency.path.clone(), scope);
+            }
+
+            self.declare_types(&dependency.path, scopes, visited, file_name);
+        }
+
+        let mut declared = Vec::new();
+        let mut declared_enums = Vec::new();
+        let mut declared_unions = Vec::new();
+        let mut declared_impls = Vec::new();
+        let mut declared_interfaces = Vec::new();
+        let module = &self.modules[path];
+        for stmt in &module.statements {
+            // The methods of an exported struct are exported with it, unless
+            // they implement an interface the module doesn't export
+            if let Statement::Impl(i) = stmt {
+                let is_private_interface =
+                    module
+                        .statements
+                        .iter()
+                        .any(|stmt| match (stmt, &i.interface) {
+                            (Statement::Interface(s), Some(interface)) => {
+                                &s.type_name == interface && !s.exported
+                            }
+                            _ => false,
+                        });
+
+                if module.exported_types.contains(&i.type_name) && !is_private_interface {
+                    declared_impls.push(ImplStatement {
+                        type_name: scopes[path].declared_name(&i.type_name),
+                        interface: i.interface.as_ref().map(|interface| {
+                            self.qualify_type(
+                                &ValueType::Struct(interface.clone()),
+                                path,
+                                scopes,
+                                &[],
+                            )
+                            .into_struct_type()
+                        }),
+                        methods: i
+                            .methods
+                            .iter()
+                            .map(|method| {
+                                self.forward_declaration(
+                                    method,
+                                    method.callee.clone(),
+                                    Self::method_symbol(&module.name, &i.type_name, &method.callee),
+                                    path,
+                                    scopes,
+                                    file_name,
+                                )
+                            })
+                            .collect(),
+                        filename: file_name.to_string(),
+                        ..i.clone()
+                    });
+                }
+            }
+
+            if let Statement::Interface(i) = stmt {
+                if i.exported {
+                    // `Self` isn't a type of the module
+                    let type_params = ["Self".to_string()];
+                    let qualify =
+                        |ty: &ValueType| self.qualify_type(ty, path, scopes, &type_params);
+
+                    declared_interfaces.push(InterfaceStatement {
+                        type_name: scopes[path].declared_name(&i.type_name),
+                        methods: i
+                            .methods
+                            .iter()
+                            .map(|method| FunctionStatement {
+                                args: method.args.as_ref().map(|args| {
+                                    args.iter()
+                                        .map(|(name, ty)| (name.clone(), qualify(ty)))
+                                        .collect()
+                                }),
+                                return_type: qualify(&method.return_type),
+                                filename: file_name.to_string(),
+                                ..method.clone()
+                            })
+                            .collect(),
+                        exported: false,
+                        filename: file_name.to_string(),
+                        ..i.clone()
+                    });
+                }
+            }
+
+            if let Statement::Union(u) = stmt {
+                if u.exported {
+                    declared_unions.push(UnionStatement {
+                        type_name: scopes[path].declared_name(&u.type_name),
+                        variants: u
+                            .variants
+                            .iter()
+                            .map(|(name, payload, doc)| {
+                                (
+                                    name.clone(),
+                                    payload
+                                        .iter()
+                                        .map(|ty| self.qualify_type(ty, path, scopes, &[]))
+                                        .collect(),
+                                    doc.clone(),
+                                )
+                            })
+                            .collect(),
+                        ..u.clone()
+                    });
+                }
+            }
+
+            if let Statement::Enum(e) = stmt {
+                if e.exported {
+                    declared_enums.push(EnumStatement {
+                        type_name: scopes[path].declared_name(&e.type_name),
+                        ..e.clone()
+                    });
+                }
+            }
+
+            if let Statement::Struct(s) = stmt {
+                if s.exported {
+                    declared.push(StructStatement {
+                        type_name: scopes[path].declared_name(&s.type_name),
+                        fields: s
+                            .fields
+                            .iter()
+                            .map(|(name, ty, doc, default)| {
+                                (
+                                    name.clone(),
+                                    self.qualify_type(ty, path, scopes, &s.type_params),
+                                    doc.clone(),
+                                    default.clone(),
+                                )
+                            })
+                            .collect(),
+                        ..s.clone()
+                    })
+                }
+            }
+        }
+
+        self.declared_structs.extend(declared);
+        self.declared_enums.extend(declared_enums);
+        self.declared_unions.extend(declared_unions);
+        self.declared_impls.extend(declared_impls);
+        self.declared_interfaces.extend(declared_interfaces);
+    }
+
+    /// Declares the functions and globals exported by an imported module,
+    /// only the selected ones for `import { item } from`.
+    fn declare_items(
+        &mut self,
+        import: &ModuleImport,
+        scopes: &HashMap<PathBuf, ModuleScope>,
+        file_name: &str,
+    ) -> ImportResolverReturn {
+        let scope = &scopes[&import.path];
+        let mut functions = Vec::new();
+        let mut globals = Vec::new();
+        // Copied bodies of the generic functions with the path of their module
+        let mut generic_bodies = Vec::new();
+
+        for (path, stmt) in self.exported_items(&import.path) {
+            let module = &self.modules[&path];
+            let qualify = |ty: &ValueType, type_params: &[String]| {
+                self.qualify_type(ty, &path, scopes, type_params)
+            };
+
+            let declared_name = |name: &String| match &import.items {
+                Some(items) if !items.contains(name) => None,
+                _ => Some(scope.declared_name(name)),
+            };
+
             match stmt {
                 Statement::Function(f) => {
-                    let declaration = FunctionStatement {
-                        callee: f.callee,
-                        args: f.args,
-                        block: None, // forward declaration
-                        return_type: f.return_type,
+                    let callee = match declared_name(&f.callee) {
+                        Some(callee) => callee,
+                        None => continue,
+                    };
+
+                    // Functions without a body are C functions, their symbol isn't mangled
+                    let mangled_name = if f.block.is_some() {
+                        Self::mangle(&module.name, &f.callee)
+                    } else {
+                        f.callee.clone()
+                    };
+
+                    let declaration =
+                        self.forward_declaration(f, callee, mangled_name, &path, scopes, file_name);
+                    if let (false, Some(block)) = (f.type_params.is_empty(), &declaration.block) {
+                        generic_bodies.push((path.clone(), block.statements.clone()));
+                    }
+                    functions.push(declaration);
+                }
+                Statement::VariableDeclaration(v) => {
+                    let identifier = match declared_name(&v.identifier) {
+                        Some(identifier) => identifier,
+                        None => continue,
+                    };
+
+                    globals.push(VariableDeclaration {
+                        identifier,
+                        variable_type: v.variable_type.as_ref().map(|ty| qualify(ty, &[])),
+                        init_expr: None, // extern declaration
                         is_exported: false,
-                        line: f.line,
-                        column: f.column,
-                        // Path is the actual module path
+                        mangled_name: Some(Self::mangle(&module.name, &v.identifier)),
                         filename: file_name.to_string(),
-                    };
+                        ..v.clone()
+                    });
+                }
+                _ => continue,
+            }
+        }
+
+        self.declared_functions.extend(functions);
+        self.declared_globals.extend(globals);
 
-                    if f.is_exported {
-                        self.declared_functions.push(declaration)
+        for (path, body) in generic_bodies {
+            self.declare_module_items(&path, &body, scopes, file_name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Hidden names of the functions and globals of the module at `path`, the
+    /// generic bodies copied in the importers name them with it. It's their
+    /// mangled symbol, it can't be written in the source.
+    fn module_items(&self, path: &PathBuf) -> HashMap<String, String> {
+        let module = &self.modules[path];
+
+        module
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Function(f) => Some(&f.callee),
+                Statement::VariableDeclaration(v) => Some(&v.identifier),
+                _ => None,
+            })
+            .map(|name| (name.clone(), Self::mangle(&module.name, name)))
+            .collect()
+    }
+
+    /// Declares the functions and globals of the module at `path` named by
+    /// the copied generic body `stmts` with their hidden name, the generic
+    /// ones bring the items named by their own body.
+    fn declare_module_items(
+        &mut self,
+        path: &PathBuf,
+        stmts: &[Statement],
+        scopes: &HashMap<PathBuf, ModuleScope>,
+        file_name: &str,
+    ) -> ImportResolverReturn {
+        let mut names = HashSet::new();
+        Self::used_names(stmts, &mut names);
+
+        let module = &self.modules[path];
+        let mut functions = Vec::new();
+        let mut globals = Vec::new();
+
+        for stmt in &module.statements {
+            match stmt {
+                Statement::Function(f) => {
+                    let hidden_name = Self::mangle(&module.name, &f.callee);
+                    if !names.contains(&hidden_name)
+                        || self
+                            .declared_functions
+                            .iter()
+                            .any(|declared| declared.callee == hidden_name)
+                    {
+                        continue;
                     }
+
+                    // Functions without a body are C functions, their symbol isn't mangled
+                    let mangled_name = if f.block.is_some() {
+                        hidden_name.clone()
+                    } else {
+                        f.callee.clone()
+                    };
+
+                    functions.push(self.forward_declaration(
+                        f,
+                        hidden_name,
+                        mangled_name,
+                        path,
+                        scopes,
+                        file_name,
+                    ));
                 }
-                Statement::Struct(s) => {
-                    if s.exported {
-                        self.declared_structs.push(s)
+                Statement::VariableDeclaration(v) => {
+                    let hidden_name = Self::mangle(&module.name, &v.identifier);
+                    if !names.contains(&hidden_name)
+                        || self
+                            .declared_globals
+                            .iter()
+                            .any(|declared| declared.identifier == hidden_name)
+                    {
+                        continue;
                     }
+
+                    // Exported by its module, see `generic_dependencies`
+                    let variable_type = match &v.variable_type {
+                        Some(ty) => self.qualify_type(ty, path, scopes, &[]),
+                        None => {
+                            return Err(format!(
+                                "{}:{}:{} Error: Global '{}' is used by a generic function, it must have a type annotation.",
+                                v.filename, v.line, v.column, v.identifier
+                            ))
+                        }
+                    };
+
+                    globals.push(VariableDeclaration {
+                        identifier: hidden_name.clone(),
+                        variable_type: Some(variable_type),
+                        init_expr: None, // extern declaration
+                        is_exported: false,
+                        mangled_name: Some(hidden_name),
+                        filename: file_name.to_string(),
+                        ..v.clone()
+                    });
                 }
                 _ => continue,
             }
         }
 
+        let generic_bodies: Vec<Vec<Statement>> = functions
+            .iter()
+            .filter(|f| !f.type_params.is_empty())
+            .filter_map(|f| f.block.as_ref().map(|block| block.statements.clone()))
+            .collect();
+
+        self.declared_functions.extend(functions);
+        self.declared_globals.extend(globals);
+
+        for body in generic_bodies {
+            self.declare_module_items(path, &body, scopes, file_name)?;
+        }
+
         Ok(())
     }
+
+    /// Functions and globals of the module named by the bodies of its exported
+    /// generic functions. They're instantiated by the importers, which must be
+    /// able to link them.
+    fn generic_dependencies(stmts: &[Statement]) -> HashSet<String> {
+        let functions: HashMap<&String, &FunctionStatement> = stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Function(f) => Some((&f.callee, f)),
+                _ => None,
+            })
+            .collect();
+        let globals: HashSet<&String> = stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::VariableDeclaration(v) => Some(&v.identifier),
+                _ => None,
+            })
+            .collect();
+
+        let mut dependencies = HashSet::new();
+        let mut pending: Vec<&FunctionStatement> = functions
+            .values()
+            .filter(|f| f.is_exported && !f.type_params.is_empty())
+            .copied()
+            .collect();
+
+        while let Some(generic) = pending.pop() {
+            let mut names = HashSet::new();
+            Self::used_function_names(generic, &mut names);
+
+            for name in names {
+                if !functions.contains_key(&name) && !globals.contains(&name) {
+                    continue;
+                }
+
+                if let Some(f) = functions.get(&name) {
+                    if !f.type_params.is_empty() && !dependencies.contains(&name) {
+                        pending.push(f);
+                    }
+                }
+                dependencies.insert(name);
+            }
+        }
+
+        dependencies
+    }
+
+    /// Declares the function `f` of the module at `path` as `callee`, its
+    /// types are qualified for the module being resolved.
+    fn forward_declaration(
+        &self,
+        f: &FunctionStatement,
+        callee: String,
+        mangled_name: String,
+        path: &PathBuf,
+        scopes: &HashMap<PathBuf, ModuleScope>,
+        file_name: &str,
+    ) -> FunctionStatement {
+        let qualify = |ty: &ValueType| self.qualify_type(ty, path, scopes, &f.type_params);
+
+        // Generic functions are instantiated by their importers
+        let block = if f.type_params.is_empty() {
+            None // forward declaration
+        } else {
+            f.block.clone().map(|mut block| {
+                // The recursive calls name the declared function
+                let mut items = self.module_items(path);
+                items.insert(f.callee.clone(), callee.clone());
+                f.args.iter().flatten().for_each(|(name, _)| {
+                    items.remove(name);
+                });
+
+                Self::qualify_body(&mut block.statements, &qualify, &mut items);
+                block
+            })
+        };
+
+        FunctionStatement {
+            callee,
+            args: f.args.as_ref().map(|args| {
+                args.iter()
+                    .map(|(name, ty)| (name.clone(), qualify(ty)))
+                    .collect()
+            }),
+            block,
+            return_type: qualify(&f.return_type),
+            type_params: f.type_params.clone(),
+            type_bounds: f
+                .type_bounds
+                .iter()
+                .map(|(param, interface)| {
+                    (
+                        param.clone(),
+                        qualify(&ValueType::Struct(interface.clone())).into_struct_type(),
+                    )
+                })
+                .collect(),
+            is_exported: false,
+            doc: f.doc.clone(),
+            mangled_name: Some(mangled_name),
+            line: f.line,
+            column: f.column,
+            // Path is the actual module path
+            filename: file_name.to_string(),
+            span: f.span,
+        }
+    }
 }
@@ -1,11 +1,15 @@
-use inkwell::types::{AnyType, BasicType};
-use inkwell::values::{AggregateValue, AnyValue, AnyValueEnum, BasicValue, BasicValueEnum};
+use inkwell::types::BasicType;
+use inkwell::values::{
+    AggregateValue, AnyValue, AnyValueEnum, BasicMetadataValueEnum, BasicValue, BasicValueEnum,
+    CallableValue,
+};
 use inkwell::{AddressSpace, FloatPredicate, IntPredicate};
 
 use crate::generation::ir_generator::IRGenerator;
 use crate::parser::visitors::{
-    AddressOf, ArrayAccess, Binary, BinaryLogic, Call, DeReference, Expression, ExpressionVisitor,
-    Group, Literal, MemberAccess, StructLiteral, Unary,
+    AddressOf, ArrayAccess, ArrayLiteral, ArrayRepeat, Binary, BinaryLogic, Call, Cast,
+    Conditional, DeReference, EnumVariant, Expression, ExpressionVisitor, Group, Literal,
+    MemberAccess, ModuleAccess, StructLiteral, Unary, UnionVariant,
 };
 use crate::type_system::typed::Typed;
 use crate::type_system::value_type::ValueType;
@@ -54,8 +58,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                 }
             }
             Literal::StringLiteral(s) => self
-                .builder
-                .build_global_string_ptr(s.value.as_str(), "string_literal")
+                .build_string_literal(s.value.as_str(), "string_literal")
                 .as_any_value_enum(),
             Literal::StructLiteral(s) => self.visit_struct_literal(s),
         }
@@ -116,10 +119,14 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                 let right = self.visit_expr(r);
 
                 match left {
-                    AnyValueEnum::IntValue(left) => self
+                    AnyValueEnum::IntValue(left) if l.get_type().is_signed() => self
                         .builder
                         .build_int_signed_div(left, self.get_int_value(right), "tmpidiv")
                         .as_any_value_enum(),
+                    AnyValueEnum::IntValue(left) => self
+                        .builder
+                        .build_int_unsigned_div(left, self.get_int_value(right), "tmpudiv")
+                        .as_any_value_enum(),
                     AnyValueEnum::FloatValue(left) => self
                         .builder
                         .build_float_div(left, self.get_float_value(right), "tmpfdiv")
@@ -132,10 +139,14 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                 let right = self.visit_expr(r);
 
                 match left {
-                    AnyValueEnum::IntValue(left) => self
+                    AnyValueEnum::IntValue(left) if l.get_type().is_signed() => self
                         .builder
                         .build_int_signed_rem(left, self.get_int_value(right), "tmpimul")
                         .as_any_value_enum(),
+                    AnyValueEnum::IntValue(left) => self
+                        .builder
+                        .build_int_unsigned_rem(left, self.get_int_value(right), "tmpurem")
+                        .as_any_value_enum(),
                     AnyValueEnum::FloatValue(left) => self
                         .builder
                         .build_float_rem(left, self.get_float_value(right), "tmpfmul")
@@ -232,7 +243,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                     AnyValueEnum::IntValue(val) => self
                         .builder
                         .build_int_compare(
-                            IntPredicate::SGT,
+                            Self::get_int_predicate(l, IntPredicate::SGT, IntPredicate::UGT),
                             val,
                             self.get_int_value(right),
                             "tmpimore",
@@ -258,7 +269,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                     AnyValueEnum::IntValue(val) => self
                         .builder
                         .build_int_compare(
-                            IntPredicate::SLT,
+                            Self::get_int_predicate(l, IntPredicate::SLT, IntPredicate::ULT),
                             val,
                             self.get_int_value(right),
                             "tmpiless",
@@ -284,7 +295,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                     AnyValueEnum::IntValue(val) => self
                         .builder
                         .build_int_compare(
-                            IntPredicate::SGE,
+                            Self::get_int_predicate(l, IntPredicate::SGE, IntPredicate::UGE),
                             val,
                             self.get_int_value(right),
                             "tmpimoreequal",
@@ -310,7 +321,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                     AnyValueEnum::IntValue(val) => self
                         .builder
                         .build_int_compare(
-                            IntPredicate::SLE,
+                            Self::get_int_predicate(l, IntPredicate::SLE, IntPredicate::ULE),
                             val,
                             self.get_int_value(right),
                             "tmpilessequal",
@@ -333,7 +344,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
 
     fn visit_unary(&mut self, unary: &Unary) -> AnyValueEnum<'a> {
         match unary {
-            Unary::Not(expr) => {
+            Unary::Not(_, expr) => {
                 let expr = self.visit_expr(expr);
 
                 match expr {
@@ -343,7 +354,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                     _ => panic!("Cannot apply not on non Number values! Type checker failed?"),
                 }
             }
-            Unary::Negate(expr) => {
+            Unary::Negate(_, expr) => {
                 let expr = self.visit_expr(expr);
 
                 match expr {
@@ -362,16 +373,12 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
     }
 
     fn visit_call(&mut self, call_expr: &Call) -> AnyValueEnum<'a> {
-        if let None = self.module.get_function(&call_expr.name) {
-            panic!("Call to a function that is not declared.");
-        }
-
-        let fn_call = self.module.get_function(&call_expr.name).unwrap();
-        let mut args_values = Vec::with_capacity(if call_expr.args.is_some() {
-            call_expr.args.as_ref().unwrap().len()
-        } else {
-            0
-        });
+        let mut args_values: Vec<BasicMetadataValueEnum> =
+            Vec::with_capacity(if call_expr.args.is_some() {
+                call_expr.args.as_ref().unwrap().len()
+            } else {
+                0
+            });
 
         if call_expr.args.is_some() {
             for (i, arg) in call_expr.args.as_ref().unwrap().iter().enumerate() {
@@ -400,20 +407,43 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                             v.into()
                         }
                     }
-                    AnyValueEnum::ArrayValue(v) => self
-                        .builder
-                        .build_bitcast(
-                            v,
-                            self.get_ptr_type(&v.get_type().get_element_type().as_any_type_enum()),
-                            "arg_array_ptr_cast",
-                        )
-                        .into(),
+                    // Array literals are passed as a pointer to their first element
+                    AnyValueEnum::ArrayValue(v) => self.spill_array_value(v).into(),
                     AnyValueEnum::StructValue(v) => v.into(),
                     _ => panic!(),
                 });
             }
         }
 
+        // `Interface::method` called on a `dyn Interface` receiver is
+        // dispatched through its vtable
+        let dyn_method = match call_expr.args.as_deref() {
+            Some([receiver, ..]) => match receiver.get_type() {
+                ValueType::Dyn(interface) => call_expr
+                    .name
+                    .strip_prefix(format!("{}::", interface).as_str())
+                    .map(|method| (interface.clone(), method.to_string())),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let fn_call: CallableValue = match dyn_method {
+            Some((interface, method)) => {
+                let receiver = match args_values[0] {
+                    BasicMetadataValueEnum::StructValue(v) => v,
+                    _ => unreachable!("A dyn value is a struct"),
+                };
+                let (data, method_ptr) = self.load_dyn_method(&interface, &method, receiver);
+                args_values[0] = data.into();
+                CallableValue::try_from(method_ptr).unwrap()
+            }
+            None => match self.get_function_by_name(&call_expr.name) {
+                Some(f) => f.into(),
+                None => panic!("Call to a function that is not declared."),
+            },
+        };
+
         let value = self
             .builder
             .build_call(fn_call, args_values.as_slice(), "tmp_call")
@@ -429,21 +459,21 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
     }
 
     fn visit_array_access(&mut self, call_expr: &ArrayAccess) -> AnyValueEnum<'a> {
+        if let Expression::ArrayAccess(_) = *call_expr.identifier {
+            let offset_ptr = self.get_array_element_pointer_value(call_expr);
+            return self
+                .builder
+                .build_load(offset_ptr, "load_nested_array")
+                .into();
+        }
+
         let expr = self.visit_expr(&call_expr.index);
         let ptr = self.visit_expr(&call_expr.identifier);
         let value = self.get_int_value(expr);
 
-        // If the array is passed as a function argument it is accessed as array ptr
-        if ptr.get_type().is_array_type() {
-            let ty = ptr
-                .get_type()
-                .into_array_type()
-                .get_element_type()
-                .ptr_type(inkwell::AddressSpace::Generic);
-
-            let array_ptr =
-                self.builder
-                    .build_pointer_cast(ptr.into_pointer_value(), ty, "array_cast");
+        // Array values (e.g literals) are stored on the stack to be indexed
+        if let AnyValueEnum::ArrayValue(array) = ptr {
+            let array_ptr = self.spill_array_value(array);
 
             let offset_ptr = unsafe {
                 self.builder
@@ -491,8 +521,7 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
         match &*address_of.identifier {
             Expression::Literal(l) => match l {
                 Literal::StringLiteral(sl) => self
-                    .builder
-                    .build_global_string_ptr(sl.value.as_str(), "addrof_string_literal")
+                    .build_string_literal(sl.value.as_str(), "addrof_string_literal")
                     .as_any_value_enum(),
                 Literal::Identifier(id) => {
                     self.variables.get(&id.name).unwrap().as_any_value_enum()
@@ -536,7 +565,15 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                 .ptr_type(AddressSpace::Generic)
                 .const_zero()
                 .as_any_value_enum(),
-            // Expression::ModuleAccess(_) => todo!(),
+            Expression::Conditional(_) => unreachable!(),
+            Expression::ArrayLiteral(_) | Expression::ArrayRepeat(_) => unreachable!(),
+            Expression::Cast(_) | Expression::EnumVariant(_) | Expression::UnionVariant(_) => {
+                unreachable!()
+            }
+            Expression::ModuleAccess(ma) => self.visit_address_of_expression(&AddressOf {
+                identifier: ma.item.clone(),
+                ..address_of.clone()
+            }),
         }
     }
 
@@ -609,4 +646,223 @@ impl<'a> ExpressionVisitor<AnyValueEnum<'a>> for IRGenerator<'a> {
                 .as_any_value_enum()
         }
     }
+
+    fn visit_conditional(&mut self, conditional: &Conditional) -> AnyValueEnum<'a> {
+        let parent = self.current_fn.unwrap();
+
+        let false_const = self.context.bool_type().const_zero();
+        let condition = self.visit_expr(&conditional.condition);
+        let cond_instr = self.builder.build_int_compare(
+            IntPredicate::NE,
+            self.get_int_value(condition),
+            false_const,
+            "cond_condition",
+        );
+
+        let then_bb = self.context.append_basic_block(parent, "cond_then");
+        let else_bb = self.context.append_basic_block(parent, "cond_else");
+        let merge_bb = self.context.append_basic_block(parent, "cond_merge");
+
+        self.builder
+            .build_conditional_branch(cond_instr, then_bb, else_bb);
+
+        let ptr_type = match conditional.ty.as_ref().unwrap() {
+            ty @ (ValueType::Pointer(_) | ValueType::String) => {
+                Some(self.get_llvm_basic_type(ty).into_pointer_type())
+            }
+            _ => None,
+        };
+
+        // Branches may add blocks, the phi needs the ones they end in
+        self.builder.position_at_end(then_bb);
+        let then_value = self.visit_conditional_branch(&conditional.then_expr, ptr_type);
+        let then_end_bb = self.builder.get_insert_block().unwrap();
+        self.builder.build_unconditional_branch(merge_bb);
+
+        self.builder.position_at_end(else_bb);
+        let else_value = self.visit_conditional_branch(&conditional.else_expr, ptr_type);
+        let else_end_bb = self.builder.get_insert_block().unwrap();
+        self.builder.build_unconditional_branch(merge_bb);
+
+        self.builder.position_at_end(merge_bb);
+        let phi = self.builder.build_phi(then_value.get_type(), "cond_value");
+        phi.add_incoming(&[(&then_value, then_end_bb), (&else_value, else_end_bb)]);
+
+        phi.as_basic_value().as_any_value_enum()
+    }
+
+    fn visit_array_literal(&mut self, array_literal: &ArrayLiteral) -> AnyValueEnum<'a> {
+        let array_type = match array_literal.ty.as_ref().unwrap() {
+            ValueType::Array(a) => self.get_llvm_array_type(a),
+            _ => unreachable!("Array literal of a non array type! Type checker failed?"),
+        };
+
+        let mut elements = Vec::with_capacity(array_literal.elements.len());
+        for element in &array_literal.elements {
+            let value = self.visit_borrowed_expr(element);
+            elements.push(self.build_value_cast(value, array_type.get_element_type()));
+        }
+
+        self.build_array_value(array_type, elements.as_slice())
+            .as_any_value_enum()
+    }
+
+    fn visit_array_repeat(&mut self, array_repeat: &ArrayRepeat) -> AnyValueEnum<'a> {
+        let array_type = match array_repeat.ty.as_ref().unwrap() {
+            ValueType::Array(a) => self.get_llvm_array_type(a),
+            _ => unreachable!("Array literal of a non array type! Type checker failed?"),
+        };
+
+        let value = self.visit_expr(&array_repeat.value);
+        let element = self.build_value_cast(value, array_type.get_element_type());
+
+        self.build_array_value(array_type, vec![element; array_repeat.count].as_slice())
+            .as_any_value_enum()
+    }
+
+    fn visit_module_access(&mut self, module_access: &ModuleAccess) -> AnyValueEnum<'a> {
+        self.visit_expr(&module_access.item)
+    }
+
+    fn visit_cast(&mut self, cast: &Cast) -> AnyValueEnum<'a> {
+        let value = self.visit_expr(&cast.expr);
+        let from = cast.expr.get_type();
+        let target_type = self.get_llvm_basic_type(&cast.target);
+
+        match (&from, &cast.target) {
+            (ValueType::Pointer(inner), ValueType::Dyn(interface)) => self
+                .build_dyn_value(
+                    value.into_pointer_value(),
+                    &inner.into_struct_type(),
+                    interface,
+                )
+                .as_any_value_enum(),
+            (from, to) if from.is_float() && to.is_float() => self
+                .builder
+                .build_float_cast(
+                    self.get_float_value(value),
+                    target_type.into_float_type(),
+                    "cast_float",
+                )
+                .as_any_value_enum(),
+            (from, to) if from.is_float() && to.is_signed() => self
+                .builder
+                .build_float_to_signed_int(
+                    self.get_float_value(value),
+                    target_type.into_int_type(),
+                    "cast_float_to_int",
+                )
+                .as_any_value_enum(),
+            (from, _) if from.is_float() => self
+                .builder
+                .build_float_to_unsigned_int(
+                    self.get_float_value(value),
+                    target_type.into_int_type(),
+                    "cast_float_to_uint",
+                )
+                .as_any_value_enum(),
+            (from, to) if from.is_signed() && to.is_float() => self
+                .builder
+                .build_signed_int_to_float(
+                    self.get_int_value(value),
+                    target_type.into_float_type(),
+                    "cast_int_to_float",
+                )
+                .as_any_value_enum(),
+            (_, to) if to.is_float() => self
+                .builder
+                .build_unsigned_int_to_float(
+                    self.get_int_value(value),
+                    target_type.into_float_type(),
+                    "cast_uint_to_float",
+                )
+                .as_any_value_enum(),
+            (_, ValueType::Bool) => {
+                let value = self.get_int_value(value);
+                self.builder
+                    .build_int_compare(
+                        IntPredicate::NE,
+                        value,
+                        value.get_type().const_zero(),
+                        "cast_int_to_bool",
+                    )
+                    .as_any_value_enum()
+            }
+            (from, _) if value.is_int_value() => {
+                let value = self.get_int_value(value);
+                let target_type = target_type.into_int_type();
+                let (from_width, to_width) = (
+                    value.get_type().get_bit_width(),
+                    target_type.get_bit_width(),
+                );
+
+                if from_width > to_width {
+                    self.builder
+                        .build_int_truncate(value, target_type, "cast_int_truncate")
+                        .as_any_value_enum()
+                } else if from_width == to_width {
+                    value.as_any_value_enum()
+                } else if from.is_signed() {
+                    self.builder
+                        .build_int_s_extend(value, target_type, "cast_int_sext")
+                        .as_any_value_enum()
+                } else {
+                    self.builder
+                        .build_int_z_extend(value, target_type, "cast_int_zext")
+                        .as_any_value_enum()
+                }
+            }
+            _ => self
+                .builder
+                .build_pointer_cast(
+                    value.into_pointer_value(),
+                    target_type.into_pointer_type(),
+                    "cast_ptr",
+                )
+                .as_any_value_enum(),
+        }
+    }
+
+    fn visit_enum_variant(&mut self, enum_variant: &EnumVariant) -> AnyValueEnum<'a> {
+        self.context
+            .i64_type()
+            .const_int(enum_variant.value as u64, true)
+            .as_any_value_enum()
+    }
+
+    fn visit_union_variant(&mut self, union_variant: &UnionVariant) -> AnyValueEnum<'a> {
+        let union_type = union_variant.get_type();
+        let union_ptr = self.create_entry_block_alloca("union_variant", &union_type);
+
+        let tag_ptr = self
+            .builder
+            .build_struct_gep(union_ptr, 0, "union_tag")
+            .unwrap();
+        self.builder.build_store(
+            tag_ptr,
+            self.context
+                .i64_type()
+                .const_int(union_variant.tag as u64, false),
+        );
+
+        let payload_ptr =
+            self.get_union_payload_pointer(union_ptr, &union_variant.union_name, union_variant.tag);
+        let payload_type = payload_ptr.get_type().get_element_type().into_struct_type();
+
+        for (i, expr) in union_variant.payload.iter().enumerate() {
+            let value = self.visit_borrowed_expr(expr);
+            let field_type = payload_type.get_field_type_at_index(i as u32).unwrap();
+            let field_ptr = self
+                .builder
+                .build_struct_gep(payload_ptr, i as u32, "union_payload_field")
+                .unwrap();
+
+            self.builder
+                .build_store(field_ptr, self.build_value_cast(value, field_type));
+        }
+
+        self.builder
+            .build_load(union_ptr, "load_union_variant")
+            .as_any_value_enum()
+    }
 }
@@ -2,8 +2,10 @@ use core::panic;
 use std::{collections::HashMap, fmt::Debug};
 
 use crate::{
-    parser::visitors::{Expression, ExpressionVisitor, MemberAccess, Statement},
-    type_system::{types_table::TypeTable, value_type::ValueType},
+    parser::visitors::{
+        ArrayAccess, Expression, ExpressionVisitor, MemberAccess, Statement, VariableDeclaration,
+    },
+    type_system::{typed::Typed, types_table::TypeTable, value_type::ValueType},
 };
 
 use crate::parser::visitors::StatementVisitor;
@@ -13,10 +15,16 @@ use inkwell::{
     builder::Builder,
     context::Context,
     execution_engine::ExecutionEngine,
-    module::Module,
-    types::{AnyType, AnyTypeEnum, BasicType, BasicTypeEnum, PointerType, StructType},
-    values::{AnyValueEnum, FloatValue, FunctionValue, IntValue, PointerValue},
-    AddressSpace,
+    module::{Linkage, Module},
+    types::{
+        AnyType, AnyTypeEnum, BasicMetadataTypeEnum, BasicType, BasicTypeEnum, PointerType,
+        StructType,
+    },
+    values::{
+        AnyValueEnum, BasicValueEnum, FloatValue, FunctionValue, GlobalValue, IntValue,
+        PointerValue, StructValue,
+    },
+    AddressSpace, IntPredicate,
 };
 
 pub struct IRGenerator<'a> {
@@ -27,7 +35,8 @@ pub struct IRGenerator<'a> {
     pub(super) current_fn: Option<FunctionValue<'a>>,
     pub(super) variables: HashMap<String, PointerValue<'a>>,
     pub(super) struct_types: HashMap<String, StructType<'a>>,
-    pub(super) loop_bb_stack: Vec<BasicBlock<'a>>,
+    /// (continue target, break target) of the enclosing loops
+    pub(super) loop_bb_stack: Vec<(BasicBlock<'a>, BasicBlock<'a>)>,
     pub(super) has_branched: bool,
 }
 
@@ -63,11 +72,19 @@ impl<'a> IRGenerator<'a> {
             Statement::WhileStatement(while_stmt) => {
                 self.visit_while_statement(while_stmt);
             }
+            Statement::MatchStatement(match_stmt) => {
+                self.visit_match_statement(match_stmt);
+            }
             Statement::ForStatement(_) => unreachable!(),
             Statement::BreakStatement(_) => unreachable!(),
+            Statement::ContinueStatement(_) => unreachable!(),
             Statement::Struct(struct_stmt) => {
                 self.visit_struct_statement(struct_stmt);
             }
+            Statement::Enum(_) => unreachable!(),
+            Statement::Union(_) => unreachable!(),
+            Statement::Impl(_) => unreachable!(),
+            Statement::Interface(_) => unreachable!(),
             Statement::Import(_) => todo!(),
         };
 
@@ -96,6 +113,20 @@ impl<'a> IRGenerator<'a> {
                 Statement::Struct(s) => {
                     self.visit_struct_statement(s);
                 }
+                // The variants are constants, enums have no code
+                Statement::Enum(_) => {}
+                Statement::Union(u) => {
+                    self.visit_union_statement(u);
+                }
+                Statement::Impl(i) => {
+                    self.visit_impl_statement(i);
+                }
+                Statement::Interface(i) => {
+                    self.visit_interface_statement(i);
+                }
+                Statement::VariableDeclaration(var_dec) => {
+                    self.generate_global(var_dec);
+                }
                 _ => {
                     return Some(self.generate_ir_anonymous(stmt));
                 }
@@ -105,6 +136,86 @@ impl<'a> IRGenerator<'a> {
         None
     }
 
+    /// Emits a module level variable. The type checker only allows constant
+    /// init expressions, the builder folds them without emitting instructions.
+    fn generate_global(&mut self, var_dec: &VariableDeclaration) {
+        let symbol = var_dec.mangled_name.as_ref().unwrap_or(&var_dec.identifier);
+
+        // A global imported in several namespaces is declared once
+        if var_dec.init_expr.is_none() {
+            if let Some(global) = self.module.get_global(symbol) {
+                self.variables
+                    .insert(var_dec.identifier.to_string(), global.as_pointer_value());
+                return;
+            }
+        }
+
+        let global_type = self.get_llvm_basic_type(var_dec.variable_type.as_ref().unwrap());
+        let global =
+            self.module
+                .add_global(global_type, Some(AddressSpace::Generic), symbol.as_str());
+        global.set_constant(var_dec.is_const);
+
+        if let Some(init_expr) = &var_dec.init_expr {
+            let init_value = self.visit_borrowed_expr(init_expr);
+            global.set_initializer(&self.build_global_initializer(init_value, global_type));
+            global.set_linkage(if var_dec.is_exported {
+                Linkage::External
+            } else {
+                Linkage::Internal
+            });
+        } else {
+            // Defined in another module
+            global.set_linkage(Linkage::External);
+        }
+
+        self.variables
+            .insert(var_dec.identifier.to_string(), global.as_pointer_value());
+    }
+
+    /// Casts the folded init expression of a global to the global type, arrays
+    /// are filled with the init value like local ones.
+    fn build_global_initializer(
+        &self,
+        value: AnyValueEnum<'a>,
+        global_type: BasicTypeEnum<'a>,
+    ) -> BasicValueEnum<'a> {
+        match (value, global_type) {
+            (AnyValueEnum::IntValue(v), BasicTypeEnum::IntType(t)) => v.const_cast(t, true).into(),
+            (AnyValueEnum::PointerValue(v), BasicTypeEnum::PointerType(t)) => {
+                v.const_cast(t).into()
+            }
+            // Array literal
+            (AnyValueEnum::ArrayValue(v), BasicTypeEnum::ArrayType(_)) => v.into(),
+            (_, BasicTypeEnum::ArrayType(t)) => {
+                let size = t.len() as usize;
+
+                match self.build_global_initializer(value, t.get_element_type()) {
+                    BasicValueEnum::IntValue(v) => v.get_type().const_array(&vec![v; size]).into(),
+                    BasicValueEnum::FloatValue(v) => {
+                        v.get_type().const_array(&vec![v; size]).into()
+                    }
+                    BasicValueEnum::PointerValue(v) => {
+                        v.get_type().const_array(&vec![v; size]).into()
+                    }
+                    _ => unreachable!("Array of a forbidden type."),
+                }
+            }
+            _ => self.into_basic_value(value),
+        }
+    }
+
+    /// Function called with `name` in the source, functions defined in a
+    /// module and imported ones have a mangled symbol.
+    pub(super) fn get_function_by_name(&self, name: &str) -> Option<FunctionValue<'a>> {
+        match self.type_table.find_function_type(name) {
+            Some(f) => self
+                .module
+                .get_function(f.mangled_name.as_ref().unwrap_or(&f.callee)),
+            None => self.module.get_function(name),
+        }
+    }
+
     pub fn print_code(&self) {
         println!("{}", self.module.print_to_string().to_string());
     }
@@ -122,6 +233,13 @@ impl<'a> IRGenerator<'a> {
             Expression::AddressOf(addr_of) => self.visit_address_of_expression(addr_of),
             Expression::DeReference(deref) => self.visit_dereference_expression(deref),
             Expression::MemberAccess(member_access) => self.visit_member_access(member_access),
+            Expression::Conditional(conditional) => self.visit_conditional(conditional),
+            Expression::ArrayLiteral(array_literal) => self.visit_array_literal(array_literal),
+            Expression::ArrayRepeat(array_repeat) => self.visit_array_repeat(array_repeat),
+            Expression::ModuleAccess(module_access) => self.visit_module_access(module_access),
+            Expression::Cast(cast) => self.visit_cast(cast),
+            Expression::EnumVariant(enum_variant) => self.visit_enum_variant(enum_variant),
+            Expression::UnionVariant(union_variant) => self.visit_union_variant(union_variant),
         }
     }
 
@@ -138,6 +256,13 @@ impl<'a> IRGenerator<'a> {
             Expression::AddressOf(addr_of) => self.visit_address_of_expression(addr_of),
             Expression::DeReference(deref) => self.visit_dereference_expression(deref),
             Expression::MemberAccess(member_access) => self.visit_member_access(member_access),
+            Expression::Conditional(conditional) => self.visit_conditional(conditional),
+            Expression::ArrayLiteral(array_literal) => self.visit_array_literal(array_literal),
+            Expression::ArrayRepeat(array_repeat) => self.visit_array_repeat(array_repeat),
+            Expression::ModuleAccess(module_access) => self.visit_module_access(module_access),
+            Expression::Cast(cast) => self.visit_cast(cast),
+            Expression::EnumVariant(enum_variant) => self.visit_enum_variant(enum_variant),
+            Expression::UnionVariant(union_variant) => self.visit_union_variant(union_variant),
         }
     }
 
@@ -148,6 +273,20 @@ impl<'a> IRGenerator<'a> {
         }
     }
 
+    /// Integers are compared as signed or unsigned values depending on the type
+    /// of the compared expressions
+    pub fn get_int_predicate(
+        operand: &Expression,
+        signed: IntPredicate,
+        unsigned: IntPredicate,
+    ) -> IntPredicate {
+        if operand.get_type().is_signed() {
+            signed
+        } else {
+            unsigned
+        }
+    }
+
     pub fn get_float_value(&self, value: AnyValueEnum<'a>) -> FloatValue<'a> {
         match value {
             AnyValueEnum::FloatValue(value) => value,
@@ -155,6 +294,36 @@ impl<'a> IRGenerator<'a> {
         }
     }
 
+    pub fn into_basic_value(&self, value: AnyValueEnum<'a>) -> BasicValueEnum<'a> {
+        match value {
+            AnyValueEnum::ArrayValue(value) => value.into(),
+            AnyValueEnum::IntValue(value) => value.into(),
+            AnyValueEnum::FloatValue(value) => value.into(),
+            AnyValueEnum::PointerValue(value) => value.into(),
+            AnyValueEnum::StructValue(value) => value.into(),
+            AnyValueEnum::VectorValue(value) => value.into(),
+            _ => panic!("Expected a basic value to unpack!"),
+        }
+    }
+
+    /// Generates one branch of a conditional expression. Pointers are cast to
+    /// `ptr_type`, the type of the whole expression, since a branch can be null.
+    pub fn visit_conditional_branch(
+        &mut self,
+        expr: &Box<Expression>,
+        ptr_type: Option<PointerType<'a>>,
+    ) -> BasicValueEnum<'a> {
+        let value = self.visit_expr(expr);
+
+        match (value, ptr_type) {
+            (AnyValueEnum::PointerValue(ptr), Some(ptr_type)) => self
+                .builder
+                .build_pointer_cast(ptr, ptr_type, "cond_ptr_cast")
+                .into(),
+            _ => self.into_basic_value(value),
+        }
+    }
+
     // allocate a value on the stack with a associated name and type,
     // in the entry block of the function
     pub fn create_entry_block_alloca(&self, name: &str, var_type: &ValueType) -> PointerValue<'a> {
@@ -177,11 +346,28 @@ impl<'a> IRGenerator<'a> {
             ValueType::Real => self.builder.build_alloca(self.context.f64_type(), name),
             ValueType::Bool => self.builder.build_alloca(self.context.bool_type(), name),
             ValueType::Char => self.builder.build_alloca(self.context.i8_type(), name),
+            ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64
+            | ValueType::F32
+            | ValueType::Enum(_) => self
+                .builder
+                .build_alloca(self.get_llvm_basic_type(var_type), name),
             ValueType::String => self
                 .builder
                 .build_alloca(self.context.i8_type().ptr_type(AddressSpace::Generic), name),
             ValueType::Array(arr) => match *arr.array_type {
-                ValueType::Array(_) => todo!(),
+                ValueType::Array(_)
+                | ValueType::Pointer(_)
+                | ValueType::Struct(_)
+                | ValueType::Union(_)
+                | ValueType::Dyn(_) => self
+                    .builder
+                    .build_alloca(self.get_concrete_array_type(arr), name),
                 ValueType::Number => self.builder.build_alloca(
                     self.context.i64_type().ptr_type(AddressSpace::Generic),
                     name,
@@ -198,19 +384,34 @@ impl<'a> IRGenerator<'a> {
                     .builder
                     .build_alloca(self.context.i8_type().ptr_type(AddressSpace::Generic), name),
                 ValueType::Char => self.builder.build_alloca(self.context.i8_type(), name),
+                ref ty @ (ValueType::I8
+                | ValueType::I16
+                | ValueType::I32
+                | ValueType::U8
+                | ValueType::U16
+                | ValueType::U32
+                | ValueType::U64
+                | ValueType::F32
+                | ValueType::Enum(_)) => self.builder.build_alloca(
+                    self.get_llvm_basic_type(ty).ptr_type(AddressSpace::Generic),
+                    name,
+                ),
                 ValueType::Function => todo!(),
                 ValueType::Void => unreachable!(),
-                ValueType::Pointer(_) => todo!(),
+                ValueType::Generic(..) => {
+                    unreachable!("Generic types are instantiated by the type checker")
+                }
                 ValueType::Null => todo!(),
-                ValueType::Struct(_) => todo!(),
             },
             ValueType::Pointer(ptr_ty) => self
                 .builder
                 .build_alloca(self.get_ptr_type(&self.get_llvm_type(ptr_ty)), name),
-            ValueType::Struct(s) => self.builder.build_alloca(
-                self.struct_types.get(s).unwrap().as_basic_type_enum(),
-                "struct_alloca",
-            ),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => {
+                self.builder.build_alloca(
+                    self.struct_types.get(s).unwrap().as_basic_type_enum(),
+                    "struct_alloca",
+                )
+            }
             _ => panic!("Unsupported type"),
         }
     }
@@ -243,9 +444,18 @@ impl<'a> IRGenerator<'a> {
                 self.visit_while_statement(expr);
                 None
             }
+            Statement::MatchStatement(expr) => {
+                self.visit_match_statement(expr);
+                None
+            }
             Statement::ForStatement(_) => unreachable!(),
             Statement::BreakStatement(b) => self.visit_break_statement(b),
+            Statement::ContinueStatement(c) => self.visit_continue_statement(c),
             Statement::Struct(struct_stmt) => self.visit_struct_statement(struct_stmt),
+            Statement::Enum(enum_stmt) => self.visit_enum_statement(enum_stmt),
+            Statement::Union(union_stmt) => self.visit_union_statement(union_stmt),
+            Statement::Impl(impl_stmt) => self.visit_impl_statement(impl_stmt),
+            Statement::Interface(interface) => self.visit_interface_statement(interface),
             Statement::Import(_) => todo!(),
         }
     }
@@ -286,6 +496,11 @@ impl<'a> IRGenerator<'a> {
             ValueType::Array(arr) => self.get_llvm_array_type(arr).into(),
             ValueType::Number => self.context.i64_type().into(),
             ValueType::Real => self.context.f64_type().into(),
+            ValueType::I8 | ValueType::U8 => self.context.i8_type().into(),
+            ValueType::I16 | ValueType::U16 => self.context.i16_type().into(),
+            ValueType::I32 | ValueType::U32 => self.context.i32_type().into(),
+            ValueType::U64 | ValueType::Enum(_) => self.context.i64_type().into(),
+            ValueType::F32 => self.context.f32_type().into(),
             ValueType::Bool => self.context.bool_type().into(),
             ValueType::Char => self.context.i8_type().into(),
             ValueType::String => self
@@ -308,13 +523,18 @@ impl<'a> IRGenerator<'a> {
                     .ptr_type(AddressSpace::Generic)
                     .into(),
             },
+            ValueType::Generic(..) => {
+                unreachable!("Generic types are instantiated by the type checker")
+            }
             ValueType::Void => self.context.void_type().into(),
             ValueType::Null => self
                 .context
                 .i64_type()
                 .ptr_type(AddressSpace::Generic)
                 .into(),
-            ValueType::Struct(s) => self.struct_types.get(s).unwrap().as_any_type_enum(),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => {
+                self.struct_types.get(s).unwrap().as_any_type_enum()
+            }
         }
     }
 
@@ -323,7 +543,13 @@ impl<'a> IRGenerator<'a> {
             ValueType::Array(arr) => self.get_llvm_array_type(arr).into(),
             ValueType::Number => self.context.i64_type().into(),
             ValueType::Real => self.context.f64_type().into(),
+            ValueType::I8 | ValueType::U8 => self.context.i8_type().into(),
+            ValueType::I16 | ValueType::U16 => self.context.i16_type().into(),
+            ValueType::I32 | ValueType::U32 => self.context.i32_type().into(),
+            ValueType::U64 | ValueType::Enum(_) => self.context.i64_type().into(),
+            ValueType::F32 => self.context.f32_type().into(),
             ValueType::Bool => self.context.bool_type().into(),
+            ValueType::Char => self.context.i8_type().into(),
             ValueType::String => self
                 .context
                 .i8_type()
@@ -344,11 +570,252 @@ impl<'a> IRGenerator<'a> {
                     .ptr_type(AddressSpace::Generic)
                     .into(),
             },
-            ValueType::Struct(_) => todo!(),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => {
+                self.struct_types.get(s).unwrap().as_basic_type_enum()
+            }
             _ => unreachable!("Building a struct of a forbidden type."),
         }
     }
 
+    /// Size and alignment in bytes of a value of type `value_type`, laid out
+    /// like LLVM lays out the structs: every field has its natural alignment.
+    pub fn get_type_layout(&self, value_type: &ValueType) -> (u64, u64) {
+        match value_type {
+            ValueType::Bool | ValueType::Char | ValueType::I8 | ValueType::U8 => (1, 1),
+            ValueType::I16 | ValueType::U16 => (2, 2),
+            ValueType::I32 | ValueType::U32 | ValueType::F32 => (4, 4),
+            ValueType::Array(array) => {
+                let (size, align) = self.get_type_layout(&array.array_type);
+                (size * array.size as u64, align)
+            }
+            ValueType::Struct(s) => {
+                let struct_stmt = self.type_table.find_struct_type(s).unwrap();
+                let fields: Vec<ValueType> = struct_stmt.fields.into_iter().map(|f| f.1).collect();
+                self.get_fields_layout(&fields)
+            }
+            ValueType::Union(u) => (8 * (1 + self.get_union_payload_words(u) as u64), 8),
+            // Pointers to the value and to the vtable
+            ValueType::Dyn(_) => (16, 8),
+            // 64 bits numbers, enums and pointers
+            _ => (8, 8),
+        }
+    }
+
+    /// Size and alignment of a struct holding `fields`
+    pub fn get_fields_layout(&self, fields: &[ValueType]) -> (u64, u64) {
+        let (mut size, mut align) = (0, 1);
+
+        for field in fields {
+            let (field_size, field_align) = self.get_type_layout(field);
+            size = (size + field_align - 1) / field_align * field_align + field_size;
+            align = align.max(field_align);
+        }
+
+        ((size + align - 1) / align * align, align)
+    }
+
+    /// A union is an `i64` tag followed by its payload, an array of `i64` big
+    /// enough to hold the payload of every variant. Returns its length.
+    pub fn get_union_payload_words(&self, union_name: &str) -> u32 {
+        let union_stmt = self.type_table.find_union_type(union_name).unwrap();
+        let largest = union_stmt
+            .variants
+            .iter()
+            .map(|(_, payload, _)| self.get_fields_layout(payload).0)
+            .max()
+            .unwrap_or(0);
+
+        ((largest + 7) / 8) as u32
+    }
+
+    /// Type of the payload of a union variant, a struct of its values
+    pub fn get_union_variant_type(&self, union_name: &str, tag: i64) -> StructType<'a> {
+        let union_stmt = self.type_table.find_union_type(union_name).unwrap();
+        let fields_type: Vec<BasicTypeEnum<'a>> = union_stmt.variants[tag as usize]
+            .1
+            .iter()
+            .map(|ty| self.get_llvm_basic_type(ty))
+            .collect();
+
+        self.context
+            .struct_type(fields_type.as_slice(), /* packed: */ false)
+    }
+
+    /// Pointer to the payload of the union at `union_ptr`, cast to the payload
+    /// type of the variant `tag`.
+    pub fn get_union_payload_pointer(
+        &self,
+        union_ptr: PointerValue<'a>,
+        union_name: &str,
+        tag: i64,
+    ) -> PointerValue<'a> {
+        let payload_ptr = self
+            .builder
+            .build_struct_gep(union_ptr, 1, "union_payload")
+            .unwrap();
+
+        self.builder.build_pointer_cast(
+            payload_ptr,
+            self.get_union_variant_type(union_name, tag)
+                .ptr_type(AddressSpace::Generic),
+            "union_variant_payload",
+        )
+    }
+
+    /// Vtable of the implementation of `interface` for the struct `type_name`,
+    /// the methods in the order of the interface. It's declared by the first
+    /// cast or impl block using it, the impl block initializes it.
+    pub fn get_vtable(&self, interface: &str, type_name: &str) -> GlobalValue<'a> {
+        let name = format!("vtable {} for {}", interface, type_name);
+        if let Some(vtable) = self.module.get_global(&name) {
+            return vtable;
+        }
+
+        let methods = self
+            .type_table
+            .find_interface_type(interface)
+            .unwrap()
+            .methods
+            .len();
+        let vtable_type = self
+            .context
+            .i8_type()
+            .ptr_type(AddressSpace::Generic)
+            .array_type(methods as u32);
+
+        let vtable = self
+            .module
+            .add_global(vtable_type, Some(AddressSpace::Generic), &name);
+        vtable.set_linkage(Linkage::Internal);
+        vtable.set_constant(true);
+        vtable
+    }
+
+    /// `dyn Interface` value of a pointer to a struct: the pointer and the
+    /// vtable of the implementation, both untyped.
+    pub fn build_dyn_value(
+        &self,
+        ptr: PointerValue<'a>,
+        type_name: &str,
+        interface: &str,
+    ) -> StructValue<'a> {
+        let i8_ptr = self.context.i8_type().ptr_type(AddressSpace::Generic);
+        let data = self.builder.build_pointer_cast(ptr, i8_ptr, "dyn_data");
+        let vtable = self.builder.build_pointer_cast(
+            self.get_vtable(interface, type_name).as_pointer_value(),
+            i8_ptr.ptr_type(AddressSpace::Generic),
+            "dyn_vtable",
+        );
+
+        let dyn_value = self.struct_types.get(interface).unwrap().get_undef();
+        let dyn_value = self
+            .builder
+            .build_insert_value(dyn_value, data, 0, "dyn_insert_data")
+            .unwrap();
+        self.builder
+            .build_insert_value(dyn_value, vtable, 1, "dyn_insert_vtable")
+            .unwrap()
+            .into_struct_value()
+    }
+
+    /// Loads `Interface::method` from the vtable of a `dyn Interface` value.
+    /// Returns the pointer to the struct, passed as `self`, and the method.
+    pub fn load_dyn_method(
+        &self,
+        interface: &str,
+        method: &str,
+        receiver: StructValue<'a>,
+    ) -> (PointerValue<'a>, PointerValue<'a>) {
+        let slot = self
+            .type_table
+            .find_interface_type(interface)
+            .unwrap()
+            .methods
+            .iter()
+            .position(|m| m.callee == method)
+            .unwrap();
+
+        // The method takes the struct as an untyped pointer
+        let dispatch = self
+            .type_table
+            .find_function_type(&format!("{}::{}", interface, method))
+            .unwrap();
+        let i8_ptr = self.context.i8_type().ptr_type(AddressSpace::Generic);
+        let args_type: Vec<BasicMetadataTypeEnum<'a>> = std::iter::once(i8_ptr.into())
+            .chain(
+                dispatch
+                    .args
+                    .iter()
+                    .flatten()
+                    .skip(1)
+                    .map(|(_, ty)| match ty {
+                        ValueType::Array(arr) => self.get_concrete_array_type(arr).into(),
+                        ty => self.get_llvm_basic_type(ty).into(),
+                    }),
+            )
+            .collect();
+        let fn_type = match &dispatch.return_type {
+            ValueType::Void => self.context.void_type().fn_type(&args_type, false),
+            ty => self.get_llvm_basic_type(ty).fn_type(&args_type, false),
+        };
+
+        let data = self
+            .builder
+            .build_extract_value(receiver, 0, "dyn_data")
+            .unwrap()
+            .into_pointer_value();
+        let vtable = self
+            .builder
+            .build_extract_value(receiver, 1, "dyn_vtable")
+            .unwrap()
+            .into_pointer_value();
+
+        let slot_ptr = unsafe {
+            self.builder.build_in_bounds_gep(
+                vtable,
+                &[self.context.i64_type().const_int(slot as u64, false)],
+                "dyn_method_gep",
+            )
+        };
+        let method_ptr = self
+            .builder
+            .build_load(slot_ptr, "dyn_method")
+            .into_pointer_value();
+
+        (
+            data,
+            self.builder.build_pointer_cast(
+                method_ptr,
+                fn_type.ptr_type(AddressSpace::Generic),
+                "dyn_method_cast",
+            ),
+        )
+    }
+
+    /// Emits a null terminated global string and returns a pointer to its first
+    /// character. Unlike `build_global_string_ptr` it supports embedded '\0'.
+    pub fn build_string_literal(&self, value: &str, name: &str) -> PointerValue<'a> {
+        let bytes: Vec<IntValue<'a>> = value
+            .bytes()
+            .chain(std::iter::once(0))
+            .map(|b| self.context.i8_type().const_int(b as u64, false))
+            .collect();
+
+        let string_type = self.context.i8_type().array_type(bytes.len() as u32);
+        let global = self
+            .module
+            .add_global(string_type, Some(AddressSpace::Generic), name);
+        global.set_linkage(Linkage::Private);
+        global.set_constant(true);
+        global.set_initializer(&self.context.i8_type().const_array(bytes.as_slice()));
+
+        self.builder.build_pointer_cast(
+            global.as_pointer_value(),
+            self.context.i8_type().ptr_type(AddressSpace::Generic),
+            name,
+        )
+    }
+
     pub fn get_struct_member_pointer_value(
         &mut self,
         member_access: &MemberAccess,
@@ -382,6 +849,43 @@ impl<'a> IRGenerator<'a> {
             )
             .unwrap()
     }
+
+    /// Address of an element of a nested array (`m[i][j]`), the rows are
+    /// indexed in place instead of being loaded.
+    pub fn get_array_element_pointer_value(&mut self, access: &ArrayAccess) -> PointerValue<'a> {
+        let elements_ptr = match &*access.identifier {
+            Expression::ArrayAccess(row) => {
+                let row_ptr = self.get_array_element_pointer_value(row);
+
+                if let ValueType::Array(_) = row.get_type() {
+                    let element_type = row_ptr
+                        .get_type()
+                        .get_element_type()
+                        .into_array_type()
+                        .get_element_type();
+
+                    self.builder.build_pointer_cast(
+                        row_ptr,
+                        element_type.ptr_type(AddressSpace::Generic),
+                        "array_row_cast",
+                    )
+                } else {
+                    // Pointer or string element
+                    self.builder
+                        .build_load(row_ptr, "load_row_ptr")
+                        .into_pointer_value()
+                }
+            }
+            // Arrays are loaded as pointers to their first element
+            expr => self.visit_borrowed_expr(expr).into_pointer_value(),
+        };
+        let index = self.visit_expr(&access.index).into_int_value();
+
+        unsafe {
+            self.builder
+                .build_gep(elements_ptr, &[index], "nested_array_gep")
+        }
+    }
 }
 
 unsafe fn execute_jit_function<'a, T: Debug>(engine: &ExecutionEngine<'a>) {
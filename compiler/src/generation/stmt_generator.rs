@@ -1,17 +1,21 @@
 use std::{borrow::Borrow, ops::Deref};
 
 use inkwell::{
+    basic_block::BasicBlock,
     module::Linkage,
     types::{ArrayType, BasicMetadataTypeEnum, BasicType, BasicTypeEnum},
-    values::{AnyValue, AnyValueEnum, BasicValue, BasicValueEnum, PointerValue},
-    AddressSpace,
+    values::{
+        AnyValue, AnyValueEnum, ArrayValue, BasicValue, BasicValueEnum, IntValue, PointerValue,
+    },
+    AddressSpace, IntPredicate,
 };
 
 use crate::{
     parser::visitors::{
-        BlockStatement, BreakStatement, Expression, ForStatement, FunctionStatement, IfStatement,
-        ImportStatement, Literal, ReturnStatement, StatementVisitor, StructStatement,
-        VariableAssignment, VariableDeclaration, WhileStatement,
+        BlockStatement, BreakStatement, ContinueStatement, EnumPattern, EnumStatement, Expression,
+        ForStatement, FunctionStatement, IfStatement, ImplStatement, ImportStatement,
+        InterfaceStatement, Literal, MatchStatement, Pattern, ReturnStatement, StatementVisitor,
+        StructStatement, UnionStatement, VariableAssignment, VariableDeclaration, WhileStatement,
     },
     type_system::{
         typed::Typed,
@@ -21,20 +25,80 @@ use crate::{
 
 use super::ir_generator::IRGenerator;
 
+/// Range patterns with at most this many values are lowered as `switch` cases,
+/// bigger ones are tested with comparisons.
+const MAX_SWITCH_RANGE: i128 = 64;
+
 impl<'a> IRGenerator<'a> {
+    fn pattern_literal_value(literal: &Literal) -> i64 {
+        match literal {
+            Literal::Number(n) => n.value,
+            Literal::Char(c) => c.value as i64,
+            Literal::Bool(b) => b.value as i64,
+            _ => unreachable!("Match patterns only hold number, char or bool literals"),
+        }
+    }
+
+    /// Copies the payload of the matched union at `union_ptr` in the variables
+    /// bound by `pattern`
+    fn bind_union_payload(&mut self, union_ptr: PointerValue<'a>, pattern: &EnumPattern) {
+        let payload_ptr =
+            self.get_union_payload_pointer(union_ptr, &pattern.enum_name, pattern.value.unwrap());
+
+        for (i, binding) in pattern.bindings.iter().enumerate() {
+            if let Some(name) = binding {
+                let field_ptr = self
+                    .builder
+                    .build_struct_gep(payload_ptr, i as u32, "union_payload_field")
+                    .unwrap();
+                let value = self.builder.build_load(field_ptr, name.as_str());
+
+                let alloca = self.builder.build_alloca(value.get_type(), name.as_str());
+                self.builder.build_store(alloca, value);
+                self.variables.insert(name.to_string(), alloca);
+            }
+        }
+    }
+
     fn generate_block_instructions(&mut self, block: &BlockStatement) {
         for stmt in &block.statements {
+            // The statements after a return, break or continue are dead, a
+            // basic block can't have instructions after its terminator
+            if self.is_block_terminated() {
+                break;
+            }
+
             self.visit_statement(&stmt);
         }
     }
 
+    fn is_block_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(|bb| bb.get_terminator())
+            .is_some()
+    }
+
     pub fn get_llvm_array_type(&self, array_type: &StaticArray) -> ArrayType<'a> {
         match array_type.array_type.deref() {
-            ValueType::Array(a) => self.get_llvm_array_type(a),
+            ValueType::Array(a) => self
+                .get_llvm_array_type(a)
+                .array_type(array_type.size as u32),
             ValueType::Number => self.context.i64_type().array_type(array_type.size as u32),
             ValueType::Real => self.context.f64_type().array_type(array_type.size as u32),
             ValueType::Bool => self.context.bool_type().array_type(array_type.size as u32),
             ValueType::Char => self.context.i8_type().array_type(array_type.size as u32),
+            ty @ (ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64
+            | ValueType::F32
+            | ValueType::Enum(_)) => self
+                .get_llvm_basic_type(ty)
+                .array_type(array_type.size as u32),
             ValueType::String => self
                 .context
                 .i8_type()
@@ -42,15 +106,28 @@ impl<'a> IRGenerator<'a> {
                 .array_type(array_type.size as u32),
             ValueType::Function => todo!(),
             ValueType::Void => unreachable!(),
-            ValueType::Pointer(_) => todo!(),
+            ValueType::Pointer(ptr) => self
+                .get_ptr_type(&self.get_llvm_type(ptr))
+                .array_type(array_type.size as u32),
+            ValueType::Generic(..) => {
+                unreachable!("Generic types are instantiated by the type checker")
+            }
             ValueType::Null => unreachable!(),
-            ValueType::Struct(_) => todo!(),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => self
+                .struct_types
+                .get(s)
+                .unwrap()
+                .array_type(array_type.size as u32),
         }
     }
 
     pub fn get_concrete_array_type(&self, array_type: &StaticArray) -> BasicTypeEnum<'a> {
         match array_type.array_type.deref() {
-            ValueType::Array(a) => self.get_concrete_array_type(a),
+            // Rows of nested arrays are kept whole
+            ValueType::Array(a) => self
+                .get_llvm_array_type(a)
+                .ptr_type(AddressSpace::Generic)
+                .into(),
             ValueType::Number => self
                 .context
                 .i64_type()
@@ -71,17 +148,40 @@ impl<'a> IRGenerator<'a> {
                 .bool_type()
                 .ptr_type(AddressSpace::Generic)
                 .into(),
+            ty @ (ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64
+            | ValueType::F32
+            | ValueType::Enum(_)) => self
+                .get_llvm_basic_type(ty)
+                .ptr_type(AddressSpace::Generic)
+                .into(),
             ValueType::String => self
                 .context
                 .i8_type()
                 .ptr_type(AddressSpace::Generic)
                 .ptr_type(AddressSpace::Generic)
                 .into(),
-            ValueType::Pointer(_) => todo!(),
+            ValueType::Pointer(ptr) => self
+                .get_ptr_type(&self.get_llvm_type(ptr))
+                .ptr_type(AddressSpace::Generic)
+                .into(),
             ValueType::Function => todo!(),
             ValueType::Void => unreachable!("array type can't be void!"),
+            ValueType::Generic(..) => {
+                unreachable!("Generic types are instantiated by the type checker")
+            }
             ValueType::Null => unreachable!("Array type of null!"),
-            ValueType::Struct(_) => todo!(),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => self
+                .struct_types
+                .get(s)
+                .unwrap()
+                .ptr_type(AddressSpace::Generic)
+                .into(),
         }
     }
 
@@ -162,6 +262,72 @@ impl<'a> IRGenerator<'a> {
         }
     }
 
+    /// Converts a value to `target_type` where the representations differ: bools
+    /// are truncated, pointers are cast (e.g null) and arrays referenced through
+    /// a pointer are loaded.
+    pub(super) fn build_value_cast(
+        &self,
+        value: AnyValueEnum<'a>,
+        target_type: BasicTypeEnum<'a>,
+    ) -> BasicValueEnum<'a> {
+        match (value, target_type) {
+            (AnyValueEnum::IntValue(v), BasicTypeEnum::IntType(t)) if v.get_type() != t => self
+                .builder
+                .build_int_truncate_or_bit_cast(v, t, "int_value_cast")
+                .into(),
+            (AnyValueEnum::PointerValue(v), BasicTypeEnum::PointerType(t)) => self
+                .builder
+                .build_pointer_cast(v, t, "ptr_value_cast")
+                .into(),
+            (AnyValueEnum::PointerValue(v), BasicTypeEnum::ArrayType(t)) => {
+                let array_ptr = self.builder.build_pointer_cast(
+                    v,
+                    t.ptr_type(AddressSpace::Generic),
+                    "array_value_cast",
+                );
+
+                self.builder.build_load(array_ptr, "load_array_value")
+            }
+            _ => self.into_basic_value(value),
+        }
+    }
+
+    /// Builds an array from its elements, the builder folds it to a constant
+    /// when all the elements are constants.
+    pub(super) fn build_array_value(
+        &self,
+        array_type: ArrayType<'a>,
+        elements: &[BasicValueEnum<'a>],
+    ) -> ArrayValue<'a> {
+        let mut array = array_type.get_undef();
+
+        for (i, element) in elements.iter().enumerate() {
+            array = self
+                .builder
+                .build_insert_value(array, *element, i as u32, "array_insert")
+                .unwrap()
+                .into_array_value();
+        }
+
+        array
+    }
+
+    /// Stores an array value on the stack so it can be indexed, returns a
+    /// pointer to its first element.
+    pub(super) fn spill_array_value(&self, array: ArrayValue<'a>) -> PointerValue<'a> {
+        let array_ptr = self.builder.build_alloca(array.get_type(), "array_tmp");
+        self.builder.build_store(array_ptr, array);
+
+        self.builder.build_pointer_cast(
+            array_ptr,
+            array
+                .get_type()
+                .get_element_type()
+                .ptr_type(AddressSpace::Generic),
+            "array_tmp_cast",
+        )
+    }
+
     /// If the lhs is an array cast it to the correct array
     /// element pointer and build the store
     fn build_assignment<T: BasicValue<'a> + Copy>(
@@ -220,19 +386,52 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         &mut self,
         var_dec: &VariableDeclaration,
     ) -> Option<AnyValueEnum<'a>> {
-        if let ValueType::Array(a) = &var_dec.variable_type {
-            let init_value = self.visit_borrowed_expr(&var_dec.init_expr);
-            self.declare_and_init_array(&a, &init_value, &var_dec.identifier);
+        // Inferred by the type checker if not declared
+        let variable_type = var_dec.variable_type.as_ref().unwrap();
+
+        // `let x: type;` only reserves the variable, it's assigned later
+        let init_expr = match &var_dec.init_expr {
+            Some(init_expr) => init_expr,
+            None => {
+                let val_ptr = match variable_type {
+                    ValueType::Array(a) => self
+                        .builder
+                        .build_alloca(self.get_llvm_array_type(a), var_dec.identifier.as_str()),
+                    _ => self.create_entry_block_alloca(var_dec.identifier.as_str(), variable_type),
+                };
+                self.variables
+                    .insert(var_dec.identifier.to_string(), val_ptr);
+                return None;
+            }
+        };
+
+        if let ValueType::Array(a) = variable_type {
+            let init_value = self.visit_borrowed_expr(init_expr);
+
+            // Array literal or copy of another array
+            if let ValueType::Array(_) = init_expr.get_type() {
+                let array_type = self.get_llvm_array_type(a);
+                let array_value = self.build_value_cast(init_value, array_type.into());
+                let array_ptr = self
+                    .builder
+                    .build_alloca(array_type, var_dec.identifier.as_str());
+
+                self.builder.build_store(array_ptr, array_value);
+                self.variables
+                    .insert(var_dec.identifier.to_string(), array_ptr);
+            } else {
+                self.declare_and_init_array(&a, &init_value, &var_dec.identifier);
+            }
+
             return None;
         }
 
-        let init_expr = self.visit_borrowed_expr(&var_dec.init_expr);
-        let val_ptr =
-            self.create_entry_block_alloca(var_dec.identifier.as_str(), &var_dec.variable_type);
+        let init_value = self.visit_borrowed_expr(init_expr);
+        let val_ptr = self.create_entry_block_alloca(var_dec.identifier.as_str(), variable_type);
         self.variables
             .insert(var_dec.identifier.to_string(), val_ptr);
 
-        match init_expr {
+        match init_value {
             AnyValueEnum::IntValue(v) => {
                 self.builder.build_store(val_ptr, v);
             }
@@ -242,8 +441,8 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
             AnyValueEnum::PointerValue(v) => {
                 // Implicit cast to i8* if the variable has type string and the init_expression has type ptr void
                 // to statisfy the llvm's type system.
-                if var_dec.variable_type == ValueType::String
-                    && var_dec.init_expr.get_type() == ValueType::Pointer(Box::new(ValueType::Void))
+                if *variable_type == ValueType::String
+                    && init_expr.get_type() == ValueType::Pointer(Box::new(ValueType::Void))
                 {
                     let i8_cast = self.builder.build_bitcast(
                         v,
@@ -274,6 +473,11 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
             Expression::Literal(Literal::Identifier(id)) => {
                 self.variables.get(&id.name).unwrap().clone()
             }
+            Expression::ArrayAccess(access)
+                if matches!(*access.identifier, Expression::ArrayAccess(_)) =>
+            {
+                self.get_array_element_pointer_value(access)
+            }
             Expression::ArrayAccess(access) => {
                 let ptr_val = self.visit_expr(&access.identifier);
 
@@ -313,6 +517,13 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                 .into_pointer_value(),
         };
 
+        // Whole array assignment
+        if let ValueType::Array(a) = var_ass.identifier.get_type() {
+            let array_value = self.build_value_cast(new_expr, self.get_llvm_array_type(&a).into());
+            self.builder.build_store(val_ptr, array_value);
+            return None;
+        }
+
         match new_expr {
             AnyValueEnum::IntValue(v) => self.build_assignment(&val_ptr, v, &var_ass.identifier),
             AnyValueEnum::FloatValue(v) => self.build_assignment(&val_ptr, v, &var_ass.identifier),
@@ -326,6 +537,20 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
     }
 
     fn visit_function_statement(&mut self, expr: &FunctionStatement) -> Option<AnyValueEnum<'a>> {
+        // Emitted through their instances
+        if !expr.type_params.is_empty() {
+            return None;
+        }
+
+        let symbol = expr.mangled_name.as_ref().unwrap_or(&expr.callee);
+
+        // A function imported in several namespaces is declared once
+        if expr.block.is_none() {
+            if let Some(fn_val) = self.module.get_function(symbol) {
+                return Some(fn_val.as_any_value_enum());
+            }
+        }
+
         let args_type = if expr.args.is_some() {
             Some(
                 expr.args
@@ -338,6 +563,15 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                             ValueType::Real => self.context.f64_type().into(),
                             ValueType::Bool => self.context.bool_type().into(),
                             ValueType::Char => self.context.i8_type().into(),
+                            ty @ (ValueType::I8
+                            | ValueType::I16
+                            | ValueType::I32
+                            | ValueType::U8
+                            | ValueType::U16
+                            | ValueType::U32
+                            | ValueType::U64
+                            | ValueType::F32
+                            | ValueType::Enum(_)) => self.get_llvm_basic_type(ty).into(),
                             ValueType::String => self
                                 .context
                                 .i8_type()
@@ -349,8 +583,13 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                             ValueType::Pointer(ptr) => {
                                 self.get_ptr_type(&self.get_llvm_type(ptr)).into()
                             }
+                            ValueType::Generic(..) => {
+                                unreachable!("Generic types are instantiated by the type checker")
+                            }
                             ValueType::Null => unreachable!("Parameter of type null!"),
-                            ValueType::Struct(strct) => self
+                            ValueType::Struct(strct)
+                            | ValueType::Union(strct)
+                            | ValueType::Dyn(strct) => self
                                 .struct_types
                                 .get(strct)
                                 .unwrap()
@@ -405,6 +644,22 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                 },
                 false,
             ),
+            ty @ (ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64
+            | ValueType::F32
+            | ValueType::Enum(_)) => self.get_llvm_basic_type(ty).fn_type(
+                if args_type.is_some() {
+                    args_type.as_ref().unwrap().as_slice()
+                } else {
+                    &[]
+                },
+                false,
+            ),
             ValueType::Function => todo!(),
             ValueType::String => self
                 .context
@@ -429,12 +684,24 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                     },
                     false,
                 ),
+            ValueType::Generic(..) => {
+                unreachable!("Generic types are instantiated by the type checker")
+            }
             ValueType::Null => unreachable!("null return type!"),
-            ValueType::Struct(_) => todo!(),
+            ValueType::Struct(s) | ValueType::Union(s) | ValueType::Dyn(s) => {
+                self.struct_types.get(s).unwrap().fn_type(
+                    if args_type.is_some() {
+                        args_type.as_ref().unwrap().as_slice()
+                    } else {
+                        &[]
+                    },
+                    false,
+                )
+            }
         };
 
         let fn_val = self.module.add_function(
-            expr.callee.as_str(),
+            symbol.as_str(),
             fn_type,
             // Main is implicitly exported
             if expr.is_exported || expr.callee == "main" {
@@ -451,6 +718,10 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
             let entry = self.context.append_basic_block(fn_val, "entry");
             self.builder.position_at_end(entry);
 
+            // Params and locals may shadow the globals, they are restored for
+            // the next functions
+            let globals = self.variables.clone();
+
             for (i, arg) in fn_val.get_param_iter().enumerate() {
                 match arg {
                     BasicValueEnum::IntValue(v) => {
@@ -492,6 +763,7 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
                 }
             }
             self.generate_block_instructions(&b);
+            self.variables = globals;
         } else {
             // else just declare the function, it has no block
             self.current_fn = None;
@@ -499,9 +771,15 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         }
 
         self.current_fn = None;
+        self.has_branched = false;
 
         if expr.return_type == ValueType::Void {
             self.builder.build_return(None);
+        } else if !self.is_block_terminated() {
+            // The type checker proved that every path returns, the end of the
+            // body is only reached after an `if` whose branches all return or
+            // a loop without break
+            self.builder.build_unreachable();
         }
 
         fn_val.verify(true);
@@ -510,6 +788,11 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
     }
 
     fn visit_struct_statement(&mut self, stct: &StructStatement) -> Option<AnyValueEnum<'a>> {
+        // Emitted through their instances
+        if !stct.type_params.is_empty() {
+            return None;
+        }
+
         let fields_type: Vec<BasicTypeEnum<'a>> = stct
             .fields
             .iter()
@@ -526,6 +809,72 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         None
     }
 
+    fn visit_enum_statement(&mut self, _enm: &EnumStatement) -> Option<AnyValueEnum<'a>> {
+        None
+    }
+
+    fn visit_union_statement(&mut self, union_stmt: &UnionStatement) -> Option<AnyValueEnum<'a>> {
+        let payload_type = self
+            .context
+            .i64_type()
+            .array_type(self.get_union_payload_words(&union_stmt.type_name));
+
+        let llvm_union_ty = self.context.struct_type(
+            &[self.context.i64_type().into(), payload_type.into()],
+            /* packed: */ false,
+        );
+
+        self.struct_types
+            .insert(union_stmt.type_name.to_string(), llvm_union_ty);
+
+        None
+    }
+
+    fn visit_impl_statement(&mut self, impl_stmt: &ImplStatement) -> Option<AnyValueEnum<'a>> {
+        for method in &impl_stmt.methods {
+            self.visit_function_statement(method);
+        }
+
+        // The type checker sorted the methods in the order of the interface
+        if let Some(interface) = &impl_stmt.interface {
+            let i8_ptr = self.context.i8_type().ptr_type(AddressSpace::Generic);
+            let methods: Vec<PointerValue<'a>> = impl_stmt
+                .methods
+                .iter()
+                .map(|m| {
+                    self.get_function_by_name(&m.callee)
+                        .unwrap()
+                        .as_global_value()
+                        .as_pointer_value()
+                        .const_cast(i8_ptr)
+                })
+                .collect();
+
+            self.get_vtable(interface, &impl_stmt.type_name)
+                .set_initializer(&i8_ptr.const_array(methods.as_slice()));
+        }
+
+        None
+    }
+
+    fn visit_interface_statement(
+        &mut self,
+        interface: &InterfaceStatement,
+    ) -> Option<AnyValueEnum<'a>> {
+        // The methods are implemented by the structs, a `dyn Interface` value
+        // is a pointer to the struct and a pointer to its vtable
+        let i8_ptr = self.context.i8_type().ptr_type(AddressSpace::Generic);
+        let dyn_type = self.context.struct_type(
+            &[i8_ptr.into(), i8_ptr.ptr_type(AddressSpace::Generic).into()],
+            /* packed: */ false,
+        );
+
+        self.struct_types
+            .insert(interface.type_name.to_string(), dyn_type);
+
+        None
+    }
+
     fn visit_block_statement(&mut self, expr: &BlockStatement) -> Option<AnyValueEnum<'a>> {
         // let current_fn = self.current_fn.unwrap();
         // let current_fn_bb = current_fn.get_last_basic_block().unwrap();
@@ -533,10 +882,19 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         // let anonymous_block = self.context.append_basic_block(current_fn, "anon_block");
         // self.builder.position_at_end(anonymous_block);
 
+        // A `let` of the block may shadow a variable until the end of the block
+        let enclosing_variables = self.variables.clone();
+
         for stmt in &expr.statements {
+            if self.is_block_terminated() {
+                break;
+            }
+
             self.visit_statement(&stmt);
         }
 
+        self.variables = enclosing_variables;
+
         // self.builder.build_unconditional_branch(current_fn_bb);
 
         None
@@ -551,8 +909,10 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         match value {
             AnyValueEnum::IntValue(v) => self.builder.build_return(Some(&v)),
             AnyValueEnum::FloatValue(v) => self.builder.build_return(Some(&v)),
+            AnyValueEnum::StructValue(v) => self.builder.build_return(Some(&v)),
             _ => panic!(),
         };
+        self.has_branched = true;
 
         Some(value)
     }
@@ -617,6 +977,10 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
 
         let test_bb = self.context.append_basic_block(parent, "while_test");
         let body_bb = self.context.append_basic_block(parent, "while_body");
+        let next_bb = while_stmt
+            .next_expr
+            .as_ref()
+            .map(|_| self.context.append_basic_block(parent, "while_next"));
         let end_loop_bb = self.context.append_basic_block(parent, "while_end");
 
         self.builder.build_unconditional_branch(test_bb);
@@ -637,16 +1001,26 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         self.builder
             .build_conditional_branch(cond_instr, body_bb, end_loop_bb);
 
-        self.loop_bb_stack.push(end_loop_bb);
+        // A desugared for loop runs its next expression before the test,
+        // continue included.
+        let continue_bb = next_bb.unwrap_or(test_bb);
+
+        self.loop_bb_stack.push((continue_bb, end_loop_bb));
         self.builder.position_at_end(body_bb);
         self.visit_block_statement(&while_stmt.loop_block);
 
         if !self.block_has_branch() {
-            self.builder.build_unconditional_branch(test_bb);
+            self.builder.build_unconditional_branch(continue_bb);
         }
 
         self.loop_bb_stack.pop();
 
+        if let (Some(next_bb), Some(next_expr)) = (next_bb, &while_stmt.next_expr) {
+            self.builder.position_at_end(next_bb);
+            self.visit_statement(next_expr);
+            self.builder.build_unconditional_branch(test_bb);
+        }
+
         self.builder.position_at_end(end_loop_bb);
 
         None
@@ -656,9 +1030,177 @@ impl<'a> StatementVisitor<Option<AnyValueEnum<'a>>> for IRGenerator<'a> {
         unreachable!()
     }
 
+    fn visit_match_statement(&mut self, match_stmt: &MatchStatement) -> Option<AnyValueEnum<'a>> {
+        let parent = if let Some(current) = self.current_fn {
+            current
+        } else {
+            panic!("Match must be in a function !")
+        };
+
+        // Unions are matched on their tag, the payload is bound in the arms
+        let (value, union_ptr) = match self.visit_borrowed_expr(&match_stmt.scrutinee) {
+            AnyValueEnum::IntValue(v) => (v, None),
+            AnyValueEnum::StructValue(v) => {
+                let union_ptr = self
+                    .create_entry_block_alloca("matched_union", &match_stmt.scrutinee.get_type());
+                self.builder.build_store(union_ptr, v);
+
+                let tag_ptr = self
+                    .builder
+                    .build_struct_gep(union_ptr, 0, "union_tag")
+                    .unwrap();
+                let tag = self.builder.build_load(tag_ptr, "load_union_tag");
+
+                (tag.into_int_value(), Some(union_ptr))
+            }
+            _ => panic!(),
+        };
+
+        // Numbers and enums are signed, chars and bools are compared as unsigned codes
+        let int_type = value.get_type();
+        let is_signed = match_stmt.scrutinee.get_type().is_signed();
+        let bit_width = int_type.get_bit_width();
+        let (min, max): (i128, i128) = if is_signed {
+            (-(1 << (bit_width - 1)), (1 << (bit_width - 1)) - 1)
+        } else {
+            (0, (1 << bit_width) - 1)
+        };
+
+        let arm_bbs: Vec<BasicBlock> = match_stmt
+            .arms
+            .iter()
+            .map(|_| self.context.append_basic_block(parent, "match_arm"))
+            .collect();
+        let default_bb = self.context.append_basic_block(parent, "match_default");
+        let end_bb = self.context.append_basic_block(parent, "match_end");
+
+        let mut cases: Vec<(IntValue, BasicBlock)> = Vec::new();
+        let mut ranges: Vec<(i128, i128, BasicBlock)> = Vec::new();
+        let mut wildcard_bb: Option<BasicBlock> = None;
+        // Values taken by the previous patterns, the first matching arm wins
+        let mut covered: Vec<(i128, i128)> = Vec::new();
+
+        for (arm, arm_bb) in match_stmt.arms.iter().zip(&arm_bbs) {
+            for pattern in &arm.patterns {
+                let (low, high) = match pattern {
+                    Pattern::Wildcard(_) => {
+                        wildcard_bb = Some(*arm_bb);
+                        break;
+                    }
+                    Pattern::Literal(literal) => {
+                        let code = Self::pattern_literal_value(literal);
+                        (code, code)
+                    }
+                    Pattern::EnumVariant(enum_pattern) => {
+                        let value = enum_pattern.value.unwrap();
+                        (value, value)
+                    }
+                    Pattern::Range(range) => {
+                        let end = Self::pattern_literal_value(&range.end);
+                        (
+                            Self::pattern_literal_value(&range.start),
+                            if range.inclusive { end } else { end - 1 },
+                        )
+                    }
+                };
+
+                let (low, high) = ((low as i128).max(min), (high as i128).min(max));
+                if low > high {
+                    continue;
+                }
+
+                if high - low < MAX_SWITCH_RANGE {
+                    for case in low..=high {
+                        if !covered.iter().any(|(l, h)| *l <= case && case <= *h) {
+                            cases.push((int_type.const_int(case as u64, is_signed), *arm_bb));
+                        }
+                    }
+                } else {
+                    ranges.push((low, high, *arm_bb));
+                }
+
+                covered.push((low, high));
+            }
+
+            // The arms after a '_' are unreachable
+            if wildcard_bb.is_some() {
+                break;
+            }
+        }
+
+        self.builder.build_switch(value, default_bb, &cases);
+
+        // Values missed by the switch are tested against the big ranges, then
+        // go to the '_' arm if any
+        self.builder.position_at_end(default_bb);
+        let (above_predicate, below_predicate) = if is_signed {
+            (IntPredicate::SGE, IntPredicate::SLE)
+        } else {
+            (IntPredicate::UGE, IntPredicate::ULE)
+        };
+
+        for (low, high, arm_bb) in ranges {
+            let next_bb = self.context.append_basic_block(parent, "match_range_next");
+            let above = self.builder.build_int_compare(
+                above_predicate,
+                value,
+                int_type.const_int(low as u64, is_signed),
+                "range_low",
+            );
+            let below = self.builder.build_int_compare(
+                below_predicate,
+                value,
+                int_type.const_int(high as u64, is_signed),
+                "range_high",
+            );
+            let in_range = self.builder.build_and(above, below, "in_range");
+
+            self.builder
+                .build_conditional_branch(in_range, arm_bb, next_bb);
+            self.builder.position_at_end(next_bb);
+        }
+
+        self.builder
+            .build_unconditional_branch(wildcard_bb.unwrap_or(end_bb));
+
+        for (arm, arm_bb) in match_stmt.arms.iter().zip(arm_bbs) {
+            self.builder.position_at_end(arm_bb);
+            let enclosing_variables = self.variables.clone();
+
+            // The type checker only allows bindings in single pattern arms
+            if let (Some(union_ptr), [Pattern::EnumVariant(variant_pattern)]) =
+                (union_ptr, arm.patterns.as_slice())
+            {
+                self.bind_union_payload(union_ptr, variant_pattern);
+            }
+
+            self.visit_block_statement(&arm.block);
+            self.variables = enclosing_variables;
+
+            if !self.block_has_branch() {
+                self.builder.build_unconditional_branch(end_bb);
+            }
+        }
+
+        self.builder.position_at_end(end_bb);
+
+        None
+    }
+
     fn visit_break_statement(&mut self, _break_stmt: &BreakStatement) -> Option<AnyValueEnum<'a>> {
         self.builder
-            .build_unconditional_branch(*self.loop_bb_stack.last().unwrap());
+            .build_unconditional_branch(self.loop_bb_stack.last().unwrap().1);
+        self.has_branched = true;
+
+        None
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _continue_stmt: &ContinueStatement,
+    ) -> Option<AnyValueEnum<'a>> {
+        self.builder
+            .build_unconditional_branch(self.loop_bb_stack.last().unwrap().0);
         self.has_branched = true;
 
         None
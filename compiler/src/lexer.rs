@@ -1,60 +1,324 @@
-use logos::{Lexer, Logos, Skip};
+use std::{iter::Peekable, str::CharIndices};
+
+use logos::{Filter, Lexer, Logos, Skip};
+
+use crate::{debug::Span, type_system::value_type::ValueType};
+
+/// Decodes the escape sequence following a '\'.
+/// `chars` must be positioned right after the backslash. On failure the
+/// offending character is not consumed so the caller can resume scanning.
+fn decode_escape(chars: &mut Peekable<CharIndices>) -> Result<char, String> {
+    let escaped = match chars.peek() {
+        Some((_, c)) => *c,
+        None => return Err("Unterminated escape sequence.".to_string()),
+    };
+
+    let simple = match escaped {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        '\\' => Some('\\'),
+        '"' => Some('"'),
+        '\'' => Some('\''),
+        '0' => Some('\0'),
+        _ => None,
+    };
+
+    if let Some(c) = simple {
+        chars.next();
+        return Ok(c);
+    }
+
+    match escaped {
+        'x' => {
+            chars.next();
+            let mut value: u32 = 0;
+
+            for _ in 0..2 {
+                match chars.peek() {
+                    Some((_, c)) if c.is_ascii_hexdigit() => {
+                        value = value * 16 + c.to_digit(16).unwrap();
+                        chars.next();
+                    }
+                    _ => return Err("Expected 2 hexadecimal digits after '\\x'.".to_string()),
+                }
+            }
+
+            if value > 0x7F {
+                return Err(format!(
+                    "'\\x{:02X}' is out of range, hexadecimal escapes must be in [\\x00-\\x7F].",
+                    value
+                ));
+            }
 
-use crate::type_system::value_type::ValueType;
+            Ok(char::from_u32(value).unwrap())
+        }
+        'u' => {
+            chars.next();
+
+            if let Some((_, '{')) = chars.peek() {
+                chars.next();
+            } else {
+                return Err("Expected '{' after '\\u'.".to_string());
+            }
+
+            let mut digits = String::new();
+            loop {
+                match chars.peek() {
+                    Some((_, '}')) if !digits.is_empty() => {
+                        chars.next();
+                        break;
+                    }
+                    Some((_, c)) if c.is_ascii_hexdigit() && digits.len() < 6 => {
+                        digits.push(*c);
+                        chars.next();
+                    }
+                    _ => {
+                        return Err(
+                            "Unicode escapes must be '\\u{...}' with 1 to 6 hexadecimal digits."
+                                .to_string(),
+                        )
+                    }
+                }
+            }
+
+            let value = u32::from_str_radix(&digits, 16).unwrap();
+            char::from_u32(value)
+                .ok_or_else(|| format!("'\\u{{{}}}' is not a valid unicode character.", digits))
+        }
+        c => Err(format!(
+            "Unknown escape sequence '\\{}'.",
+            c.escape_default()
+        )),
+    }
+}
 
 /// Walks the source code until an other " is reached.
 /// Then bump the lexer to second " location to resume lexing
 /// it acts likes Flex sublexer
 fn handle_quote(lex: &mut Lexer<LogosToken>) -> Result<String, ()> {
-    let mut inner_content: Vec<char> = Vec::new();
-    let remainder_string = lex.remainder();
+    let content_start = lex.span().end;
+    let (line, column) = lex.extras.position(lex.span().start);
 
-    for chr in remainder_string.chars() {
-        if chr == '"' {
-            // Bump to the literal's size + 1 to skip the closing quote
-            lex.bump(inner_content.len() + 1);
-            return Ok(inner_content.iter().collect());
+    let remainder_string = lex.remainder();
+    let mut inner_content = String::new();
+    let mut is_valid = true;
+    let mut chars = remainder_string.char_indices().peekable();
+
+    while let Some((i, chr)) = chars.next() {
+        match chr {
+            '"' => {
+                // Bump to the literal's size + 1 to skip the closing quote
+                lex.bump(i + 1);
+                return if is_valid { Ok(inner_content) } else { Err(()) };
+            }
+            '\\' => match decode_escape(&mut chars) {
+                Ok(c) => inner_content.push(c),
+                Err(msg) => {
+                    lex.extras.report_error(msg, content_start + i);
+                    is_valid = false;
+                }
+            },
+            '\n' => {
+                lex.extras.increase_line_number(content_start + i + 1);
+                inner_content.push(chr);
+            }
+            _ => inner_content.push(chr),
         }
-
-        inner_content.push(chr);
     }
 
-    eprint!("Error: Unclosed string literal.");
+    // An unclosed literal swallows the rest of the file
+    lex.bump(remainder_string.len());
+    lex.extras.errors.push(LexerError {
+        message: "Unclosed string literal.".to_string(),
+        line,
+        column,
+    });
+
     Err(())
 }
 
 fn handle_single_quote(lex: &mut Lexer<LogosToken>) -> Result<char, ()> {
-    let mut remainder_string = lex.remainder().chars();
+    let literal_start = lex.span().start;
+    let content_start = lex.span().end;
+    let mut chars = lex.remainder().char_indices().peekable();
+
+    let content = match chars.next() {
+        Some((i, '\\')) => match decode_escape(&mut chars) {
+            Ok(c) => Some(c),
+            Err(msg) => {
+                lex.extras.report_error(msg, content_start + i);
+                None
+            }
+        },
+        Some((_, '\'')) => {
+            lex.bump(1);
+            lex.extras
+                .report_error("Empty char literal.".to_string(), literal_start);
+            return Err(());
+        }
+        Some((_, '\n')) | None => {
+            lex.extras
+                .report_error("Unclosed char literal.".to_string(), literal_start);
+            return Err(());
+        }
+        Some((_, c)) => Some(c),
+    };
+
+    // Chars are lowered to a byte, like '\x' escapes they are limited to ASCII
+    let content = match content {
+        Some(c) if !c.is_ascii() => {
+            lex.extras.report_error(
+                format!(
+                    "Char literal '{}' is out of range, chars must be in [\\x00-\\x7F].",
+                    c
+                ),
+                literal_start,
+            );
+            None
+        }
+        content => content,
+    };
 
-    if let Some(content) = remainder_string.next() {
-        if let Some(end_quote) = remainder_string.next() {
-            if end_quote == '\'' {
-                lex.bump(2);
-                Ok(content)
-            } else {
-                Err(())
+    match chars.next() {
+        Some((i, '\'')) => {
+            lex.bump(i + 1);
+            content.ok_or(())
+        }
+        Some((_, c)) if content.is_none() && c != '\n' => {
+            // An invalid escape has already been reported, the rest of the
+            // literal is skipped so that it isn't lexed as other tokens
+            if let Some((i, _)) = chars
+                .take_while(|(_, c)| *c != '\n')
+                .find(|(_, c)| *c == '\'')
+            {
+                lex.bump(i + 1);
             }
-        } else {
+
+            Err(())
+        }
+        _ => {
+            // An invalid escape has already been reported
+            if content.is_some() {
+                lex.extras.report_error(
+                    "Expected ''' after char literal, it must hold exactly one character."
+                        .to_string(),
+                    literal_start,
+                );
+            }
+
             Err(())
         }
-    } else {
-        Err(())
     }
 }
 
+/// Parses decimal, hexadecimal (0x), octal (0o) and binary (0b) literals.
+/// '_' can be used as a digit separator. Every base has the same range, up
+/// to the largest 64 bits signed number, negative numbers are negations.
+fn handle_number(lex: &mut Lexer<LogosToken>) -> Result<i64, ()> {
+    let literal = lex.slice();
+    let (radix, digits) = match literal.get(..2) {
+        Some("0x") | Some("0X") => (16, &literal[2..]),
+        Some("0o") | Some("0O") => (8, &literal[2..]),
+        Some("0b") | Some("0B") => (2, &literal[2..]),
+        _ => (10, literal),
+    };
+
+    let digits = digits.replace('_', "");
+    let start = lex.span().start;
+
+    if digits.is_empty() {
+        lex.extras.report_error(
+            format!("Number literal '{}' has no digits.", literal),
+            start,
+        );
+        return Err(());
+    }
+
+    i64::from_str_radix(&digits, radix).map_err(|_| {
+        lex.extras.report_error(
+            format!(
+                "Number literal '{}' is out of range for a 64 bits number.",
+                literal
+            ),
+            start,
+        )
+    })
+}
+
+fn handle_real(lex: &mut Lexer<LogosToken>) -> Result<f64, ()> {
+    let literal = lex.slice();
+    let message = match literal.replace('_', "").parse::<f64>() {
+        Ok(value) if value.is_finite() => return Ok(value),
+        Ok(_) => format!(
+            "Real literal '{}' is out of range for a 64 bits real.",
+            literal
+        ),
+        Err(_) => format!("Malformed real literal '{}'.", literal),
+    };
+
+    lex.extras.report_error(message, lex.span().start);
+    Err(())
+}
+
+/// Skips a line comment. The newline itself is left to the lexer so the
+/// line count stays correct.
 fn handle_comment(lex: &mut Lexer<LogosToken>) -> Skip {
-    let mut chars_to_bump: usize = 0;
+    let remainder = lex.remainder();
+    lex.bump(remainder.find('\n').unwrap_or(remainder.len()));
 
-    for chr in lex.remainder().chars() {
-        if chr == '\n' {
-            lex.bump(chars_to_bump + 1);
-            return Skip {};
-        }
+    Skip {}
+}
 
-        chars_to_bump += 1;
+/// '///' starts a doc comment, its content is kept to be attached to the
+/// following declaration. '////' and more is a regular comment.
+fn handle_doc_comment(lex: &mut Lexer<LogosToken>) -> Filter<String> {
+    let remainder = lex.remainder();
+    let line = &remainder[..remainder.find('\n').unwrap_or(remainder.len())];
+    lex.bump(line.len());
+
+    if line.starts_with('/') {
+        return Filter::Skip;
     }
 
-    Skip {}
+    let content = line.strip_prefix(' ').unwrap_or(line);
+    Filter::Emit(content.trim_end_matches('\r').to_string())
+}
+
+/// Skips a block comment, block comments can be nested.
+/// An unclosed block comment swallows the rest of the file and produces an error.
+fn handle_block_comment(lex: &mut Lexer<LogosToken>) -> Filter<()> {
+    let comment_start = lex.span().start;
+    let content_start = lex.span().end;
+    let remainder = lex.remainder();
+    let mut depth: usize = 1;
+    let mut chars = remainder.char_indices().peekable();
+
+    while let Some((i, chr)) = chars.next() {
+        match (chr, chars.peek()) {
+            ('/', Some((_, '*'))) => {
+                chars.next();
+                depth += 1;
+            }
+            ('*', Some((_, '/'))) => {
+                chars.next();
+                depth -= 1;
+
+                if depth == 0 {
+                    lex.bump(i + 2);
+                    return Filter::Skip;
+                }
+            }
+            ('\n', _) => lex.extras.increase_line_number(content_start + i + 1),
+            _ => {}
+        }
+    }
+
+    lex.bump(remainder.len());
+    lex.extras
+        .report_error("Unclosed block comment.".to_string(), comment_start);
+
+    Filter::Emit(())
 }
 
 fn handle_newline(lex: &mut Lexer<LogosToken>) -> Skip {
@@ -64,35 +328,55 @@ fn handle_newline(lex: &mut Lexer<LogosToken>) -> Skip {
     Skip {}
 }
 
+/// An error detected while lexing, located at the faulty character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexerError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
 pub struct TokenInfo {
     pub line_count: usize,
-    last_newline_position: usize,
+    // Byte offset of the first character of each line
+    line_starts: Vec<usize>,
+    pub errors: Vec<LexerError>,
 }
 
 impl TokenInfo {
     pub fn new() -> Self {
         TokenInfo {
             line_count: 0,
-            last_newline_position: 0,
+            line_starts: vec![0],
+            errors: Vec::new(),
         }
     }
 
     pub fn increase_line_number(&mut self, position: usize) {
         self.line_count += 1;
-        self.last_newline_position = position;
+        self.line_starts.push(position);
+    }
+
+    /// Line (1 indexed) and column of an already lexed position.
+    pub fn position(&self, position: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= position) - 1;
+
+        (line + 1, position - self.line_starts[line])
     }
 
-    pub fn last_newline_index(&self, tk_begin_position: usize) -> usize {
-        tk_begin_position - self.last_newline_position
+    pub fn report_error(&mut self, message: String, position: usize) {
+        let (line, column) = self.position(position);
+        self.errors.push(LexerError {
+            message,
+            line,
+            column,
+        });
     }
 }
 
 impl Default for TokenInfo {
     fn default() -> Self {
-        Self {
-            line_count: Default::default(),
-            last_newline_position: Default::default(),
-        }
+        Self::new()
     }
 }
 
@@ -100,6 +384,10 @@ pub struct Token {
     pub logos_tk: LogosToken,
     pub line_number: usize,
     pub column_number: usize,
+    pub span: Span,
+    /// Errors reported by the lexer while producing this token, only
+    /// `Error` tokens have some.
+    pub errors: Vec<LexerError>,
 }
 
 impl Token {
@@ -108,19 +396,100 @@ impl Token {
         let mut result: Vec<Self> = Vec::new();
 
         while let Some(tk) = lexer.next() {
-            let line_number = lexer.extras.line_count + 1;
+            let range = lexer.span();
+            let (line_number, column_number) = lexer.extras.position(range.start);
+            let (end_line, end_column) = lexer.extras.position(range.end);
+
+            let mut errors: Vec<LexerError> = lexer.extras.errors.drain(..).collect();
+            if tk == LogosToken::Error && errors.is_empty() {
+                errors.push(LexerError {
+                    message: format!("Unexpected character '{}'.", lexer.slice()),
+                    line: line_number,
+                    column: column_number,
+                });
+            }
+
+            if let LogosToken::Real(_) = tk {
+                if lexer.slice().ends_with('.') && lexer.remainder().starts_with('.') {
+                    Self::split_range_start(&mut lexer, &mut result);
+                    continue;
+                }
+            }
+
             result.push(Self {
                 logos_tk: tk,
                 line_number,
-                column_number: lexer.extras.last_newline_index(lexer.span().start),
-            })
+                column_number,
+                span: Span {
+                    start: range.start,
+                    end: range.end,
+                    end_line,
+                    end_column,
+                },
+                errors,
+            });
         }
 
         result
     }
+
+    /// In `1..5` the lexer reads `1.` as a real literal, the token is split
+    /// back into the number and the range operator.
+    fn split_range_start(lexer: &mut Lexer<LogosToken>, result: &mut Vec<Self>) {
+        let start = lexer.span().start;
+        let number_end = lexer.span().end - 1;
+        let digits = lexer.slice()[..number_end - start].replace('_', "");
+
+        // The current token is extended up to the end of the range operator
+        lexer.bump(1);
+        let range_tk = if lexer.remainder().starts_with('=') {
+            lexer.bump(1);
+            LogosToken::DotDotEqual
+        } else {
+            LogosToken::DotDot
+        };
+
+        let mut errors: Vec<LexerError> = lexer.extras.errors.drain(..).collect();
+        let (line_number, column_number) = lexer.extras.position(start);
+        let number_tk = match digits.parse::<i64>() {
+            Ok(value) => LogosToken::Number(value),
+            Err(_) => {
+                errors.push(LexerError {
+                    message: format!(
+                        "Number literal '{}' is out of range for a 64 bits number.",
+                        &lexer.slice()[..number_end - start]
+                    ),
+                    line: line_number,
+                    column: column_number,
+                });
+                LogosToken::Error
+            }
+        };
+
+        for (tk, tk_start, tk_end, errors) in [
+            (number_tk, start, number_end, errors),
+            (range_tk, number_end, lexer.span().end, Vec::new()),
+        ] {
+            let (line_number, column_number) = lexer.extras.position(tk_start);
+            let (end_line, end_column) = lexer.extras.position(tk_end);
+
+            result.push(Self {
+                logos_tk: tk,
+                line_number,
+                column_number,
+                span: Span {
+                    start: tk_start,
+                    end: tk_end,
+                    end_line,
+                    end_column,
+                },
+                errors,
+            });
+        }
+    }
 }
 
-#[derive(Logos, Debug)]
+#[derive(Logos, Debug, Clone)]
 #[logos(extras = TokenInfo)]
 pub enum LogosToken {
     #[token("if")]
@@ -135,6 +504,8 @@ pub enum LogosToken {
     Loop,
     #[token("let")]
     Let,
+    #[token("const")]
+    Const,
     #[token("break")]
     Break,
     #[token("continue")]
@@ -143,12 +514,28 @@ pub enum LogosToken {
     Function,
     #[token("struct")]
     Struct,
+    #[token("enum")]
+    Enum,
+    #[token("union")]
+    Union,
+    #[token("impl")]
+    Impl,
+    #[token("interface")]
+    Interface,
+    #[token("dyn")]
+    Dyn,
     #[token("export")]
     Export,
     #[token("return")]
     Return,
     #[token("import")]
     Import,
+    #[token("as")]
+    As,
+    #[token("from")]
+    From,
+    #[token("match")]
+    Match,
     #[token("=")]
     Equal,
     #[token("+")]
@@ -201,6 +588,18 @@ pub enum LogosToken {
     Colon,
     #[token("::")]
     DoubleColon,
+    #[token("?")]
+    QuestionMark,
+    #[token("=>")]
+    FatArrow,
+    #[token("|")]
+    Pipe,
+    #[token("_")]
+    Underscore,
+    #[token("..")]
+    DotDot,
+    #[token("..=")]
+    DotDotEqual,
     #[token("true")]
     True,
     #[token("false")]
@@ -221,12 +620,19 @@ pub enum LogosToken {
     Null,
 
     // Light types
-    #[regex("(number)|(real)|(bool)|(string)|(void)|(char)", |lex| lex.slice().parse())]
+    #[regex(
+        "(number)|(real)|(bool)|(string)|(void)|(char)|(i8)|(i16)|(i32)|(i64)|(u8)|(u16)|(u32)|(u64)|(f32)|(f64)",
+        |lex| lex.slice().parse()
+    )]
     Type(ValueType),
 
-    #[regex(r"[0-9]+", |lex| lex.slice().parse())]
+    #[regex(r"[0-9][0-9_]*", handle_number)]
+    #[regex(r"0[xX][0-9a-fA-F_]+", handle_number)]
+    #[regex(r"0[oO][0-7_]+", handle_number)]
+    #[regex(r"0[bB][01_]+", handle_number)]
     Number(i64),
-    #[regex(r"[0-9][0-9]*(\.[0-9]*)", |lex| lex.slice().parse())]
+    #[regex(r"[0-9][0-9_]*\.[0-9_]*([eE][+-]?[0-9_]+)?", handle_real)]
+    #[regex(r"[0-9][0-9_]*[eE][+-]?[0-9_]+", handle_real)]
     Real(f64),
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", |lex| lex.slice().parse())]
     Identifier(String),
@@ -237,6 +643,8 @@ pub enum LogosToken {
 
     #[token("//", handle_comment)]
     Comment,
+    #[token("///", handle_doc_comment)]
+    DocComment(String),
 
     // Used to count lines in source code, this token is skipped but the callback
     // is used to increase the line count
@@ -246,6 +654,7 @@ pub enum LogosToken {
     // Skip spaces characters and handle error
     #[error]
     #[regex(r"[ \t\v\r]", logos::skip)]
+    #[token("/*", handle_block_comment)]
     Error,
 }
 
@@ -259,12 +668,15 @@ impl PartialEq for LogosToken {
             (LogosToken::For, LogosToken::For) => true,
             (LogosToken::Loop, LogosToken::Loop) => true,
             (LogosToken::Let, LogosToken::Let) => true,
+            (LogosToken::Const, LogosToken::Const) => true,
             (LogosToken::Break, LogosToken::Break) => true,
             (LogosToken::Continue, LogosToken::Continue) => true,
             (LogosToken::Function, LogosToken::Function) => true,
             (LogosToken::Export, LogosToken::Export) => true,
             (LogosToken::Return, LogosToken::Return) => true,
             (LogosToken::Import, LogosToken::Import) => true,
+            (LogosToken::As, LogosToken::As) => true,
+            (LogosToken::From, LogosToken::From) => true,
             (LogosToken::Equal, LogosToken::Equal) => true,
             (LogosToken::Plus, LogosToken::Plus) => true,
             (LogosToken::Minus, LogosToken::Minus) => true,
@@ -289,6 +701,13 @@ impl PartialEq for LogosToken {
             (LogosToken::Comma, LogosToken::Comma) => true,
             (LogosToken::Semicolon, LogosToken::Semicolon) => true,
             (LogosToken::Colon, LogosToken::Colon) => true,
+            (LogosToken::QuestionMark, LogosToken::QuestionMark) => true,
+            (LogosToken::Match, LogosToken::Match) => true,
+            (LogosToken::FatArrow, LogosToken::FatArrow) => true,
+            (LogosToken::Pipe, LogosToken::Pipe) => true,
+            (LogosToken::Underscore, LogosToken::Underscore) => true,
+            (LogosToken::DotDot, LogosToken::DotDot) => true,
+            (LogosToken::DotDotEqual, LogosToken::DotDotEqual) => true,
             (LogosToken::True, LogosToken::True) => true,
             (LogosToken::False, LogosToken::False) => true,
             (LogosToken::Type(_), LogosToken::Type(_)) => true,
@@ -297,11 +716,17 @@ impl PartialEq for LogosToken {
             (LogosToken::Identifier(_), LogosToken::Identifier(_)) => true,
             (LogosToken::Quote(_), LogosToken::Quote(_)) => true,
             (LogosToken::CharLiteral(_), LogosToken::CharLiteral(_)) => true,
+            (LogosToken::DocComment(_), LogosToken::DocComment(_)) => true,
             (LogosToken::EndOfFile, LogosToken::EndOfFile) => true,
             (LogosToken::Pointer, LogosToken::Pointer) => true,
             (LogosToken::AddressOf, LogosToken::AddressOf) => true,
             (LogosToken::Dereference, LogosToken::Dereference) => true,
             (LogosToken::Struct, LogosToken::Struct) => true,
+            (LogosToken::Enum, LogosToken::Enum) => true,
+            (LogosToken::Union, LogosToken::Union) => true,
+            (LogosToken::Impl, LogosToken::Impl) => true,
+            (LogosToken::Interface, LogosToken::Interface) => true,
+            (LogosToken::Dyn, LogosToken::Dyn) => true,
             (LogosToken::Dot, LogosToken::Dot) => true,
             (LogosToken::Error, LogosToken::Error) => true,
             _ => false,
@@ -317,7 +742,7 @@ impl PartialEq for LogosToken {
 mod tests {
     use crate::type_system::value_type::ValueType;
 
-    use super::LogosToken;
+    use super::{LexerError, LogosToken, Token, TokenInfo};
     use logos::Logos;
 
     #[test]
@@ -650,6 +1075,35 @@ mod tests {
         assert_eq!(lexer.next(), Some(LogosToken::Semicolon));
     }
 
+    #[test]
+    fn sized_types_test() {
+        let mut lexer = LogosToken::lexer("i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 i128");
+        let expected = [
+            ValueType::I8,
+            ValueType::I16,
+            ValueType::I32,
+            ValueType::Number,
+            ValueType::U8,
+            ValueType::U16,
+            ValueType::U32,
+            ValueType::U64,
+            ValueType::F32,
+            ValueType::Real,
+        ];
+
+        for ty in expected {
+            match lexer.next() {
+                Some(LogosToken::Type(lexed)) => assert_eq!(lexed, ty),
+                token => panic!("Expected type {} but got {:?}", ty, token),
+            }
+        }
+
+        assert_eq!(
+            lexer.next(),
+            Some(LogosToken::Identifier("i128".to_string()))
+        );
+    }
+
     #[test]
     fn fn_test() {
         let mut lexer = LogosToken::lexer("fn hey(mom: string): void {}");
@@ -760,6 +1214,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enum_keyword_test() {
+        let mut lexer = LogosToken::lexer("enum Color");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Enum);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier(String::from("Color"))
+        );
+    }
+
+    #[test]
+    fn union_keyword_test() {
+        let mut lexer = LogosToken::lexer("union Shape");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Union);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier(String::from("Shape"))
+        );
+    }
+
+    #[test]
+    fn impl_keyword_test() {
+        let mut lexer = LogosToken::lexer("impl User");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Impl);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier(String::from("User"))
+        );
+    }
+
+    #[test]
+    fn interface_keyword_test() {
+        let mut lexer = LogosToken::lexer("interface Printable dyn Printable");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Interface);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier(String::from("Printable"))
+        );
+        assert_eq!(lexer.next().unwrap(), LogosToken::Dyn);
+    }
+
     #[test]
     fn dot_keyword_test() {
         let mut lexer = LogosToken::lexer("obj.member");
@@ -790,6 +1285,92 @@ mod tests {
         assert_eq!(lexer.next().unwrap(), LogosToken::Semicolon);
     }
 
+    #[test]
+    fn import_module_alias_test() {
+        let mut lexer = LogosToken::lexer("import \"stdio\" as io;");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Import);
+        assert_eq!(lexer.next().unwrap(), LogosToken::Quote(String::new()));
+        assert_eq!(lexer.next().unwrap(), LogosToken::As);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier("io".to_string())
+        );
+        assert_eq!(lexer.next().unwrap(), LogosToken::Semicolon);
+    }
+
+    #[test]
+    fn selective_import_test() {
+        let mut lexer = LogosToken::lexer("import { println } from \"stdio\";");
+        assert_eq!(lexer.next().unwrap(), LogosToken::Import);
+        assert_eq!(lexer.next().unwrap(), LogosToken::LeftBrace);
+        assert_eq!(
+            lexer.next().unwrap(),
+            LogosToken::Identifier("println".to_string())
+        );
+        assert_eq!(lexer.next().unwrap(), LogosToken::RightBrace);
+        assert_eq!(lexer.next().unwrap(), LogosToken::From);
+        assert_eq!(lexer.next().unwrap(), LogosToken::Quote(String::new()));
+        assert_eq!(lexer.next().unwrap(), LogosToken::Semicolon);
+    }
+
+    #[test]
+    fn question_mark_test() {
+        let mut lexer = LogosToken::lexer("a ? b : c");
+
+        lexer.next();
+        assert_eq!(lexer.next(), Some(LogosToken::QuestionMark));
+        lexer.next();
+        assert_eq!(lexer.next(), Some(LogosToken::Colon));
+    }
+
+    #[test]
+    fn const_test() {
+        let mut lexer = LogosToken::lexer("export const MAX: number = 10;");
+
+        assert_eq!(lexer.next(), Some(LogosToken::Export));
+        assert_eq!(lexer.next(), Some(LogosToken::Const));
+        assert!(matches!(lexer.next(), Some(LogosToken::Identifier(id)) if id == "MAX"));
+    }
+
+    #[test]
+    fn match_tokens_test() {
+        let mut lexer = LogosToken::lexer("match c { 'a' | _ => {} }");
+
+        assert_eq!(lexer.next(), Some(LogosToken::Match));
+        lexer.next();
+        lexer.next();
+        lexer.next();
+        assert_eq!(lexer.next(), Some(LogosToken::Pipe));
+        assert_eq!(lexer.next(), Some(LogosToken::Underscore));
+        assert_eq!(lexer.next(), Some(LogosToken::FatArrow));
+    }
+
+    #[test]
+    fn range_test() {
+        let tokens: Vec<LogosToken> = Token::lex_string("1..5 1..=1_0 a .. b 2.5")
+            .into_iter()
+            .map(|tk| tk.logos_tk)
+            .collect();
+
+        assert!(matches!(tokens[0], LogosToken::Number(1)));
+        assert_eq!(tokens[1], LogosToken::DotDot);
+        assert!(matches!(tokens[2], LogosToken::Number(5)));
+        assert!(matches!(tokens[3], LogosToken::Number(1)));
+        assert_eq!(tokens[4], LogosToken::DotDotEqual);
+        assert!(matches!(tokens[5], LogosToken::Number(10)));
+        assert_eq!(tokens[7], LogosToken::DotDot);
+        assert!(matches!(tokens[9], LogosToken::Real(_)));
+    }
+
+    #[test]
+    fn range_span_test() {
+        let tokens = Token::lex_string("10..=20");
+
+        assert_eq!((tokens[0].span.start, tokens[0].span.end), (0, 2));
+        assert_eq!((tokens[1].span.start, tokens[1].span.end), (2, 5));
+        assert_eq!(tokens[2].column_number, 5);
+    }
+
     #[test]
     fn double_colon_test() {
         let mut lexer = LogosToken::lexer("module::function()");
@@ -859,4 +1440,289 @@ mod tests {
         let tk = lexer.next().unwrap();
         assert_eq!(tk, LogosToken::Error);
     }
+
+    #[test]
+    fn escaped_string_literal_test() {
+        let mut lexer = LogosToken::lexer(r#""a\tb\n\"c\" \\ \'\0""#);
+
+        assert!(if let LogosToken::Quote(s) = lexer.next().unwrap() {
+            s == "a\tb\n\"c\" \\ '\0"
+        } else {
+            false
+        });
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn hex_and_unicode_escape_test() {
+        let mut lexer = LogosToken::lexer(r#""\x41\x7f\u{e9}\u{1F600}""#);
+
+        assert!(if let LogosToken::Quote(s) = lexer.next().unwrap() {
+            s == "A\x7f\u{e9}\u{1F600}"
+        } else {
+            false
+        });
+    }
+
+    #[test]
+    fn escaped_char_literal_test() {
+        let mut lexer = LogosToken::lexer(r"'\n' '\0' '\'' '\\' '\x41' '\u{7A}'");
+
+        for expected in ['\n', '\0', '\'', '\\', 'A', 'z'] {
+            let tk = lexer.next().unwrap();
+            assert!(
+                if let LogosToken::CharLiteral(c) = tk {
+                    c == expected
+                } else {
+                    false
+                },
+                "Got {:?}",
+                tk
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_escape_location_test() {
+        let mut lexer =
+            LogosToken::lexer_with_extras("let s: string =\n  \"ab\\qc\";", TokenInfo::new());
+
+        while let Some(tk) = lexer.next() {
+            if let LogosToken::Quote(_) = tk {
+                assert!(false, "Invalid literal should not be lexed as a string");
+            }
+        }
+
+        assert_eq!(
+            lexer.extras.errors,
+            vec![LexerError {
+                message: "Unknown escape sequence '\\q'.".to_string(),
+                line: 2,
+                column: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn malformed_hex_escape_test() {
+        let mut lexer = LogosToken::lexer_with_extras(r#""\x4g" "\x80""#, TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 2);
+        assert_eq!(lexer.extras.errors[0].column, 1);
+        assert_eq!(lexer.extras.errors[1].column, 8);
+    }
+
+    #[test]
+    fn malformed_unicode_escape_test() {
+        let mut lexer =
+            LogosToken::lexer_with_extras(r#"'\u{D800}' '\u41' '\u{}'"#, TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 1);
+        assert_eq!(lexer.extras.errors[0].column, 1);
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 2);
+    }
+
+    #[test]
+    fn malformed_char_escape_skips_literal_test() {
+        let mut lexer = LogosToken::lexer_with_extras(r"'\q' '\u41' 'a'", TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.next(), Some(LogosToken::CharLiteral('a')));
+        assert_eq!(lexer.next(), None);
+        assert_eq!(lexer.extras.errors.len(), 2);
+        assert_eq!(lexer.extras.errors[1].column, 6);
+    }
+
+    #[test]
+    fn non_ascii_char_literal_test() {
+        let mut lexer = LogosToken::lexer_with_extras(
+            "'\\u{1F600}' 'é' '\\u{80}' '\\x80' '\\u{7F}'",
+            TokenInfo::new(),
+        );
+
+        for _ in 0..4 {
+            assert_eq!(lexer.next(), Some(LogosToken::Error));
+        }
+        assert_eq!(lexer.next(), Some(LogosToken::CharLiteral('\x7F')));
+        assert_eq!(lexer.next(), None);
+
+        let columns: Vec<usize> = lexer.extras.errors.iter().map(|e| e.column).collect();
+        assert_eq!(columns, vec![0, 12, 17, 27]);
+        assert_eq!(
+            lexer.extras.errors[1].message,
+            "Char literal 'é' is out of range, chars must be in [\\x00-\\x7F]."
+        );
+    }
+
+    #[test]
+    fn multiline_string_keeps_line_count_test() {
+        let tokens = Token::lex_string("\"a\nb\"\n;");
+
+        assert_eq!(tokens[0].line_number, 1);
+        assert_eq!(tokens[1].logos_tk, LogosToken::Semicolon);
+        assert_eq!(tokens[1].line_number, 3);
+        assert_eq!(tokens[1].column_number, 0);
+    }
+
+    #[test]
+    fn number_literal_bases_test() {
+        let values: Vec<i64> = LogosToken::lexer("0xFF 0b1010 0o755 1_000_000 0x_dead_BEEF")
+            .map(|tk| match tk {
+                LogosToken::Number(n) => n,
+                _ => panic!("Expected a number got {:?}", tk),
+            })
+            .collect();
+
+        assert_eq!(values, vec![255, 10, 493, 1_000_000, 0xdead_beef]);
+    }
+
+    #[test]
+    fn number_literal_bounds_test() {
+        let source = "9223372036854775807 0x7FFF_FFFF_FFFF_FFFF \
+                      0o777777777777777777777 0b0111111111111111111111111111111111111111111111111111111111111111";
+        let values: Vec<i64> = LogosToken::lexer(source)
+            .map(|tk| match tk {
+                LogosToken::Number(n) => n,
+                _ => panic!("Expected a number got {:?}", tk),
+            })
+            .collect();
+        assert_eq!(values, vec![i64::MAX; 4]);
+
+        // Every base is out of range past i64::MAX
+        let source = "9223372036854775808 0x8000_0000_0000_0000 \
+                      0o1000000000000000000000 0b1000000000000000000000000000000000000000000000000000000000000000";
+        let mut lexer = LogosToken::lexer_with_extras(source, TokenInfo::new());
+        let mut count = 0;
+        while let Some(tk) = lexer.next() {
+            count += 1;
+            assert_eq!(tk, LogosToken::Error);
+            let error = lexer.extras.errors.pop().unwrap();
+            assert_eq!(
+                error.message,
+                format!(
+                    "Number literal '{}' is out of range for a 64 bits number.",
+                    lexer.slice()
+                )
+            );
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn real_literal_exponent_test() {
+        let values: Vec<f64> = LogosToken::lexer("1.5e-3 2E10 3. 1_000.5 6.02e+23")
+            .map(|tk| match tk {
+                LogosToken::Real(r) => r,
+                _ => panic!("Expected a real got {:?}", tk),
+            })
+            .collect();
+
+        assert_eq!(values, vec![1.5e-3, 2e10, 3.0, 1000.5, 6.02e23]);
+    }
+
+    #[test]
+    fn number_literal_out_of_range_test() {
+        let mut lexer = LogosToken::lexer_with_extras(
+            "let n: number =\n 9223372036854775808;",
+            TokenInfo::new(),
+        );
+
+        assert_eq!(lexer.nth(5), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 1);
+        assert_eq!(lexer.extras.errors[0].line, 2);
+        assert_eq!(lexer.extras.errors[0].column, 1);
+    }
+
+    #[test]
+    fn hex_literal_out_of_range_test() {
+        let mut lexer = LogosToken::lexer_with_extras("0x1_0000_0000_0000_0000", TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 1);
+    }
+
+    #[test]
+    fn real_literal_out_of_range_test() {
+        let mut lexer = LogosToken::lexer_with_extras("1e400", TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 1);
+    }
+
+    #[test]
+    fn empty_prefixed_literal_test() {
+        let mut lexer = LogosToken::lexer_with_extras("0x_", TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.extras.errors.len(), 1);
+    }
+
+    #[test]
+    fn line_comment_keeps_line_count_test() {
+        let tokens = Token::lex_string("// comment\n// other comment\nlet");
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].logos_tk, LogosToken::Let);
+        assert_eq!(tokens[0].line_number, 3);
+    }
+
+    #[test]
+    fn line_comment_at_end_of_file_test() {
+        let mut lexer = LogosToken::lexer("let // let");
+
+        assert_eq!(lexer.next(), Some(LogosToken::Let));
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn nested_block_comment_test() {
+        let tokens = Token::lex_string("/* a /* nested\n */ comment */ let /**/ fn");
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].logos_tk, LogosToken::Let);
+        assert_eq!(tokens[0].line_number, 2);
+        assert_eq!(tokens[1].logos_tk, LogosToken::Function);
+    }
+
+    #[test]
+    fn unclosed_block_comment_test() {
+        let mut lexer = LogosToken::lexer_with_extras("let /* a /* b */ fn", TokenInfo::new());
+
+        assert_eq!(lexer.next(), Some(LogosToken::Let));
+        assert_eq!(lexer.next(), Some(LogosToken::Error));
+        assert_eq!(lexer.next(), None);
+        assert_eq!(lexer.extras.errors.len(), 1);
+        assert_eq!(lexer.extras.errors[0].column, 4);
+    }
+
+    #[test]
+    fn doc_comment_test() {
+        let mut lexer = LogosToken::lexer("/// Prints a message\n//// not a doc\nfn");
+
+        assert!(if let LogosToken::DocComment(doc) = lexer.next().unwrap() {
+            doc == "Prints a message"
+        } else {
+            false
+        });
+        assert_eq!(lexer.next(), Some(LogosToken::Function));
+    }
+
+    #[test]
+    fn token_span_test() {
+        let tokens = Token::lex_string("let s =\n\"a\nb\";");
+
+        assert_eq!(tokens[1].span.start, 4);
+        assert_eq!(tokens[1].span.end, 5);
+        assert_eq!(tokens[3].line_number, 2);
+        assert_eq!(tokens[3].column_number, 0);
+        assert_eq!(tokens[3].span.end_line, 3);
+        assert_eq!(tokens[3].span.end_column, 2);
+        assert_eq!(tokens[4].span.start, 13);
+    }
 }
@@ -1,7 +1,8 @@
-mod debug;
+pub mod debug;
 pub mod desugar;
 pub mod generation;
 pub mod lexer;
+pub mod lint;
 pub mod parser;
 pub mod type_system;
 
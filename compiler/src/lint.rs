@@ -0,0 +1,138 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::debug::LineDebugInfo;
+
+/// Checks reporting code that compiles but is likely a mistake. Their
+/// warnings don't stop the compilation unless the lint is denied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// Local variables and parameters never read
+    UnusedVariables,
+    /// Imported modules and items never named by the importer
+    UnusedImports,
+    /// Statements following a `return`, `break` or `continue`
+    UnreachableCode,
+    /// Match arms and patterns covered by the previous arms
+    UnreachablePatterns,
+    /// Parameters, `let` and match bindings hiding a variable of an enclosing
+    /// scope
+    Shadowing,
+    /// Local variables declared without init expression read before being
+    /// assigned
+    UninitialisedUse,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 6] = [
+        Lint::UnusedVariables,
+        Lint::UnusedImports,
+        Lint::UnreachableCode,
+        Lint::UnreachablePatterns,
+        Lint::Shadowing,
+        Lint::UninitialisedUse,
+    ];
+
+    /// Name of the lint on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused-variables",
+            Lint::UnusedImports => "unused-imports",
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::UnreachablePatterns => "unreachable-patterns",
+            Lint::Shadowing => "shadowing",
+            Lint::UninitialisedUse => "uninitialised-use",
+        }
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .into_iter()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Lint::ALL.iter().map(|lint| lint.name()).collect();
+                format!(
+                    "Unknown lint '{}', expected one of {}.",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The warnings aren't reported
+    Allow,
+    Warn,
+    /// The warnings are reported as errors
+    Deny,
+}
+
+/// Level of each lint, they all warn by default
+#[derive(Clone, Debug, Default)]
+pub struct LintLevels {
+    levels: HashMap<Lint, LintLevel>,
+}
+
+impl LintLevels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    pub fn get(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or(LintLevel::Warn)
+    }
+}
+
+/// Non fatal diagnostic reported by a lint
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    pub lint: Lint,
+    pub filename: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new<T: LineDebugInfo>(lint: Lint, message: &str, element: &T) -> Self {
+        Warning {
+            lint,
+            filename: element.file_name().to_string(),
+            line: element.line(),
+            column: element.column(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Formats the warning of a denied lint
+    pub fn as_error(&self) -> String {
+        format!(
+            "{}:{}:{} Error: {}",
+            self.filename, self.line, self.column, self.message
+        )
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{}:{}:{} Warning: {}",
+            self.filename, self.line, self.column, self.message
+        ))
+    }
+}
@@ -1,8 +1,13 @@
+use std::fmt::Display;
+
 use super::visitors::{
-    AddressOf, ArrayAccess, Binary, BinaryLogic, BlockStatement, BreakStatement, Call, DeReference,
-    Expression, ExpressionVisitor, ForStatement, FunctionStatement, Group, IfStatement,
-    ImportStatement, Literal, MemberAccess, ReturnStatement, Statement, StatementVisitor,
-    StructLiteral, StructStatement, Unary, VariableAssignment, VariableDeclaration, WhileStatement,
+    AddressOf, ArrayAccess, ArrayLiteral, ArrayRepeat, Binary, BinaryLogic, BlockStatement,
+    BreakStatement, Call, Cast, Conditional, ContinueStatement, DeReference, EnumStatement,
+    EnumVariant, Expression, ExpressionVisitor, ForStatement, FunctionStatement, Group,
+    IfStatement, ImplStatement, ImportStatement, InterfaceStatement, Literal, MatchStatement,
+    MemberAccess, ModuleAccess, Pattern, ReturnStatement, Statement, StatementVisitor,
+    StructLiteral, StructStatement, Unary, UnionStatement, UnionVariant, VariableAssignment,
+    VariableDeclaration, WhileStatement,
 };
 
 struct AstPrinter;
@@ -19,9 +24,15 @@ impl AstPrinter {
             Statement::IfStatement(if_stmt) => self.visit_if_statement(if_stmt),
             Statement::WhileStatement(while_stmt) => self.visit_while_statement(while_stmt),
             Statement::ForStatement(for_stmt) => self.visit_for_statement(for_stmt),
+            Statement::MatchStatement(match_stmt) => self.visit_match_statement(match_stmt),
             Statement::BreakStatement(b) => self.visit_break_statement(b),
+            Statement::ContinueStatement(c) => self.visit_continue_statement(c),
             Statement::Struct(struct_stmt) => self.visit_struct_statement(struct_stmt),
-            Statement::Import(_) => todo!(),
+            Statement::Enum(enum_stmt) => self.visit_enum_statement(enum_stmt),
+            Statement::Union(union_stmt) => self.visit_union_statement(union_stmt),
+            Statement::Impl(impl_stmt) => self.visit_impl_statement(impl_stmt),
+            Statement::Interface(interface) => self.visit_interface_statement(interface),
+            Statement::Import(import_stmt) => self.visit_import_statement(import_stmt),
         }
     }
 
@@ -38,6 +49,13 @@ impl AstPrinter {
             Expression::AddressOf(address_of) => self.visit_address_of_expression(address_of),
             Expression::DeReference(deref) => self.visit_dereference_expression(deref),
             Expression::MemberAccess(member_access) => self.visit_member_access(member_access),
+            Expression::Conditional(conditional) => self.visit_conditional(conditional),
+            Expression::ArrayLiteral(array_literal) => self.visit_array_literal(array_literal),
+            Expression::ArrayRepeat(array_repeat) => self.visit_array_repeat(array_repeat),
+            Expression::ModuleAccess(module_access) => self.visit_module_access(module_access),
+            Expression::Cast(cast) => self.visit_cast(cast),
+            Expression::EnumVariant(enum_variant) => self.visit_enum_variant(enum_variant),
+            Expression::UnionVariant(union_variant) => self.visit_union_variant(union_variant),
         }
     }
 
@@ -130,17 +148,19 @@ impl ExpressionVisitor<()> for AstPrinter {
 
     fn visit_unary(&mut self, unary: &super::visitors::Unary) -> () {
         match unary {
-            Unary::Not(val) => {
+            Unary::Not(_, val) => {
                 self.print_body("Not", &[val]);
             }
-            Unary::Negate(val) => {
+            Unary::Negate(_, val) => {
                 self.print_body("Negate", &[val]);
             }
         }
     }
 
     fn visit_call(&mut self, call_expr: &Call) -> () {
-        print!("call {}(", &call_expr.name);
+        print!("call {}", &call_expr.name);
+        print_type_list(&call_expr.type_args);
+        print!("(");
 
         if let Some(args) = &call_expr.args {
             for arg in args {
@@ -186,7 +206,76 @@ impl ExpressionVisitor<()> for AstPrinter {
     fn visit_member_access(&mut self, member_access: &MemberAccess) -> () {
         print!(" Access [");
         self.visit_expr(&member_access.object);
-        print!(", {}] ", member_access.member);
+        print!(", {}", member_access.member);
+
+        if let Some(args) = &member_access.args {
+            print!("(");
+            for arg in args {
+                self.visit_expr(arg);
+            }
+            print!(")");
+        }
+
+        print!("] ");
+    }
+
+    fn visit_conditional(&mut self, conditional: &Conditional) -> () {
+        print!(" Conditional [");
+        self.visit_expr(&conditional.condition);
+        print!(" ? ");
+        self.visit_expr(&conditional.then_expr);
+        print!(" : ");
+        self.visit_expr(&conditional.else_expr);
+        print!("] ");
+    }
+
+    fn visit_array_literal(&mut self, array_literal: &ArrayLiteral) -> () {
+        print!("Array Literal [");
+
+        array_literal.elements.iter().for_each(|e| {
+            self.visit_expr(e);
+            print!(", ");
+        });
+
+        print!("]");
+    }
+
+    fn visit_array_repeat(&mut self, array_repeat: &ArrayRepeat) -> () {
+        print!("Array Repeat [");
+        self.visit_expr(&array_repeat.value);
+        print!("; {}]", array_repeat.count);
+    }
+
+    fn visit_module_access(&mut self, module_access: &ModuleAccess) -> () {
+        print!("{}::", module_access.module);
+        self.visit_expr(&module_access.item);
+    }
+
+    fn visit_cast(&mut self, cast: &Cast) -> () {
+        print!(" Cast [");
+        self.visit_expr(&cast.expr);
+        print!(" as {}] ", cast.target);
+    }
+
+    fn visit_enum_variant(&mut self, enum_variant: &EnumVariant) -> () {
+        print!(
+            "{}::{} ({})",
+            enum_variant.enum_name, enum_variant.variant, enum_variant.value
+        );
+    }
+
+    fn visit_union_variant(&mut self, union_variant: &UnionVariant) -> () {
+        print!(
+            "{}::{} ({}) [",
+            union_variant.union_name, union_variant.variant, union_variant.tag
+        );
+
+        for expr in &union_variant.payload {
+            self.visit_expr(expr);
+            print!(", ");
+        }
+
+        print!("]");
     }
 }
 
@@ -199,10 +288,17 @@ impl StatementVisitor<()> for AstPrinter {
 
     fn visit_declaration_statement(&mut self, expr: &VariableDeclaration) -> () {
         print!(
-            "Declaration: [identifier: {}, typename: {:?}, init_expr: ",
-            expr.identifier, expr.variable_type
+            "Declaration: [{}identifier: {}, typename: {:?}, init_expr: ",
+            if expr.is_const { "const " } else { "" },
+            expr.identifier,
+            expr.variable_type
         );
-        self.visit_expr(&expr.init_expr);
+
+        if let Some(init_expr) = &expr.init_expr {
+            self.visit_expr(init_expr);
+        } else {
+            print!("extern");
+        }
         println!("] ");
     }
 
@@ -213,7 +309,23 @@ impl StatementVisitor<()> for AstPrinter {
     }
 
     fn visit_function_statement(&mut self, expr: &FunctionStatement) -> () {
-        print!("Function {}(", expr.callee);
+        if let Some(doc) = &expr.doc {
+            print_doc(doc, "");
+        }
+
+        print!("Function {}", expr.callee);
+        let type_params: Vec<String> = expr
+            .type_params
+            .iter()
+            .map(
+                |param| match expr.type_bounds.iter().find(|(p, _)| p == param) {
+                    Some((_, interface)) => format!("{}: {}", param, interface),
+                    None => param.clone(),
+                },
+            )
+            .collect();
+        print_type_list(&type_params);
+        print!("(");
 
         if let Some(args) = &expr.args {
             for arg in args {
@@ -264,6 +376,12 @@ impl StatementVisitor<()> for AstPrinter {
         self.visit_expr(&while_stmt.condition);
         print!(" block: ");
         self.visit_block_statement(&while_stmt.loop_block);
+
+        if let Some(next_expr) = &while_stmt.next_expr {
+            print!(" next: ");
+            self.visit_stmt(next_expr);
+        }
+
         print!(" endwhile\n");
     }
 
@@ -277,23 +395,146 @@ impl StatementVisitor<()> for AstPrinter {
         print!(") \n");
     }
 
+    fn visit_match_statement(&mut self, match_stmt: &MatchStatement) -> () {
+        print!("Match ");
+        self.visit_expr(&match_stmt.scrutinee);
+
+        for arm in &match_stmt.arms {
+            let patterns: Vec<String> = arm.patterns.iter().map(Pattern::to_string).collect();
+            print!(" arm {}: ", patterns.join(" | "));
+            self.visit_block_statement(&arm.block);
+        }
+
+        println!(" endmatch");
+    }
+
     fn visit_break_statement(&mut self, _break_stmt: &BreakStatement) {
         println!("Break");
     }
 
+    fn visit_continue_statement(&mut self, _continue_stmt: &ContinueStatement) {
+        println!("Continue");
+    }
+
     fn visit_struct_statement(&mut self, stct: &StructStatement) -> () {
-        println!("Struct {} [", stct.type_name);
+        if let Some(doc) = &stct.doc {
+            print_doc(doc, "");
+        }
+
+        print!("Struct {}", stct.type_name);
+        print_type_list(&stct.type_params);
+        println!(" [");
+
+        for (field_name, field_type, field_doc, default_value) in &stct.fields {
+            if let Some(doc) = field_doc {
+                print_doc(doc, "\t");
+            }
+
+            print!("\t{}: {}", field_name, field_type);
+            if let Some(default_value) = default_value {
+                print!(" = ");
+                self.visit_expr(default_value);
+            }
+            println!(";");
+        }
+    }
+
+    fn visit_enum_statement(&mut self, enm: &EnumStatement) -> () {
+        if let Some(doc) = &enm.doc {
+            print_doc(doc, "");
+        }
+
+        println!("Enum {} [", enm.type_name);
 
-        for (field_name, field_type) in &stct.fields {
-            println!("\t{}: {};", field_name, field_type);
+        for (variant, value, variant_doc) in &enm.variants {
+            if let Some(doc) = variant_doc {
+                print_doc(doc, "\t");
+            }
+
+            println!("\t{} = {},", variant, value);
         }
+
+        println!("]");
+    }
+
+    fn visit_union_statement(&mut self, union_stmt: &UnionStatement) -> () {
+        if let Some(doc) = &union_stmt.doc {
+            print_doc(doc, "");
+        }
+
+        println!("Union {} [", union_stmt.type_name);
+
+        for (variant, payload, variant_doc) in &union_stmt.variants {
+            if let Some(doc) = variant_doc {
+                print_doc(doc, "\t");
+            }
+
+            let payload: Vec<String> = payload.iter().map(|ty| ty.to_string()).collect();
+            println!("\t{}({}),", variant, payload.join(", "));
+        }
+
+        println!("]");
+    }
+
+    fn visit_impl_statement(&mut self, impl_stmt: &ImplStatement) -> () {
+        match &impl_stmt.interface {
+            Some(interface) => println!("Impl {} for {} [", interface, impl_stmt.type_name),
+            None => println!("Impl {} [", impl_stmt.type_name),
+        }
+
+        for method in &impl_stmt.methods {
+            self.visit_function_statement(method);
+        }
+
+        println!("]");
+    }
+
+    fn visit_interface_statement(&mut self, interface: &InterfaceStatement) -> () {
+        if let Some(doc) = &interface.doc {
+            print_doc(doc, "");
+        }
+
+        println!("Interface {} [", interface.type_name);
+
+        for method in &interface.methods {
+            self.visit_function_statement(method);
+        }
+
+        println!("]");
     }
 
     fn visit_import_statement(&mut self, import_stmt: &ImportStatement) -> () {
-        println!(
+        if import_stmt.is_exported {
+            print!("Export ");
+        }
+
+        print!(
             "Import {} from '{}'",
             import_stmt.module_path, import_stmt.file_path
         );
+
+        if let Some(items) = &import_stmt.items {
+            print!(" {{ {} }}", items.join(", "));
+        }
+
+        match &import_stmt.alias {
+            Some(alias) => println!(" as {}", alias),
+            None => println!(),
+        }
+    }
+}
+
+fn print_doc(doc: &str, indent: &str) {
+    for line in doc.lines() {
+        println!("{}/// {}", indent, line);
+    }
+}
+
+/// `<T, ...>` after a generic item, nothing if the list is empty
+fn print_type_list<T: Display>(types: &[T]) {
+    if !types.is_empty() {
+        let types: Vec<String> = types.iter().map(|ty| ty.to_string()).collect();
+        print!("<{}>", types.join(", "));
     }
 }
 
@@ -1,16 +1,50 @@
-use crate::lexer::LogosToken;
+use crate::{lexer::LogosToken, type_system::value_type::ValueType};
 
 use super::{
     literals::{Bool, Char, Number, Real, StringLiteral},
     parser::Parser,
     visitors::{
-        AddressOf, ArrayAccess, Binary, BinaryLogic, Call, DeReference, Expression, Group,
-        Identifier, Literal, MemberAccess, Null, StructLiteral, Unary,
+        AddressOf, ArrayAccess, ArrayLiteral, ArrayRepeat, Binary, BinaryLogic, Call, Cast,
+        Conditional, DeReference, Expression, Group, Identifier, Literal, MemberAccess,
+        ModuleAccess, Null, StructLiteral, Unary, UnaryOperator,
     },
 };
 
 impl Parser {
-    pub fn or(&mut self) -> Result<Expression, ()> {
+    /// Entry point of expressions, the conditional has the lowest precedence.
+    pub fn expression(&mut self) -> Result<Expression, ()> {
+        let begin = self.begin_node();
+        let condition = self.or()?;
+
+        if self.match_expr(&[LogosToken::QuestionMark]) {
+            let then_expr = self.expression()?;
+
+            if let None = self.consume(
+                &LogosToken::Colon,
+                "Expected ':' after the first branch of conditional expression.",
+            ) {
+                return Err(());
+            }
+
+            let else_expr = self.expression()?;
+
+            let (line, column, span) = self.location_from(begin);
+            return Ok(Expression::Conditional(Conditional {
+                condition: Box::new(condition),
+                then_expr: Box::new(then_expr),
+                else_expr: Box::new(else_expr),
+                ty: None,
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
+            }));
+        }
+
+        Ok(condition)
+    }
+
+    fn or(&mut self) -> Result<Expression, ()> {
         let mut left = self.and()?;
 
         while let Some(LogosToken::Or) = self.expect(&LogosToken::Or) {
@@ -120,7 +154,7 @@ impl Parser {
     }
 
     fn factor(&mut self) -> Result<Expression, ()> {
-        let mut left = self.unary()?;
+        let mut left = self.cast()?;
 
         loop {
             match self.expect_tokens(&[
@@ -129,15 +163,15 @@ impl Parser {
                 LogosToken::Modulo,
             ]) {
                 Some(LogosToken::Multiply) => {
-                    let right = self.unary()?;
+                    let right = self.cast()?;
                     left = Expression::Binary(Binary::Multiply(Box::new(left), Box::new(right)));
                 }
                 Some(LogosToken::Divide) => {
-                    let right = self.unary()?;
+                    let right = self.cast()?;
                     left = Expression::Binary(Binary::Divide(Box::new(left), Box::new(right)));
                 }
                 Some(LogosToken::Modulo) => {
-                    let right = self.unary()?;
+                    let right = self.cast()?;
                     left = Expression::Binary(Binary::Modulo(Box::new(left), Box::new(right)));
                 }
                 _ => break,
@@ -147,17 +181,92 @@ impl Parser {
         Ok(left)
     }
 
-    fn unary(&mut self) -> Result<Expression, ()> {
-        match self.expect_tokens(&[LogosToken::Minus, LogosToken::Not]) {
-            Some(LogosToken::Minus) => Ok(Expression::Unary(Unary::Negate(Box::new(self.call()?)))),
-            Some(LogosToken::Not) => Ok(Expression::Unary(Unary::Not(Box::new(self.call()?)))),
-            _ => Ok(self.call()?),
+    /// `expr as type`, binds tighter than the binary operators
+    fn cast(&mut self) -> Result<Expression, ()> {
+        let begin = self.begin_node();
+        let mut expr = self.unary()?;
+
+        while self.match_expr(&[LogosToken::As]) {
+            let target = self.parse_type()?;
+
+            let (line, column, span) = self.location_from(begin);
+            expr = Expression::Cast(Cast {
+                expr: Box::new(expr),
+                target,
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
+            });
         }
+
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ()> {
+        let begin = self.begin_node();
+        let operator = match self.expect_tokens(&[LogosToken::Minus, LogosToken::Not]) {
+            Some(operator) => operator.clone(),
+            None => return self.call(),
+        };
+
+        let (line, column, span) = self.location_from(begin);
+        let unary_operator = UnaryOperator {
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        };
+        let operand = Box::new(self.call()?);
+
+        Ok(Expression::Unary(if operator == LogosToken::Minus {
+            Unary::Negate(unary_operator, operand)
+        } else {
+            Unary::Not(unary_operator, operand)
+        }))
     }
 
     fn call(&mut self) -> Result<Expression, ()> {
+        let begin = self.begin_node();
         let primary_expr = self.primary()?;
 
+        // `name::<type, ...>(args)` calls a generic function with the given
+        // type arguments
+        let is_generic_call =
+            self.check(&LogosToken::DoubleColon) && self.peek_nth(1) == Some(&LogosToken::Less);
+
+        if let Expression::Literal(Literal::Identifier(module)) = &primary_expr {
+            if !is_generic_call && self.match_expr(&[LogosToken::DoubleColon]) {
+                let module = module.name.clone();
+                let item = self.call()?;
+
+                let (line, column, span) = self.location_from(begin);
+                return Ok(Expression::ModuleAccess(ModuleAccess {
+                    module,
+                    item: Box::new(item),
+                    line,
+                    column,
+                    filename: self.file.clone(),
+                    span,
+                }));
+            }
+        }
+
+        let type_args = if is_generic_call {
+            self.advance();
+            self.advance();
+            let type_args = self.parse_type_args()?;
+
+            if !self.check(&LogosToken::LeftParenthesis) {
+                self.put_error_at_current_token("Expected '(' after type arguments.");
+                return Err(());
+            }
+
+            type_args
+        } else {
+            Vec::new()
+        };
+
         if self.match_expr(&[LogosToken::LeftParenthesis]) {
             let mut args: Vec<Expression> = Vec::new();
 
@@ -166,7 +275,7 @@ impl Parser {
                     break;
                 }
 
-                args.push(self.or()?);
+                args.push(self.expression()?);
 
                 if !self.match_expr(&[LogosToken::Comma]) {
                     break;
@@ -183,86 +292,255 @@ impl Parser {
             let identifier = match primary_expr {
                 Expression::Literal(Literal::Identifier(n)) => n,
                 _ => {
-                    self.put_error_at_current_token(
-                        "Error: Expected identifier before function call.",
-                    );
+                    self.put_error_at_current_token("Expected identifier before function call.");
                     return Err(());
                 }
             };
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Expression::Call(Call {
                 name: identifier.name,
                 ty: None,
                 args: if !args.is_empty() { Some(args) } else { None },
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                type_args,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
         if self.match_expr(&[LogosToken::Dot]) {
-            let rhs = if let Expression::Literal(Literal::Identifier(id)) = self.or()? {
-                id.name
+            let member = if let Some(LogosToken::Identifier(id)) = self.consume(
+                &LogosToken::Identifier(String::new()),
+                "Expected identifier after Dot member access.",
+            ) {
+                id.clone()
             } else {
-                self.put_error_at_current_token("Expected identifier after Dot member access.");
                 return Err(());
             };
 
-            let debug_tk = self.peek_token_with_info_debug();
+            // `object.method(args)`
+            let args = if self.match_expr(&[LogosToken::LeftParenthesis]) {
+                let mut args: Vec<Expression> = Vec::new();
+
+                loop {
+                    if self.check(&LogosToken::RightParenthesis) {
+                        break;
+                    }
+
+                    args.push(self.expression()?);
+
+                    if !self.match_expr(&[LogosToken::Comma]) {
+                        break;
+                    }
+                }
+
+                if let None = self.consume(
+                    &LogosToken::RightParenthesis,
+                    "Unclosed '(' in method call.",
+                ) {
+                    return Err(());
+                }
+
+                Some(args)
+            } else {
+                None
+            };
+
+            let (line, column, span) = self.location_from(begin);
             return Ok(Expression::MemberAccess(MemberAccess {
                 object: Box::new(primary_expr),
-                member: rhs.to_string(),
+                member,
+                args,
                 ty: None,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
-        if self.match_expr(&[LogosToken::LeftBracket]) {
-            let matched_token = self.previous().unwrap();
+        if self.check(&LogosToken::LeftBracket) {
+            let mut array_expr = primary_expr;
 
-            match matched_token {
-                LogosToken::LeftBracket => {
-                    let index = self.or()?;
+            // Nested arrays are indexed with chained accesses `m[i][j]`
+            while self.match_expr(&[LogosToken::LeftBracket]) {
+                let index = self.expression()?;
 
-                    if let None =
-                        self.consume(&LogosToken::RightBracket, "Unclosed ']' in array access.")
-                    {
-                        return Err(());
-                    }
+                if let None =
+                    self.consume(&LogosToken::RightBracket, "Unclosed ']' in array access.")
+                {
+                    return Err(());
+                }
 
-                    let debug_tk = self.peek_token_with_info_debug();
-                    return Ok(Expression::ArrayAccess(ArrayAccess {
-                        ty: None,
-                        identifier: Box::new(primary_expr),
-                        is_lvalue: false,
-                        index: Box::new(index.clone()),
-                        line: debug_tk.line_number,
-                        column: debug_tk.column_number,
-                        filename: self.file.clone(),
-                    }));
+                let (line, column, span) = self.location_from(begin);
+                array_expr = Expression::ArrayAccess(ArrayAccess {
+                    ty: None,
+                    identifier: Box::new(array_expr),
+                    is_lvalue: false,
+                    index: Box::new(index),
+                    line,
+                    column,
+                    filename: self.file.clone(),
+                    span,
+                });
+            }
+
+            return Ok(array_expr);
+        }
+
+        Ok(primary_expr)
+    }
+
+    /// `{ expr, ... }` or `{ field: expr, ... }` after the struct type name
+    fn parse_struct_literal(
+        &mut self,
+        type_name: String,
+        type_args: Vec<ValueType>,
+        begin: usize,
+    ) -> Result<Expression, ()> {
+        let (line, column, _) = self.location_from(begin);
+
+        if let None = self.consume(
+            &LogosToken::LeftBrace,
+            "Expected '{' in struct initialization",
+        ) {
+            return Err(());
+        }
+
+        let is_named = self.peek_nth(1) == Some(&LogosToken::Colon);
+        let mut field_names: Vec<String> = Vec::new();
+        let mut expressions: Vec<Expression> = Vec::new();
+        loop {
+            // A right brace mark the end of the expression list.
+            if self.check(&LogosToken::RightBrace) {
+                break;
+            }
+
+            if is_named {
+                let field_name = if let Some(LogosToken::Identifier(name)) = self.consume(
+                    &LogosToken::Identifier(String::new()),
+                    "Expected field name in struct initialization",
+                ) {
+                    name.to_string()
+                } else {
+                    return Err(());
+                };
+
+                if let None = self.consume(&LogosToken::Colon, "Expected ':' after field name.") {
+                    return Err(());
                 }
-                _ => unreachable!(),
+
+                field_names.push(field_name);
+            }
+
+            expressions.push(self.expression()?);
+
+            if !self.match_expr(&[LogosToken::Comma]) {
+                break;
             }
         }
 
-        // TODO: Support namespace
-        // if self.match_expr(&[LogosToken::DoubleColon]) {
-        //     let rhs = self.or()?;
+        if let None = self.consume(
+            &LogosToken::RightBrace,
+            "Unclosed '}' in struct initialization",
+        ) {
+            return Err(());
+        }
+        let (_, _, span) = self.location_from(begin);
+
+        Ok(Expression::Literal(Literal::StructLiteral(StructLiteral {
+            literal_type: None,
+            type_name,
+            expressions,
+            field_names: if is_named { Some(field_names) } else { None },
+            type_args,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        })))
+    }
+
+    /// `[a, b, c]` or `[value; count]`, the '[' is already consumed
+    fn parse_array_literal(&mut self, begin: usize) -> Result<Expression, ()> {
+        let (line, column, _) = self.location_from(begin);
 
-        //     return Ok(Expression::ModuleAccess(ModuleAccess {
-        //         left: Box::new(primary_expr),
-        //         right: Box::new(rhs),
-        //     }));
-        // }
+        if self.check(&LogosToken::RightBracket) {
+            self.put_error_at_current_token("Array literals can't be empty.");
+            return Err(());
+        }
 
-        Ok(primary_expr)
+        let first = self.expression()?;
+
+        if self.match_expr(&[LogosToken::Semicolon]) {
+            let count = if let Some(LogosToken::Number(n)) = self.consume(
+                &LogosToken::Number(0),
+                "Expected constant number of elements in array literal.",
+            ) {
+                *n as usize
+            } else {
+                return Err(());
+            };
+
+            if let None = self.consume(&LogosToken::RightBracket, "Unclosed ']' in array literal.")
+            {
+                return Err(());
+            }
+            let (_, _, span) = self.location_from(begin);
+
+            return Ok(Expression::ArrayRepeat(ArrayRepeat {
+                value: Box::new(first),
+                count,
+                ty: None,
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
+            }));
+        }
+
+        let mut elements = vec![first];
+        while self.match_expr(&[LogosToken::Comma]) {
+            // Allow a trailing comma
+            if self.check(&LogosToken::RightBracket) {
+                break;
+            }
+            elements.push(self.expression()?);
+        }
+
+        if let None = self.consume(&LogosToken::RightBracket, "Unclosed ']' in array literal.") {
+            return Err(());
+        }
+        let (_, _, span) = self.location_from(begin);
+
+        Ok(Expression::ArrayLiteral(ArrayLiteral {
+            elements,
+            ty: None,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
     }
 
     fn primary(&mut self) -> Result<Expression, ()> {
-        let (line, column) = self.get_current_token_postion();
+        // Leave statement boundaries to the error recovery
+        match self.peek() {
+            None => {
+                self.put_error_at_current_token("Unexpected EOF, expected expression.");
+                return Err(());
+            }
+            Some(LogosToken::Semicolon) | Some(LogosToken::RightBrace) => {
+                self.put_error_at_current_token("Expected expression.");
+                return Err(());
+            }
+            _ => {}
+        }
+
+        let begin = self.begin_node();
+        let (line, column, span) = self.location_from(begin);
         let tk = self.advance();
 
         if tk.is_some() {
@@ -272,30 +550,35 @@ impl Parser {
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 }))),
                 LogosToken::False => Ok(Expression::Literal(Literal::Bool(Bool {
                     value: false,
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 }))),
                 LogosToken::Number(value) => Ok(Expression::Literal(Literal::Number(Number {
                     value: *value,
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 }))),
                 LogosToken::CharLiteral(value) => Ok(Expression::Literal(Literal::Char(Char {
                     value: *value,
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 }))),
                 LogosToken::Real(value) => Ok(Expression::Literal(Literal::Real(Real {
                     value: *value,
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 }))),
                 LogosToken::Quote(s) => {
                     Ok(Expression::Literal(Literal::StringLiteral(StringLiteral {
@@ -303,26 +586,30 @@ impl Parser {
                         line,
                         column,
                         filename: self.file.clone(),
+                        span,
                     })))
                 }
                 LogosToken::Null => Ok(Expression::Null(Null {
                     line,
                     column,
                     filename: self.file.clone(),
+                    span,
                 })),
                 LogosToken::AddressOf => {
                     let identifier = self.or()?;
-                    let debug_tk = self.peek_token_with_info_debug();
+                    let (line, column, span) = self.location_from(begin);
                     Ok(Expression::AddressOf(AddressOf {
                         identifier: Box::new(identifier),
                         ty: None,
-                        line: debug_tk.line_number,
-                        column: debug_tk.column_number,
+                        line,
+                        column,
                         filename: self.file.clone(),
+                        span,
                     }))
                 }
                 LogosToken::Dereference => {
                     let identifier = self.or()?;
+                    let (_, _, span) = self.location_from(begin);
 
                     Ok(Expression::DeReference(DeReference {
                         identifier: Box::new(identifier),
@@ -331,11 +618,21 @@ impl Parser {
                         line,
                         column,
                         filename: self.file.clone(),
+                        span,
                     }))
                 }
                 LogosToken::Identifier(value) => {
                     let name = value.clone(); // Copy the literal's name to avoid borrow checker errors
 
+                    // `Type { field: ... }`, no statement starts with `identifier:` so
+                    // it can't be mistaken for a block
+                    if self.check(&LogosToken::LeftBrace)
+                        && self.peek_nth(1) == Some(&LogosToken::Identifier(String::new()))
+                        && self.peek_nth(2) == Some(&LogosToken::Colon)
+                    {
+                        return self.parse_struct_literal(name, Vec::new(), begin);
+                    }
+
                     Ok(Expression::Literal(Literal::Identifier(Identifier {
                         name,
                         is_lvalue: false,
@@ -343,24 +640,28 @@ impl Parser {
                         column,
                         line,
                         filename: self.file.clone(),
+                        span,
                     })))
                 }
                 LogosToken::LeftParenthesis => {
-                    let inner_expr = self.or()?;
+                    let inner_expr = self.expression()?;
 
                     if let None =
                         self.consume(&LogosToken::RightParenthesis, "Unclosed parenthesis.")
                     {
                         return Err(());
                     }
+                    let (_, _, span) = self.location_from(begin);
 
                     Ok(Expression::Group(Group {
                         inner_expression: Box::new(inner_expr),
                         line,
                         column,
                         filename: self.file.clone(),
+                        span,
                     }))
                 }
+                LogosToken::LeftBracket => self.parse_array_literal(begin),
                 LogosToken::Struct => {
                     let type_name = if let Some(LogosToken::Identifier(name)) = self.consume(
                         &LogosToken::Identifier(String::new()),
@@ -371,53 +672,38 @@ impl Parser {
                         return Err(());
                     };
 
-                    if let None = self.consume(
-                        &LogosToken::LeftBrace,
-                        "Expected '{' in struct initialization",
-                    ) {
-                        return Err(());
-                    }
-
-                    let mut expressions: Vec<Expression> = Vec::new();
-                    loop {
-                        // A right brace mark the end of the expression list.
-                        if self.check(&LogosToken::RightBrace) {
-                            break;
-                        }
-                        expressions.push(self.or()?);
-
-                        // Expect a comma
-
-                        if !self.match_expr(&[LogosToken::Comma]) {
-                            break;
+                    // `struct module::Type { ... }`
+                    let type_name = if self.match_expr(&[LogosToken::DoubleColon]) {
+                        if let Some(LogosToken::Identifier(name)) = self.consume(
+                            &LogosToken::Identifier(String::new()),
+                            "Expected type name after '::' in struct literal.",
+                        ) {
+                            format!("{}::{}", type_name, name)
+                        } else {
+                            return Err(());
                         }
-                    }
+                    } else {
+                        type_name
+                    };
 
-                    if let None = self.consume(
-                        &&LogosToken::RightBrace,
-                        "Unclosed '}' in struct initialization",
-                    ) {
-                        return Err(());
-                    }
+                    // `struct Type<type, ...> { ... }`
+                    let type_args = if self.match_expr(&[LogosToken::Less]) {
+                        self.parse_type_args()?
+                    } else {
+                        Vec::new()
+                    };
 
-                    Ok(Expression::Literal(Literal::StructLiteral(StructLiteral {
-                        literal_type: None,
-                        type_name,
-                        expressions,
-                        line,
-                        column,
-                        filename: self.file.clone(),
-                    })))
+                    self.parse_struct_literal(type_name, type_args, begin)
                 }
                 _ => {
                     // We can unwrap safely here we are in `is_token` branch
-                    let msg = format!("Error: Unexpected {:?}", tk.unwrap());
-                    self.put_error_at_current_token(&msg);
+                    let msg = format!("Unexpected {:?}", tk.unwrap());
+                    self.put_error_at_previous_token(&msg);
                     Err(())
                 }
             }
         } else {
-            self.put_error_at_current_token("Error: Unexpected EOF");
+            self.put_error_at_current_token("Unexpected EOF");
             Err(())
         }
     }
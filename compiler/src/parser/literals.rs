@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::debug::LineDebugInfo;
+use crate::debug::{LineDebugInfo, Span, SpanDebugInfo};
 use compiler_macro::line_debug_info;
 
 #[line_debug_info]
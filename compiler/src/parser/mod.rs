@@ -4,5 +4,6 @@ mod type_parser;
 
 pub mod ast_printer;
 pub mod literals;
+pub mod parse_error;
 pub mod parser;
 pub mod visitors;
@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use crate::{
+    debug::{LineDebugInfo, Span, SpanDebugInfo},
+    lexer::LogosToken,
+};
+
+/// A syntax error found while lexing or parsing a module.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    pub filename: String,
+    pub line: usize,
+    pub column: usize,
+    pub span: Span,
+    /// Tokens that would have been accepted instead, empty when the parser
+    /// had no precise expectation.
+    pub expected: Vec<LogosToken>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{} Error: {}",
+            self.filename, self.line, self.column, self.message
+        )
+    }
+}
+
+impl LineDebugInfo for ParseError {
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn column(&self) -> usize {
+        self.column
+    }
+
+    fn file_name<'a>(&'a self) -> &'a str {
+        &self.filename
+    }
+}
+
+impl SpanDebugInfo for ParseError {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
@@ -1,12 +1,16 @@
-use crate::lexer::{LogosToken, Token};
+use crate::{
+    debug::Span,
+    lexer::{LogosToken, Token},
+};
 
-use super::visitors::Statement;
+use super::{parse_error::ParseError, visitors::Statement};
 
 pub struct Parser {
     pub lexer: Vec<Token>,
     pub(crate) module_path: String,
     pub(crate) file: String,
     current_token: usize,
+    errors: Vec<ParseError>,
 }
 
 impl Parser {
@@ -17,26 +21,103 @@ impl Parser {
     /// The module path arguments is used by the parser for import statements
     /// nodes of the AST.
     pub fn new(lexer: Vec<Token>, module_path: &str, file: &str) -> Self {
+        // Lexer errors come first, the parser doesn't report anything on the
+        // `Error` tokens holding them.
+        let errors = lexer
+            .iter()
+            .flat_map(|tk| {
+                tk.errors.iter().map(|error| ParseError {
+                    message: error.message.clone(),
+                    filename: file.to_string(),
+                    line: error.line,
+                    column: error.column,
+                    span: tk.span,
+                    expected: Vec::new(),
+                })
+            })
+            .collect();
+
         Self {
             lexer,
             module_path: module_path.to_string(),
             current_token: 0,
             file: file.to_string(),
+            errors,
         }
     }
 
-    pub fn parse(&mut self) -> Option<Vec<Statement>> {
+    /// Parses the whole module. On failure, returns every syntax error found,
+    /// the parser resynchronises after each one.
+    pub fn parse(&mut self) -> Result<Vec<Statement>, Vec<ParseError>> {
         let mut stmts: Vec<Statement> = Vec::new();
 
         while !self.is_at_the_end() {
-            if let Ok(ret) = self.parse_import_statement() {
-                stmts.push(ret);
-            } else {
-                return None;
+            let begin = self.current_token;
+
+            match self.parse_import_statement() {
+                Ok(stmt) => stmts.push(stmt),
+                Err(()) => {
+                    self.synchronize();
+
+                    // There is no enclosing block at the top level, skip the '}'
+                    // the synchronisation stopped on.
+                    if self.current_token == begin || self.check(&LogosToken::RightBrace) {
+                        self.advance();
+                    }
+                }
             }
         }
 
-        Some(stmts)
+        if self.errors.is_empty() {
+            Ok(stmts)
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    /// Skips tokens until a statement boundary: right after a ';' or a
+    /// whole block, or right before a '}' closing the enclosing block or a
+    /// top level declaration.
+    pub fn synchronize(&mut self) {
+        let mut depth = 0;
+
+        while let Some(tk) = self.peek() {
+            match tk {
+                LogosToken::Semicolon if depth == 0 => {
+                    self.advance();
+                    return;
+                }
+                LogosToken::LeftBrace => depth += 1,
+                LogosToken::RightBrace => {
+                    if depth == 0 {
+                        return;
+                    }
+
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        return;
+                    }
+                }
+                LogosToken::Function
+                | LogosToken::Struct
+                | LogosToken::Enum
+                | LogosToken::Union
+                | LogosToken::Impl
+                | LogosToken::Interface
+                | LogosToken::Const
+                | LogosToken::Let
+                | LogosToken::Import
+                | LogosToken::Export
+                    if depth == 0 =>
+                {
+                    return
+                }
+                _ => {}
+            }
+
+            self.advance();
+        }
     }
 
     pub fn peek(&self) -> Option<&LogosToken> {
@@ -47,6 +128,14 @@ impl Parser {
         Some(&self.lexer[self.current_token].logos_tk)
     }
 
+    /// Looks `n` tokens after the next one without consuming them.
+    pub fn peek_nth(&self, n: usize) -> Option<&LogosToken> {
+        match self.lexer.get(self.current_token + n) {
+            Some(tk) if tk.logos_tk != LogosToken::EndOfFile => Some(&tk.logos_tk),
+            _ => None,
+        }
+    }
+
     pub fn peek_token_with_info_debug(&self) -> &Token {
         if self.is_at_the_end() {
             &self.lexer[self.current_token - 1]
@@ -61,6 +150,26 @@ impl Parser {
         (tk.line_number, tk.column_number)
     }
 
+    /// Index of the next token, to give to `location_from` once the node
+    /// starting there has been parsed.
+    pub fn begin_node(&self) -> usize {
+        self.current_token
+    }
+
+    /// Returns the line, column and span of a node going from the `begin`
+    /// token to the last consumed one.
+    pub fn location_from(&self, begin: usize) -> (usize, usize, Span) {
+        let last_index = self.lexer.len() - 1;
+        let first = &self.lexer[begin.min(last_index)];
+        let last = &self.lexer[self.current_token.max(begin + 1).min(last_index + 1) - 1];
+
+        (
+            first.line_number,
+            first.column_number,
+            first.span.to(&last.span),
+        )
+    }
+
     pub fn expect(&mut self, token: &LogosToken) -> Option<&LogosToken> {
         if self.is_at_the_end() {
             return None;
@@ -106,36 +215,41 @@ impl Parser {
 
     pub fn consume(&mut self, token: &LogosToken, error_message: &str) -> Option<&LogosToken> {
         if !self.check(token) {
-            if !self.is_at_the_end() {
-                let current_tk = &self.lexer[self.current_token];
-                println!(
-                    "{}:{}:{} Error: {}",
-                    self.module_path,
-                    current_tk.line_number,
-                    current_tk.column_number,
-                    error_message
-                );
-            } else {
-                println!("{}: Error: {}", self.module_path, error_message);
-            }
-
+            self.error_at(self.current_token, error_message, vec![token.clone()]);
             return None;
         }
 
         self.advance()
     }
 
-    pub fn put_error_at_current_token(&self, error_message: &str) {
-        let current_tk = if !self.is_at_the_end() {
-            &self.lexer[self.current_token]
-        } else {
-            &self.lexer[self.current_token - 1]
-        };
+    pub fn put_error_at_current_token(&mut self, error_message: &str) {
+        self.error_at(self.current_token, error_message, Vec::new());
+    }
+
+    pub fn put_error_at_previous_token(&mut self, error_message: &str) {
+        self.error_at(self.current_token.max(1) - 1, error_message, Vec::new());
+    }
+
+    fn error_at(&mut self, index: usize, error_message: &str, expected: Vec<LogosToken>) {
+        if self.lexer.is_empty() {
+            return;
+        }
+
+        let tk = &self.lexer[index.min(self.lexer.len() - 1)];
 
-        println!(
-            "{}:{}:{} Error: {}",
-            self.file, current_tk.line_number, current_tk.column_number, error_message
-        );
+        // Invalid tokens have already been reported by the lexer
+        if tk.logos_tk == LogosToken::Error {
+            return;
+        }
+
+        self.errors.push(ParseError {
+            message: error_message.to_string(),
+            filename: self.file.clone(),
+            line: tk.line_number,
+            column: tk.column_number,
+            span: tk.span,
+            expected,
+        });
     }
 
     pub fn match_expr(&mut self, token_types: &[LogosToken]) -> bool {
@@ -149,6 +263,22 @@ impl Parser {
         false
     }
 
+    /// Consumes consecutive doc comments and joins them line by line.
+    pub fn take_doc_comments(&mut self) -> Option<String> {
+        let mut lines: Vec<String> = Vec::new();
+
+        while let Some(LogosToken::DocComment(line)) = self.peek() {
+            lines.push(line.clone());
+            self.advance();
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
     pub fn check(&self, token: &LogosToken) -> bool {
         !self.is_at_the_end() && self.lexer[self.current_token].logos_tk == *token
     }
@@ -1,17 +1,21 @@
-use crate::lexer::LogosToken;
+use crate::{lexer::LogosToken, type_system::value_type::ValueType};
 
 use super::{
-    literals::Bool,
+    literals::{Bool, Char, Number},
     parser::Parser,
     visitors::{
-        Argument, BlockStatement, BreakStatement, Expression, ForStatement, FunctionStatement,
-        IfStatement, ImportStatement, Literal, ReturnStatement, Statement, StructField,
-        StructStatement, VariableAssignment, VariableDeclaration, WhileStatement,
+        Argument, BlockStatement, BreakStatement, ContinueStatement, EnumMember, EnumPattern,
+        EnumStatement, Expression, ForStatement, FunctionStatement, IfStatement, ImplStatement,
+        ImportStatement, InterfaceStatement, Literal, MatchArm, MatchStatement, Pattern,
+        RangePattern, ReturnStatement, Statement, StructField, StructStatement, UnionMember,
+        UnionStatement, VariableAssignment, VariableDeclaration, WhileStatement, Wildcard,
     },
 };
 
 impl Parser {
-    fn parse_function(&mut self, exported: bool) -> Result<Statement, ()> {
+    fn parse_function(&mut self, exported: bool, doc: Option<String>) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::Function]) {
             let callee = match self.consume(
                 &LogosToken::Identifier(String::new()),
@@ -23,6 +27,8 @@ impl Parser {
                 }
             };
 
+            let (type_params, type_bounds) = self.parse_type_params(true)?;
+
             if let None = self.consume(
                 &LogosToken::LeftParenthesis,
                 "Expected '(' after function identifier",
@@ -75,39 +81,71 @@ impl Parser {
                     return Err(());
                 }
 
-                let debug_tk = self.peek_token_with_info_debug();
+                let (line, column, span) = self.location_from(begin);
                 return Ok(Statement::Function(FunctionStatement {
                     callee,
                     args: if !args.is_empty() { Some(args) } else { None },
                     block: None,
                     return_type,
                     is_exported: exported,
-                    line: debug_tk.line_number,
-                    column: debug_tk.column_number,
+                    doc,
+                    mangled_name: None,
+                    type_params,
+                    type_bounds,
+                    line,
+                    column,
                     filename: self.file.clone(),
+                    span,
                 }));
             }
 
             let block = self.parse_block()?;
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::Function(FunctionStatement {
                 callee,
                 args: if !args.is_empty() { Some(args) } else { None },
                 block: Some(block),
                 return_type,
                 is_exported: exported,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                doc,
+                mangled_name: None,
+                type_params,
+                type_bounds,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
         if self.match_expr(&[LogosToken::Struct]) {
-            self.parse_struct_statement(exported)
+            self.parse_struct_statement(exported, doc, begin)
+        } else if self.match_expr(&[LogosToken::Enum]) {
+            self.parse_enum_statement(exported, doc, begin)
+        } else if self.match_expr(&[LogosToken::Union]) {
+            self.parse_union_statement(exported, doc, begin)
+        } else if self.match_expr(&[LogosToken::Interface]) {
+            self.parse_interface_statement(exported, doc, begin)
+        } else if self.check(&LogosToken::Impl) {
+            if exported {
+                self.put_error_at_current_token(
+                    "Methods are exported with their struct, 'impl' can't be exported.",
+                );
+                return Err(());
+            }
+
+            self.advance();
+            self.parse_impl_statement(begin)
         } else {
+            if self.check(&LogosToken::Let) || self.check(&LogosToken::Const) {
+                return self.parse_variable_declaration(exported, true, begin);
+            }
+
             if exported {
-                self.put_error_at_current_token("Expected 'fn' keyword after 'export'.");
+                self.put_error_at_current_token(
+                    "Expected 'fn', 'struct', 'enum', 'union', 'interface', 'let', 'const' or 'import' keyword after 'export'.",
+                );
                 return Err(());
             }
 
@@ -116,16 +154,49 @@ impl Parser {
     }
 
     pub fn parse_import_statement(&mut self) -> Result<Statement, ()> {
+        let doc = self.take_doc_comments();
+        let begin = self.begin_node();
+
+        // `export import "module";` re-exports the module
+        let is_exported =
+            self.check(&LogosToken::Export) && self.peek_nth(1) == Some(&LogosToken::Import);
+        if is_exported {
+            self.advance();
+        }
+
         if self.match_expr(&[LogosToken::Import]) {
+            let items = if self.match_expr(&[LogosToken::LeftBrace]) {
+                Some(self.parse_import_items()?)
+            } else {
+                None
+            };
+
             let name = if let Some(LogosToken::Quote(name)) = self.consume(
                 &LogosToken::Quote(String::new()),
-                "Expected a string literal after 'import' keyword.",
+                if items.is_some() {
+                    "Expected a string literal after 'from' keyword."
+                } else {
+                    "Expected a string literal after 'import' keyword."
+                },
             ) {
                 name.clone()
             } else {
                 return Err(());
             };
 
+            let alias = if items.is_none() && self.match_expr(&[LogosToken::As]) {
+                if let Some(LogosToken::Identifier(alias)) = self.consume(
+                    &LogosToken::Identifier(String::new()),
+                    "Expected a namespace identifier after 'as'.",
+                ) {
+                    Some(alias.clone())
+                } else {
+                    return Err(());
+                }
+            } else {
+                None
+            };
+
             if self
                 .consume(
                     &LogosToken::Semicolon,
@@ -133,28 +204,116 @@ impl Parser {
                 )
                 .is_some()
             {
-                let debug_tk = self.peek_token_with_info_debug();
+                let (line, column, span) = self.location_from(begin);
                 Ok(Statement::Import(ImportStatement {
                     file_path: self.module_path.clone(),
                     module_path: name,
-                    line: debug_tk.line_number,
-                    column: debug_tk.column_number,
+                    alias,
+                    items,
+                    is_exported,
+                    line,
+                    column,
                     filename: self.file.clone(),
+                    span,
                 }))
             } else {
                 Err(())
             }
         } else {
-            self.parse_function_statement()
+            self.parse_function_statement(doc)
+        }
+    }
+
+    /// `name, ... } from` after the '{' of a selective import
+    fn parse_import_items(&mut self) -> Result<Vec<String>, ()> {
+        let mut items = Vec::new();
+
+        loop {
+            match self.consume(
+                &LogosToken::Identifier(String::new()),
+                "Expected an identifier in the import list.",
+            ) {
+                Some(LogosToken::Identifier(item)) => items.push(item.clone()),
+                _ => return Err(()),
+            }
+
+            if !self.match_expr(&[LogosToken::Comma]) || self.check(&LogosToken::RightBrace) {
+                break;
+            }
+        }
+
+        if let None = self.consume(&LogosToken::RightBrace, "Unclosed '{' in import list.") {
+            return Err(());
         }
+
+        if let None = self.consume(&LogosToken::From, "Expected 'from' after the import list.") {
+            return Err(());
+        }
+
+        Ok(items)
     }
 
-    fn parse_function_statement(&mut self) -> Result<Statement, ()> {
+    fn parse_function_statement(&mut self, doc: Option<String>) -> Result<Statement, ()> {
         let exported = self.match_expr(&[LogosToken::Export]);
-        self.parse_function(exported)
+        self.parse_function(exported, doc)
+    }
+
+    /// `<T, ...>` after the name of a generic function or struct, empty if
+    /// there's none. The type parameters of a function can be bounded by an
+    /// interface `<T: Interface>`, they're returned with it.
+    fn parse_type_params(
+        &mut self,
+        allow_bounds: bool,
+    ) -> Result<(Vec<String>, Vec<(String, String)>), ()> {
+        let mut type_params = Vec::new();
+        let mut type_bounds = Vec::new();
+
+        if !self.match_expr(&[LogosToken::Less]) {
+            return Ok((type_params, type_bounds));
+        }
+
+        loop {
+            let param = match self.consume(
+                &LogosToken::Identifier(String::new()),
+                "Expected a type parameter identifier.",
+            ) {
+                Some(LogosToken::Identifier(name)) => name.clone(),
+                _ => return Err(()),
+            };
+
+            if self.check(&LogosToken::Colon) {
+                if !allow_bounds {
+                    self.put_error_at_current_token(
+                        "Only the type parameters of a function can be bounded by an interface.",
+                    );
+                    return Err(());
+                }
+
+                self.advance();
+                let interface = self
+                    .parse_path_name("Expected an interface name after ':' in type parameter.")?;
+                type_bounds.push((param.clone(), interface));
+            }
+            type_params.push(param);
+
+            if !self.match_expr(&[LogosToken::Comma]) {
+                break;
+            }
+        }
+
+        if let None = self.consume(&LogosToken::More, "Expected '>' after type parameters.") {
+            return Err(());
+        }
+
+        Ok((type_params, type_bounds))
     }
 
-    fn parse_struct_statement(&mut self, exported: bool) -> Result<Statement, ()> {
+    fn parse_struct_statement(
+        &mut self,
+        exported: bool,
+        doc: Option<String>,
+        begin: usize,
+    ) -> Result<Statement, ()> {
         let type_name = if let Some(LogosToken::Identifier(id)) = self.consume(
             &LogosToken::Identifier(String::new()),
             "Expected <type identifier> after 'struct'.",
@@ -164,6 +323,8 @@ impl Parser {
             return Err(());
         };
 
+        let (type_params, _) = self.parse_type_params(false)?;
+
         if let None = self.consume(
             &LogosToken::LeftBrace,
             "Expected '{' after struct type identifier.",
@@ -172,9 +333,17 @@ impl Parser {
         }
 
         let mut fields: Vec<StructField> = Vec::new();
-        while let Some(LogosToken::Identifier(name)) = self.advance() {
+        loop {
+            let field_doc = self.take_doc_comments();
             // Copy here because of other mutable borrows below ...
-            let field_name = name.clone();
+            let field_name = match self.advance() {
+                Some(LogosToken::Identifier(name)) => name.clone(),
+                Some(LogosToken::RightBrace) => break,
+                _ => {
+                    self.put_error_at_previous_token("Expected field identifier or '}'.");
+                    return Err(());
+                }
+            };
 
             if let None = self.consume(&LogosToken::Colon, "Expected ':' after field identifier.") {
                 return Err(());
@@ -182,46 +351,365 @@ impl Parser {
 
             let field_type = self.parse_type()?;
 
+            let default_value = if self.match_expr(&[LogosToken::Equal]) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+
             if let None = self.consume(
                 &LogosToken::Semicolon,
-                "Expected ';' after field type name.",
+                if default_value.is_some() {
+                    "Expected ';' after field default value."
+                } else {
+                    "Expected ';' after field type name."
+                },
             ) {
                 return Err(());
             }
 
-            fields.push((field_name, field_type));
+            fields.push((field_name, field_type, field_doc, default_value));
         }
 
-        let debug_tk = self.peek_token_with_info_debug();
+        let (line, column, span) = self.location_from(begin);
         Ok(Statement::Struct(StructStatement {
             type_name,
             fields,
             exported,
-            line: debug_tk.line_number,
-            column: debug_tk.column_number,
+            doc,
+            type_params,
+            line,
+            column,
             filename: self.file.clone(),
+            span,
         }))
     }
 
+    fn parse_enum_statement(
+        &mut self,
+        exported: bool,
+        doc: Option<String>,
+        begin: usize,
+    ) -> Result<Statement, ()> {
+        let type_name = if let Some(LogosToken::Identifier(id)) = self.consume(
+            &LogosToken::Identifier(String::new()),
+            "Expected <type identifier> after 'enum'.",
+        ) {
+            id.clone()
+        } else {
+            return Err(());
+        };
+
+        if let None = self.consume(
+            &LogosToken::LeftBrace,
+            "Expected '{' after enum type identifier.",
+        ) {
+            return Err(());
+        }
+
+        let mut variants: Vec<EnumMember> = Vec::new();
+        loop {
+            let variant_doc = self.take_doc_comments();
+            let variant_name = match self.advance() {
+                Some(LogosToken::Identifier(name)) => name.clone(),
+                Some(LogosToken::RightBrace) => break,
+                _ => {
+                    self.put_error_at_previous_token("Expected variant identifier or '}'.");
+                    return Err(());
+                }
+            };
+
+            let value = if self.match_expr(&[LogosToken::Equal]) {
+                let negative = self.match_expr(&[LogosToken::Minus]);
+
+                match self.consume(
+                    &LogosToken::Number(0),
+                    "Expected a number literal after '=' in enum variant.",
+                ) {
+                    Some(LogosToken::Number(n)) if negative => -*n,
+                    Some(LogosToken::Number(n)) => *n,
+                    _ => return Err(()),
+                }
+            } else {
+                match variants.last() {
+                    Some((_, previous, _)) => match previous.checked_add(1) {
+                        Some(value) => value,
+                        None => {
+                            self.put_error_at_previous_token(
+                                format!("Value of variant '{}' overflows.", variant_name).as_str(),
+                            );
+                            return Err(());
+                        }
+                    },
+                    None => 0,
+                }
+            };
+
+            variants.push((variant_name, value, variant_doc));
+
+            // The comma after the last variant is optional
+            if !self.match_expr(&[LogosToken::Comma]) {
+                if let None = self.consume(
+                    &LogosToken::RightBrace,
+                    "Expected ',' or '}' after enum variant.",
+                ) {
+                    return Err(());
+                }
+
+                break;
+            }
+        }
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Statement::Enum(EnumStatement {
+            type_name,
+            variants,
+            exported,
+            doc,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    fn parse_union_statement(
+        &mut self,
+        exported: bool,
+        doc: Option<String>,
+        begin: usize,
+    ) -> Result<Statement, ()> {
+        let type_name = if let Some(LogosToken::Identifier(id)) = self.consume(
+            &LogosToken::Identifier(String::new()),
+            "Expected <type identifier> after 'union'.",
+        ) {
+            id.clone()
+        } else {
+            return Err(());
+        };
+
+        if let None = self.consume(
+            &LogosToken::LeftBrace,
+            "Expected '{' after union type identifier.",
+        ) {
+            return Err(());
+        }
+
+        let mut variants: Vec<UnionMember> = Vec::new();
+        loop {
+            let variant_doc = self.take_doc_comments();
+            let variant_name = match self.advance() {
+                Some(LogosToken::Identifier(name)) => name.clone(),
+                Some(LogosToken::RightBrace) => break,
+                _ => {
+                    self.put_error_at_previous_token("Expected variant identifier or '}'.");
+                    return Err(());
+                }
+            };
+
+            // `Variant(type, ...)`, a variant without parentheses has no payload
+            let mut payload: Vec<ValueType> = Vec::new();
+            if self.match_expr(&[LogosToken::LeftParenthesis]) {
+                loop {
+                    payload.push(self.parse_type()?);
+
+                    if !self.match_expr(&[LogosToken::Comma]) {
+                        break;
+                    }
+                }
+
+                if let None = self.consume(
+                    &LogosToken::RightParenthesis,
+                    "Expected ')' after union variant payload.",
+                ) {
+                    return Err(());
+                }
+            }
+
+            variants.push((variant_name, payload, variant_doc));
+
+            // The comma after the last variant is optional
+            if !self.match_expr(&[LogosToken::Comma]) {
+                if let None = self.consume(
+                    &LogosToken::RightBrace,
+                    "Expected ',' or '}' after union variant.",
+                ) {
+                    return Err(());
+                }
+
+                break;
+            }
+        }
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Statement::Union(UnionStatement {
+            type_name,
+            variants,
+            exported,
+            doc,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    fn parse_impl_statement(&mut self, begin: usize) -> Result<Statement, ()> {
+        let name = self.parse_path_name("Expected <type identifier> after 'impl'.")?;
+
+        // `impl Interface for Type`
+        let (type_name, interface) = if self.match_expr(&[LogosToken::For]) {
+            let type_name = self.parse_path_name("Expected <type identifier> after 'for'.")?;
+            (type_name, Some(name))
+        } else {
+            (name, None)
+        };
+
+        if let None = self.consume(
+            &LogosToken::LeftBrace,
+            "Expected '{' after impl type identifier.",
+        ) {
+            return Err(());
+        }
+
+        let methods = self.parse_methods("impl block")?;
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Statement::Impl(ImplStatement {
+            type_name,
+            methods,
+            interface,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    fn parse_interface_statement(
+        &mut self,
+        exported: bool,
+        doc: Option<String>,
+        begin: usize,
+    ) -> Result<Statement, ()> {
+        let type_name = if let Some(LogosToken::Identifier(id)) = self.consume(
+            &LogosToken::Identifier(String::new()),
+            "Expected <interface identifier> after 'interface'.",
+        ) {
+            id.clone()
+        } else {
+            return Err(());
+        };
+
+        if let None = self.consume(
+            &LogosToken::LeftBrace,
+            "Expected '{' after interface identifier.",
+        ) {
+            return Err(());
+        }
+
+        let methods = self.parse_methods("interface")?;
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Statement::Interface(InterfaceStatement {
+            type_name,
+            methods,
+            exported,
+            doc,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    /// Functions until the '}' closing an impl block or an interface, the
+    /// '{' has already been consumed
+    fn parse_methods(&mut self, item: &str) -> Result<Vec<FunctionStatement>, ()> {
+        let mut methods: Vec<FunctionStatement> = Vec::new();
+        loop {
+            let method_doc = self.take_doc_comments();
+
+            if self.match_expr(&[LogosToken::RightBrace]) {
+                break;
+            }
+
+            if !self.check(&LogosToken::Function) {
+                self.put_error_at_current_token(
+                    format!("Expected 'fn' or '}}' in {}.", item).as_str(),
+                );
+                return Err(());
+            }
+
+            if let Statement::Function(method) = self.parse_function(false, method_doc)? {
+                methods.push(method);
+            }
+        }
+
+        Ok(methods)
+    }
+
+    /// `Name` or `module::Name`, a type or an interface named where only a
+    /// name is expected
+    pub fn parse_path_name(&mut self, msg: &str) -> Result<String, ()> {
+        let mut name = match self.consume(&LogosToken::Identifier(String::new()), msg) {
+            Some(LogosToken::Identifier(id)) => id.clone(),
+            _ => return Err(()),
+        };
+
+        while self.match_expr(&[LogosToken::DoubleColon]) {
+            match self.consume(
+                &LogosToken::Identifier(String::new()),
+                "Expected an identifier after '::'.",
+            ) {
+                Some(LogosToken::Identifier(id)) => name = format!("{}::{}", name, id),
+                _ => return Err(()),
+            }
+        }
+
+        Ok(name)
+    }
+
     fn parse_block(&mut self) -> Result<BlockStatement, ()> {
+        // The '{' has already been consumed by the caller
+        let begin = self.begin_node() - 1;
         let mut statements: Vec<Statement> = Vec::new();
 
-        while !self.match_expr(&[LogosToken::RightBrace]) {
-            statements.push(self.parse_if_statement()?);
+        loop {
+            // Doc comments are only meaningful on declarations
+            self.take_doc_comments();
+
+            if self.match_expr(&[LogosToken::RightBrace]) {
+                break;
+            }
+
+            if self.is_at_the_end() {
+                self.put_error_at_current_token("Unclosed '{', expected '}' at the end of block.");
+                return Err(());
+            }
+
+            // Keep parsing the block after a faulty statement to report all the errors
+            match self.parse_if_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(()) => self.synchronize(),
+            }
         }
 
-        let debug_tk = self.peek_token_with_info_debug();
+        let (line, column, span) = self.location_from(begin);
         Ok(BlockStatement {
             statements,
-            line: debug_tk.line_number,
-            column: debug_tk.column_number,
+            line,
+            column,
             filename: self.file.clone(),
+            span,
         })
     }
 
     fn parse_if_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::If]) {
-            let condition = self.or()?;
+            let condition = self.expression()?;
             let then_branch = if let Statement::Block(b) = self.parse_block_statement()? {
                 b
             } else {
@@ -230,32 +718,48 @@ impl Parser {
             };
 
             if let Some(_) = self.expect(&LogosToken::Else) {
-                let else_branch = if let Statement::Block(b) = self.parse_block_statement()? {
+                let else_begin = self.begin_node();
+
+                let else_branch = if self.check(&LogosToken::If) {
+                    // An 'else if' is an else branch only holding the next if statement
+                    let else_if = self.parse_if_statement()?;
+
+                    let (line, column, span) = self.location_from(else_begin);
+                    BlockStatement {
+                        statements: vec![else_if],
+                        line,
+                        column,
+                        filename: self.file.clone(),
+                        span,
+                    }
+                } else if let Statement::Block(b) = self.parse_block_statement()? {
                     b
                 } else {
-                    self.put_error_at_current_token("Expected block after if condition.");
+                    self.put_error_at_current_token("Expected block or 'if' after 'else'.");
                     return Err(());
                 };
 
-                let debug_tk = self.peek_token_with_info_debug();
+                let (line, column, span) = self.location_from(begin);
                 return Ok(Statement::IfStatement(IfStatement {
                     condition,
                     then_branch,
                     else_branch: Some(else_branch),
-                    line: debug_tk.line_number,
-                    column: debug_tk.column_number,
+                    line,
+                    column,
                     filename: self.file.clone(),
+                    span,
                 }));
             }
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::IfStatement(IfStatement {
                 condition,
                 then_branch,
                 else_branch: None,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
@@ -263,6 +767,8 @@ impl Parser {
     }
 
     fn parse_for_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::For]) {
             let init_expr =
                 if let Statement::VariableDeclaration(dec) = self.parse_declaration_statement()? {
@@ -272,7 +778,7 @@ impl Parser {
                     return Err(());
                 };
 
-            let loop_condition = self.or()?;
+            let loop_condition = self.expression()?;
 
             if let None = self.expect(&LogosToken::Semicolon) {
                 self.put_error_at_current_token("Expected ';' after loop condition.");
@@ -288,15 +794,16 @@ impl Parser {
                 return Err(());
             };
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::ForStatement(ForStatement {
                 init_expr,
                 loop_condition,
                 next_expr: Box::new(next_expr),
                 block_stmt,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
@@ -304,8 +811,10 @@ impl Parser {
     }
 
     fn parse_while_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::While]) {
-            let condition = self.or()?;
+            let condition = self.expression()?;
             let loop_block = if let Statement::Block(b) = self.parse_block_statement()? {
                 b
             } else {
@@ -313,13 +822,15 @@ impl Parser {
                 return Err(());
             };
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::WhileStatement(WhileStatement {
                 condition,
                 loop_block,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                next_expr: None,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
@@ -327,30 +838,253 @@ impl Parser {
     }
 
     fn parse_loop_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::Loop]) {
             self.consume(&LogosToken::LeftBrace, "Expected '{' after 'loop'");
             let loop_block = self.parse_block()?;
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
 
             // A loop is just a while true {}
             return Ok(Statement::WhileStatement(WhileStatement {
                 condition: Expression::Literal(Literal::Bool(Bool {
                     value: true,
-                    line: debug_tk.line_number,
-                    column: debug_tk.column_number,
+                    line,
+                    column,
                     filename: self.file.clone(),
+                    span,
                 })),
                 loop_block,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                next_expr: None,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
+            }));
+        }
+
+        Ok(self.parse_match_statement()?)
+    }
+
+    fn parse_match_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
+        if self.match_expr(&[LogosToken::Match]) {
+            let scrutinee = self.expression()?;
+
+            if let None = self.consume(
+                &LogosToken::LeftBrace,
+                "Expected '{' after match expression.",
+            ) {
+                return Err(());
+            }
+
+            let mut arms: Vec<MatchArm> = Vec::new();
+            while !self.match_expr(&[LogosToken::RightBrace]) {
+                if self.is_at_the_end() {
+                    self.put_error_at_current_token("Unclosed '{', expected '}' after match arms.");
+                    return Err(());
+                }
+
+                arms.push(self.parse_match_arm()?);
+            }
+
+            let (line, column, span) = self.location_from(begin);
+            return Ok(Statement::MatchStatement(MatchStatement {
+                scrutinee,
+                arms,
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
             }));
         }
 
         Ok(self.parse_block_statement()?)
     }
 
+    fn parse_match_arm(&mut self) -> Result<MatchArm, ()> {
+        let begin = self.begin_node();
+        let mut patterns = vec![self.parse_pattern()?];
+
+        while self.match_expr(&[LogosToken::Pipe]) {
+            patterns.push(self.parse_pattern()?);
+        }
+
+        if let None = self.consume(&LogosToken::FatArrow, "Expected '=>' after match pattern.") {
+            return Err(());
+        }
+
+        if let None = self.consume(&LogosToken::LeftBrace, "Expected block after '=>'.") {
+            return Err(());
+        }
+
+        let block = self.parse_block()?;
+
+        // Arms may be separated by a comma
+        self.match_expr(&[LogosToken::Comma]);
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(MatchArm {
+            patterns,
+            block,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        })
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, ()> {
+        let begin = self.begin_node();
+
+        if self.match_expr(&[LogosToken::Underscore]) {
+            let (line, column, span) = self.location_from(begin);
+            return Ok(Pattern::Wildcard(Wildcard {
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
+            }));
+        }
+
+        // `Enum::Variant` or `module::Enum::Variant`
+        if let Some(LogosToken::Identifier(_)) = self.peek() {
+            return self.parse_enum_pattern();
+        }
+
+        let start = self.parse_pattern_literal()?;
+
+        let inclusive = match self.expect_tokens(&[LogosToken::DotDot, LogosToken::DotDotEqual]) {
+            Some(LogosToken::DotDot) => false,
+            Some(_) => true,
+            None => return Ok(Pattern::Literal(start)),
+        };
+
+        let end = self.parse_pattern_literal()?;
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Pattern::Range(RangePattern {
+            start,
+            end,
+            inclusive,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    fn parse_enum_pattern(&mut self) -> Result<Pattern, ()> {
+        let begin = self.begin_node();
+        let mut path: Vec<String> = Vec::new();
+
+        loop {
+            match self.consume(
+                &LogosToken::Identifier(String::new()),
+                "Expected an identifier after '::' in match pattern.",
+            ) {
+                Some(LogosToken::Identifier(name)) => path.push(name.clone()),
+                _ => return Err(()),
+            }
+
+            if !self.match_expr(&[LogosToken::DoubleColon]) {
+                break;
+            }
+        }
+
+        let variant = path.pop().unwrap();
+        if path.is_empty() {
+            self.put_error_at_previous_token(
+                "Expected an enum variant 'Enum::Variant' in match pattern.",
+            );
+            return Err(());
+        }
+
+        // `Union::Variant(name, _)` binds the payload of a union variant
+        let mut bindings: Vec<Option<String>> = Vec::new();
+        if self.match_expr(&[LogosToken::LeftParenthesis]) {
+            loop {
+                match self.advance() {
+                    Some(LogosToken::Identifier(name)) => bindings.push(Some(name.clone())),
+                    Some(LogosToken::Underscore) => bindings.push(None),
+                    _ => {
+                        self.put_error_at_previous_token(
+                            "Expected an identifier or '_' in variant payload pattern.",
+                        );
+                        return Err(());
+                    }
+                }
+
+                if !self.match_expr(&[LogosToken::Comma]) {
+                    break;
+                }
+            }
+
+            if let None = self.consume(
+                &LogosToken::RightParenthesis,
+                "Expected ')' after variant payload pattern.",
+            ) {
+                return Err(());
+            }
+        }
+
+        let (line, column, span) = self.location_from(begin);
+        Ok(Pattern::EnumVariant(EnumPattern {
+            enum_name: path.join("::"),
+            variant,
+            bindings,
+            value: None,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
+    }
+
+    /// Number, char or bool literal, numbers may be negative.
+    fn parse_pattern_literal(&mut self) -> Result<Literal, ()> {
+        let begin = self.begin_node();
+        let negative = self.match_expr(&[LogosToken::Minus]);
+
+        let token = self.advance().cloned();
+        let (line, column, span) = self.location_from(begin);
+        let filename = self.file.clone();
+
+        match token {
+            Some(LogosToken::Number(value)) => Ok(Literal::Number(Number {
+                value: if negative { -value } else { value },
+                line,
+                column,
+                filename,
+                span,
+            })),
+            Some(LogosToken::CharLiteral(value)) if !negative => Ok(Literal::Char(Char {
+                value,
+                line,
+                column,
+                filename,
+                span,
+            })),
+            Some(LogosToken::True) | Some(LogosToken::False) if !negative => {
+                Ok(Literal::Bool(Bool {
+                    value: token == Some(LogosToken::True),
+                    line,
+                    column,
+                    filename,
+                    span,
+                }))
+            }
+            _ => {
+                self.put_error_at_previous_token(
+                    "Expected a number, char or bool literal or an enum variant in match pattern.",
+                );
+                Err(())
+            }
+        }
+    }
+
     fn parse_block_statement(&mut self) -> Result<Statement, ()> {
         if self.match_expr(&[LogosToken::LeftBrace]) {
             return Ok(Statement::Block(self.parse_block()?));
@@ -360,8 +1094,10 @@ impl Parser {
     }
 
     fn parse_return_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::Return]) {
-            let expr = self.or()?;
+            let expr = self.expression()?;
 
             if let None = self.consume(
                 &LogosToken::Semicolon,
@@ -370,12 +1106,13 @@ impl Parser {
                 return Err(());
             }
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::Return(ReturnStatement {
                 expr,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
@@ -383,77 +1120,136 @@ impl Parser {
     }
 
     fn parse_declaration_statement(&mut self) -> Result<Statement, ()> {
-        if self.match_expr(&[LogosToken::Let]) {
-            let identifier = match self.consume(
-                &LogosToken::Identifier(String::new()),
-                "Expected identifier after Let",
-            ) {
-                Some(LogosToken::Identifier(name)) => name.clone(),
-                _ => {
-                    return Err(());
-                }
-            };
+        let begin = self.begin_node();
+
+        if self.check(&LogosToken::Let) || self.check(&LogosToken::Const) {
+            return self.parse_variable_declaration(false, false, begin);
+        }
+
+        self.parse_expression_statement()
+    }
 
-            if let None = self.consume(&LogosToken::Colon, "Expected ':' after identifier.") {
+    /// Parses a `let` or `const` declaration, the parser must be positioned on
+    /// the keyword. Only the locals can be declared without init expression.
+    fn parse_variable_declaration(
+        &mut self,
+        exported: bool,
+        is_global: bool,
+        begin: usize,
+    ) -> Result<Statement, ()> {
+        let is_const = self.advance() == Some(&LogosToken::Const);
+
+        let identifier = match self.consume(
+            &LogosToken::Identifier(String::new()),
+            if is_const {
+                "Expected identifier after 'const'."
+            } else {
+                "Expected identifier after Let"
+            },
+        ) {
+            Some(LogosToken::Identifier(name)) => name.clone(),
+            _ => {
                 return Err(());
             }
+        };
 
-            let variable_type = self.parse_type()?;
+        let variable_type = if self.match_expr(&[LogosToken::Colon]) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
 
-            if let None = self.consume(&LogosToken::Equal, "Expected '=' after typename.") {
+        // `let x: type;` is assigned later
+        let init_expr = if !is_global
+            && !is_const
+            && variable_type.is_some()
+            && self.match_expr(&[LogosToken::Semicolon])
+        {
+            None
+        } else {
+            if let None = self.consume(
+                &LogosToken::Equal,
+                if variable_type.is_some() {
+                    "Expected '=' after typename."
+                } else {
+                    "Expected ':' or '=' after identifier."
+                },
+            ) {
                 return Err(());
             }
 
-            let init_expr = self.or()?;
+            let init_expr = self.expression()?;
 
             if let None = self.consume(&LogosToken::Semicolon, "Expected ';' after <init_expr>.") {
                 return Err(());
             }
 
-            let debug_tk = self.peek_token_with_info_debug();
-            return Ok(Statement::VariableDeclaration(VariableDeclaration {
-                identifier,
-                variable_type,
-                init_expr,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
-                filename: self.file.clone(),
-            }));
-        }
+            Some(init_expr)
+        };
 
-        self.parse_expression_statement()
+        let (line, column, span) = self.location_from(begin);
+        Ok(Statement::VariableDeclaration(VariableDeclaration {
+            identifier,
+            variable_type,
+            init_expr,
+            is_const,
+            is_exported: exported,
+            mangled_name: None,
+            line,
+            column,
+            filename: self.file.clone(),
+            span,
+        }))
     }
 
     fn parse_expression_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.begin_node();
+
         if self.match_expr(&[LogosToken::Break]) {
             if let None = self.consume(&LogosToken::Semicolon, "Expected ';' after assigment.") {
                 return Err(());
             }
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::BreakStatement(BreakStatement {
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
+                filename: self.file.clone(),
+                span,
+            }));
+        }
+
+        if self.match_expr(&[LogosToken::Continue]) {
+            if let None = self.consume(&LogosToken::Semicolon, "Expected ';' after 'continue'.") {
+                return Err(());
+            }
+
+            let (line, column, span) = self.location_from(begin);
+            return Ok(Statement::ContinueStatement(ContinueStatement {
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
-        let expr = self.or()?;
+        let expr = self.expression()?;
 
         if self.match_expr(&[LogosToken::Equal]) {
-            let rhs = self.or()?;
+            let rhs = self.expression()?;
 
             if let None = self.consume(&LogosToken::Semicolon, "Expected ';' after assigment.") {
                 return Err(());
             }
 
-            let debug_tk = self.peek_token_with_info_debug();
+            let (line, column, span) = self.location_from(begin);
             return Ok(Statement::VariableAssignment(VariableAssignment {
                 identifier: expr,
                 new_value: rhs,
-                line: debug_tk.line_number,
-                column: debug_tk.column_number,
+                line,
+                column,
                 filename: self.file.clone(),
+                span,
             }));
         }
 
@@ -37,12 +37,58 @@ impl Parser {
                     let inner_type = self.parse_type()?;
                     Ok(ValueType::Pointer(Box::new(inner_type)))
                 }
-                Some(LogosToken::Identifier(name)) => Ok(ValueType::Struct(name.to_string())),
+                Some(LogosToken::Dyn) => Ok(ValueType::Dyn(
+                    self.parse_path_name("Expected an interface name after 'dyn'.")?,
+                )),
+                Some(LogosToken::Identifier(name)) => {
+                    let name = name.to_string();
+
+                    // Struct exported by an imported module `module::Type`
+                    let name = if self.match_expr(&[LogosToken::DoubleColon]) {
+                        if let Some(LogosToken::Identifier(type_name)) = self.consume(
+                            &LogosToken::Identifier(String::new()),
+                            "Expected a type name after '::'.",
+                        ) {
+                            format!("{}::{}", name, type_name)
+                        } else {
+                            return Err(());
+                        }
+                    } else {
+                        name
+                    };
+
+                    // Generic struct `Type<type, ...>`
+                    if self.match_expr(&[LogosToken::Less]) {
+                        Ok(ValueType::Generic(name, self.parse_type_args()?))
+                    } else {
+                        Ok(ValueType::Struct(name))
+                    }
+                }
                 _ => {
-                    println!("Expected type hints.");
+                    self.put_error_at_previous_token("Expected type hints.");
                     Err(())
                 }
             }
         }
     }
+
+    /// `type, ...>` after the '<' of the type arguments of a generic function
+    /// or struct
+    pub fn parse_type_args(&mut self) -> Result<Vec<ValueType>, ()> {
+        let mut type_args = Vec::new();
+
+        loop {
+            type_args.push(self.parse_type()?);
+
+            if !self.match_expr(&[LogosToken::Comma]) {
+                break;
+            }
+        }
+
+        if let None = self.consume(&LogosToken::More, "Expected '>' after type arguments.") {
+            return Err(());
+        }
+
+        Ok(type_args)
+    }
 }
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-use crate::{debug::LineDebugInfo, type_system::value_type::ValueType};
+use crate::{
+    debug::{LineDebugInfo, Span, SpanDebugInfo},
+    type_system::value_type::ValueType,
+};
 use compiler_macro::line_debug_info;
 
 use super::literals::{Bool, Char, Number, Real, StringLiteral};
@@ -49,6 +52,12 @@ impl Display for Identifier {
 pub struct StructLiteral {
     pub type_name: String,
     pub expressions: Vec<Expression>,
+    /// Set for `Type { field: expr }` literals, the type checker puts the
+    /// expressions back in the declaration order and clears it
+    pub field_names: Option<Vec<String>>,
+    /// `struct Type<type, ...> { ... }`, the type arguments of a generic
+    /// struct are inferred from the fields when they're not given
+    pub type_args: Vec<ValueType>,
     pub literal_type: Option<ValueType>,
 }
 
@@ -78,16 +87,23 @@ pub enum BinaryLogic {
     LessEqual(Box<Expression>, Box<Expression>),
 }
 
+/// `!` or `-` of a unary expression, the expression begins with it
+#[line_debug_info]
+pub struct UnaryOperator;
+
 #[line_debug_info]
 pub enum Unary {
-    Not(Box<Expression>),
-    Negate(Box<Expression>),
+    Not(UnaryOperator, Box<Expression>),
+    Negate(UnaryOperator, Box<Expression>),
 }
 
 #[line_debug_info]
 pub struct Call {
     pub name: String,
     pub args: Option<Vec<Expression>>,
+    /// `name::<type, ...>(args)`, the type arguments of a generic function
+    /// are inferred from the arguments when they're not given
+    pub type_args: Vec<ValueType>,
     pub ty: Option<ValueType>,
 }
 
@@ -116,18 +132,74 @@ pub struct DeReference {
 pub struct MemberAccess {
     pub object: Box<Expression>,
     pub member: String,
+    /// `object.method(args)`, the type checker replaces the method call with
+    /// a call to `Type::method`
+    pub args: Option<Vec<Expression>>,
     pub ty: Option<ValueType>,
 }
 
 #[line_debug_info]
 pub struct Null;
 
-// TODO: Namespace support
-// #[derive(Clone)]
-// pub struct ModuleAccess {
-//     pub left: Box<Expression>,
-//     pub right: Box<Expression>,
-// }
+/// `condition ? then_expr : else_expr`
+#[line_debug_info]
+pub struct Conditional {
+    pub condition: Box<Expression>,
+    pub then_expr: Box<Expression>,
+    pub else_expr: Box<Expression>,
+    pub ty: Option<ValueType>,
+}
+
+/// `[first, second, ...]`
+#[line_debug_info]
+pub struct ArrayLiteral {
+    pub elements: Vec<Expression>,
+    pub ty: Option<ValueType>,
+}
+
+/// `[value; count]`, the value is evaluated once
+#[line_debug_info]
+pub struct ArrayRepeat {
+    pub value: Box<Expression>,
+    pub count: usize,
+    pub ty: Option<ValueType>,
+}
+
+/// `module::item`, the item is a call, a global, a struct literal or an
+/// access on one of them. The type checker qualifies the item name with the
+/// module namespace.
+#[line_debug_info]
+pub struct ModuleAccess {
+    pub module: String,
+    pub item: Box<Expression>,
+}
+
+/// `expr as type`, the type checker only accepts conversions between numeric
+/// types and between pointers
+#[line_debug_info]
+pub struct Cast {
+    pub expr: Box<Expression>,
+    pub target: ValueType,
+}
+
+/// `Enum::Variant`, the type checker replaces the module accesses naming an
+/// enum variant with this expression
+#[line_debug_info]
+pub struct EnumVariant {
+    pub enum_name: String,
+    pub variant: String,
+    pub value: i64,
+}
+
+/// `Union::Variant(payload, ...)` or `Union::Variant`, the type checker
+/// replaces the module accesses naming a union variant with this expression
+#[line_debug_info]
+pub struct UnionVariant {
+    pub union_name: String,
+    pub variant: String,
+    pub tag: i64,
+    pub payload: Vec<Expression>,
+}
 
 #[line_debug_info]
 pub enum Expression {
@@ -141,15 +213,30 @@ pub enum Expression {
     AddressOf(AddressOf),
     DeReference(DeReference),
     MemberAccess(MemberAccess),
-    // ModuleAccess(ModuleAccess), // TODO: Namespace support
+    Conditional(Conditional),
+    ArrayLiteral(ArrayLiteral),
+    ArrayRepeat(ArrayRepeat),
+    ModuleAccess(ModuleAccess),
+    Cast(Cast),
+    EnumVariant(EnumVariant),
+    UnionVariant(UnionVariant),
     Null(Null),
 }
 
 #[line_debug_info]
 pub struct VariableDeclaration {
     pub identifier: String,
-    pub variable_type: ValueType,
-    pub init_expr: Expression,
+    // If no type is declared it's inferred from the init expression by the
+    // type checker
+    pub variable_type: Option<ValueType>,
+    // If the variable has no init expression it's a global declared in
+    // another module or a local assigned later
+    pub init_expr: Option<Expression>,
+    pub is_const: bool,
+    pub is_exported: bool,
+    // Symbol of an exported global in the object files, qualified with the
+    // name of the module that defines it
+    pub mangled_name: Option<String>,
 }
 
 #[line_debug_info]
@@ -171,15 +258,74 @@ pub struct FunctionStatement {
     pub block: Option<BlockStatement>,
     pub return_type: ValueType,
     pub is_exported: bool,
+    pub doc: Option<String>,
+    // Symbol of the function in the object files, functions defined in a
+    // module are qualified with its name. C functions keep their name.
+    pub mangled_name: Option<String>,
+    // `fn name<T, ...>`, the type checker instantiates a generic function
+    // for each combination of type arguments it's called with
+    pub type_params: Vec<String>,
+    // `fn name<T: Interface>`, (type parameter, interface) the type argument
+    // of the parameter must implement
+    pub type_bounds: Vec<(String, String)>,
 }
 
-pub type StructField = (String, ValueType);
+/// (name, type, doc comment, default value)
+pub type StructField = (String, ValueType, Option<String>, Option<Expression>);
 
 #[line_debug_info]
 pub struct StructStatement {
     pub type_name: String,
     pub fields: Vec<StructField>,
     pub exported: bool,
+    pub doc: Option<String>,
+    /// `struct Name<T, ...>`, instantiated like generic functions
+    pub type_params: Vec<String>,
+}
+
+/// `impl Type { fn method(...) ... }`, the methods of a struct. The type
+/// checker names them `Type::method`, the ones with a first parameter `self`
+/// are called on a value `value.method(...)`.
+#[line_debug_info]
+pub struct ImplStatement {
+    pub type_name: String,
+    pub methods: Vec<FunctionStatement>,
+    /// `impl Interface for Type`, the methods are the ones of the interface
+    pub interface: Option<String>,
+}
+
+/// `interface Name { fn method(self: ptr Self, ...): type; }`, the methods a
+/// struct implements with `impl Name for Type`. A `dyn Name` value calls them
+/// through the vtable of the implementation it was created from.
+#[line_debug_info]
+pub struct InterfaceStatement {
+    pub type_name: String,
+    pub methods: Vec<FunctionStatement>,
+    pub exported: bool,
+    pub doc: Option<String>,
+}
+
+/// (name, value, doc comment), the value of a variant without discriminant
+/// is the value of the previous one plus one
+pub type EnumMember = (String, i64, Option<String>);
+
+#[line_debug_info]
+pub struct EnumStatement {
+    pub type_name: String,
+    pub variants: Vec<EnumMember>,
+    pub exported: bool,
+    pub doc: Option<String>,
+}
+
+/// (name, payload types, doc comment), the tag of a variant is its index
+pub type UnionMember = (String, Vec<ValueType>, Option<String>);
+
+#[line_debug_info]
+pub struct UnionStatement {
+    pub type_name: String,
+    pub variants: Vec<UnionMember>,
+    pub exported: bool,
+    pub doc: Option<String>,
 }
 
 #[line_debug_info]
@@ -198,6 +344,9 @@ pub struct IfStatement {
 pub struct WhileStatement {
     pub condition: Expression,
     pub loop_block: BlockStatement,
+    /// Statement ending every iteration, even the ones skipped by `continue`.
+    /// Only set when desugaring a for loop.
+    pub next_expr: Option<Box<Statement>>,
 }
 
 #[line_debug_info]
@@ -212,11 +361,95 @@ pub struct ForStatement {
 pub struct ImportStatement {
     pub file_path: String,
     pub module_path: String,
+    // `import "module" as alias;`, the namespace defaults to the module name
+    pub alias: Option<String>,
+    // `import { item, ... } from "module";` declares only these items, without
+    // namespace
+    pub items: Option<Vec<String>>,
+    // `export import "module";` re-exports the items of the module
+    pub is_exported: bool,
+}
+
+/// `start..end` or `start..=end` pattern
+#[line_debug_info]
+pub struct RangePattern {
+    pub start: Literal,
+    pub end: Literal,
+    pub inclusive: bool,
+}
+
+/// `Enum::Variant` or `Union::Variant(name, _)` pattern, the type checker
+/// sets the value of the variant, the tag for a union
+#[line_debug_info]
+pub struct EnumPattern {
+    pub enum_name: String,
+    pub variant: String,
+    // Variables bound to the payload of a union variant, `None` for `_`
+    pub bindings: Vec<Option<String>>,
+    pub value: Option<i64>,
+}
+
+/// `_` pattern, matches any value
+#[line_debug_info]
+pub struct Wildcard;
+
+#[line_debug_info]
+pub enum Pattern {
+    Literal(Literal),
+    Range(RangePattern),
+    EnumVariant(EnumPattern),
+    Wildcard(Wildcard),
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Literal(Literal::Char(c)) => f.write_fmt(format_args!("'{}'", c)),
+            Pattern::Literal(l) => f.write_fmt(format_args!("{}", l)),
+            Pattern::Range(r) => f.write_fmt(format_args!(
+                "{}{}{}",
+                Pattern::Literal(r.start.clone()),
+                if r.inclusive { "..=" } else { ".." },
+                Pattern::Literal(r.end.clone())
+            )),
+            Pattern::EnumVariant(e) => {
+                f.write_fmt(format_args!("{}::{}", e.enum_name, e.variant))?;
+
+                if !e.bindings.is_empty() {
+                    let bindings: Vec<&str> = e
+                        .bindings
+                        .iter()
+                        .map(|b| b.as_deref().unwrap_or("_"))
+                        .collect();
+                    f.write_fmt(format_args!("({})", bindings.join(", ")))?;
+                }
+
+                Ok(())
+            }
+            Pattern::Wildcard(_) => f.write_str("_"),
+        }
+    }
+}
+
+/// `pattern | pattern => { ... }`
+#[line_debug_info]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub block: BlockStatement,
+}
+
+#[line_debug_info]
+pub struct MatchStatement {
+    pub scrutinee: Expression,
+    pub arms: Vec<MatchArm>,
 }
 
 #[line_debug_info]
 pub struct BreakStatement;
 
+#[line_debug_info]
+pub struct ContinueStatement;
+
 #[line_debug_info]
 pub enum Statement {
     Expression(Expression),
@@ -224,13 +457,19 @@ pub enum Statement {
     VariableAssignment(VariableAssignment),
     Function(FunctionStatement),
     Struct(StructStatement),
+    Enum(EnumStatement),
+    Union(UnionStatement),
+    Impl(ImplStatement),
+    Interface(InterfaceStatement),
     Block(BlockStatement),
     Return(ReturnStatement),
     IfStatement(IfStatement),
     WhileStatement(WhileStatement),
     ForStatement(ForStatement),
+    MatchStatement(MatchStatement),
     Import(ImportStatement),
     BreakStatement(BreakStatement),
+    ContinueStatement(ContinueStatement),
 }
 
 pub trait StatementVisitor<T> {
@@ -239,12 +478,18 @@ pub trait StatementVisitor<T> {
     fn visit_assignment_statement(&mut self, expr: &VariableAssignment) -> T;
     fn visit_function_statement(&mut self, expr: &FunctionStatement) -> T;
     fn visit_struct_statement(&mut self, stct: &StructStatement) -> T;
+    fn visit_enum_statement(&mut self, enm: &EnumStatement) -> T;
+    fn visit_union_statement(&mut self, union_stmt: &UnionStatement) -> T;
+    fn visit_impl_statement(&mut self, impl_stmt: &ImplStatement) -> T;
+    fn visit_interface_statement(&mut self, interface: &InterfaceStatement) -> T;
     fn visit_block_statement(&mut self, expr: &BlockStatement) -> T;
     fn visit_return_statement(&mut self, return_stmt: &ReturnStatement) -> T;
     fn visit_if_statement(&mut self, if_stmt: &IfStatement) -> T;
     fn visit_while_statement(&mut self, while_stmt: &WhileStatement) -> T;
     fn visit_for_statement(&mut self, for_stmt: &ForStatement) -> T;
+    fn visit_match_statement(&mut self, match_stmt: &MatchStatement) -> T;
     fn visit_break_statement(&mut self, break_stmt: &BreakStatement) -> T;
+    fn visit_continue_statement(&mut self, continue_stmt: &ContinueStatement) -> T;
     fn visit_import_statement(&mut self, import_stmt: &ImportStatement) -> T;
 }
 
@@ -253,13 +498,19 @@ pub trait MutableStatementVisitor<T> {
     fn visit_declaration_statement(&mut self, expr: &mut VariableDeclaration) -> T;
     fn visit_assignment_statement(&mut self, expr: &mut VariableAssignment) -> T;
     fn visit_function_statement(&mut self, expr: &mut FunctionStatement) -> T;
-    fn visit_struct_statement(&mut self, stct: &StructStatement) -> T;
+    fn visit_struct_statement(&mut self, stct: &mut StructStatement) -> T;
+    fn visit_enum_statement(&mut self, enm: &mut EnumStatement) -> T;
+    fn visit_union_statement(&mut self, union_stmt: &mut UnionStatement) -> T;
+    fn visit_impl_statement(&mut self, impl_stmt: &mut ImplStatement) -> T;
+    fn visit_interface_statement(&mut self, interface: &mut InterfaceStatement) -> T;
     fn visit_block_statement(&mut self, expr: &mut BlockStatement) -> T;
     fn visit_return_statement(&mut self, return_stmt: &mut ReturnStatement) -> T;
     fn visit_if_statement(&mut self, if_stmt: &mut IfStatement) -> T;
     fn visit_while_statement(&mut self, while_stmt: &mut WhileStatement) -> T;
     fn visit_for_statement(&mut self, for_stmt: &mut ForStatement) -> T;
+    fn visit_match_statement(&mut self, match_stmt: &mut MatchStatement) -> T;
     fn visit_break_statement(&mut self, break_stmt: &mut BreakStatement) -> T;
+    fn visit_continue_statement(&mut self, continue_stmt: &mut ContinueStatement) -> T;
     fn visit_import_statement(&mut self, import_stmt: &mut ImportStatement) -> T;
 }
 
@@ -276,6 +527,13 @@ pub trait ExpressionVisitor<T> {
     fn visit_dereference_expression(&mut self, dereference: &DeReference) -> T;
     fn visit_struct_literal(&mut self, struct_literal: &StructLiteral) -> T;
     fn visit_member_access(&mut self, member_access: &MemberAccess) -> T;
+    fn visit_conditional(&mut self, conditional: &Conditional) -> T;
+    fn visit_array_literal(&mut self, array_literal: &ArrayLiteral) -> T;
+    fn visit_array_repeat(&mut self, array_repeat: &ArrayRepeat) -> T;
+    fn visit_module_access(&mut self, module_access: &ModuleAccess) -> T;
+    fn visit_cast(&mut self, cast: &Cast) -> T;
+    fn visit_enum_variant(&mut self, enum_variant: &EnumVariant) -> T;
+    fn visit_union_variant(&mut self, union_variant: &UnionVariant) -> T;
 }
 
 pub trait MutableExpressionVisitor<T> {
@@ -291,4 +549,11 @@ pub trait MutableExpressionVisitor<T> {
     fn visit_dereference_expression(&mut self, dereference: &mut DeReference) -> T;
     fn visit_struct_literal(&mut self, struct_literal: &mut StructLiteral) -> T;
     fn visit_member_access(&mut self, member_access: &mut MemberAccess) -> T;
+    fn visit_conditional(&mut self, conditional: &mut Conditional) -> T;
+    fn visit_array_literal(&mut self, array_literal: &mut ArrayLiteral) -> T;
+    fn visit_array_repeat(&mut self, array_repeat: &mut ArrayRepeat) -> T;
+    fn visit_module_access(&mut self, module_access: &mut ModuleAccess) -> T;
+    fn visit_cast(&mut self, cast: &mut Cast) -> T;
+    fn visit_enum_variant(&mut self, enum_variant: &mut EnumVariant) -> T;
+    fn visit_union_variant(&mut self, union_variant: &mut UnionVariant) -> T;
 }
@@ -0,0 +1,618 @@
+use std::collections::VecDeque;
+
+use crate::{
+    lint::Lint,
+    parser::visitors::{
+        Binary, BinaryLogic, BlockStatement, Expression, FunctionStatement, Identifier, Literal,
+        Pattern, Statement, Unary, VariableDeclaration,
+    },
+};
+
+use super::{type_check::TypeChecker, value_type::ValueType};
+
+type NodeId = usize;
+
+/// Node reached by the return statements
+const RETURN: NodeId = 0;
+/// First node of the body
+const ENTRY: NodeId = 1;
+
+#[derive(Default)]
+struct Node<'a> {
+    successors: Vec<NodeId>,
+    /// First statement run in the node
+    first: Option<&'a Statement>,
+    /// Last statement run in the node, where the control leaves it
+    last: Option<&'a Statement>,
+}
+
+/// Control flow graph of a function body. A node is a sequence of statements
+/// run one after the other, the edges are the jumps of the if, while and
+/// match statements and of return, break and continue.
+struct ControlFlowGraph<'a> {
+    nodes: Vec<Node<'a>>,
+    /// Node running after the last statement of the body
+    end: NodeId,
+    /// (continue target, break target) of the enclosing loops
+    loops: Vec<(NodeId, NodeId)>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    fn new(body: &'a BlockStatement) -> Self {
+        let mut cfg = ControlFlowGraph {
+            nodes: vec![Node::default(), Node::default()],
+            end: ENTRY,
+            loops: Vec::new(),
+        };
+
+        cfg.end = cfg.add_statements(&body.statements, ENTRY);
+        cfg
+    }
+
+    /// A path of the body reaches its end without returning
+    fn falls_through(&self) -> bool {
+        self.reachable()[self.end]
+    }
+
+    /// Last statement run by a path falling through the end of the body,
+    /// `None` when the path runs no statement.
+    fn fall_through_statement(&self) -> Option<&'a Statement> {
+        let reachable = self.reachable();
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([self.end]);
+
+        // Walk the path backward up to its last statement
+        while let Some(node) = queue.pop_front() {
+            if visited[node] || !reachable[node] {
+                continue;
+            }
+            visited[node] = true;
+
+            if let Some(stmt) = self.nodes[node].last {
+                return Some(stmt);
+            }
+
+            queue.extend(
+                (0..self.nodes.len()).filter(|&pred| self.nodes[pred].successors.contains(&node)),
+            );
+        }
+
+        None
+    }
+
+    /// First statement of each unreachable part of the body. The statements
+    /// only reached from a reported one aren't reported.
+    fn unreachable_statements(&self) -> Vec<&'a Statement> {
+        let reachable = self.reachable();
+        let mut covered = vec![false; self.nodes.len()];
+        let mut statements = Vec::new();
+
+        // The nodes are created in the order of their statements
+        for node in 0..self.nodes.len() {
+            if reachable[node] {
+                continue;
+            }
+
+            covered[node] = (0..self.nodes.len())
+                .any(|pred| covered[pred] && self.nodes[pred].successors.contains(&node));
+
+            if !covered[node] {
+                if let Some(stmt) = self.nodes[node].first {
+                    statements.push(stmt);
+                    covered[node] = true;
+                }
+            }
+        }
+
+        statements
+    }
+
+    fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut stack = vec![ENTRY];
+
+        while let Some(node) = stack.pop() {
+            if !reachable[node] {
+                reachable[node] = true;
+                stack.extend(&self.nodes[node].successors);
+            }
+        }
+
+        reachable
+    }
+
+    fn add_node(&mut self) -> NodeId {
+        self.nodes.push(Node::default());
+        self.nodes.len() - 1
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.nodes[from].successors.push(to);
+    }
+
+    /// Appends `stmt` to the statements run in `node`
+    fn add_to_node(&mut self, node: NodeId, stmt: &'a Statement) {
+        let node = &mut self.nodes[node];
+        node.first.get_or_insert(stmt);
+        node.last = Some(stmt);
+    }
+
+    /// Adds the statements run from `current`, returns the node running
+    /// after them
+    fn add_statements(&mut self, statements: &'a [Statement], mut current: NodeId) -> NodeId {
+        for stmt in statements {
+            current = self.add_statement(stmt, current);
+        }
+
+        current
+    }
+
+    fn add_statement(&mut self, stmt: &'a Statement, current: NodeId) -> NodeId {
+        match stmt {
+            Statement::Block(block) => self.add_statements(&block.statements, current),
+            Statement::Return(_) => self.add_jump(stmt, current, RETURN),
+            // The type checker rejects break and continue outside of loops
+            Statement::BreakStatement(_) => {
+                let target = self.loops.last().unwrap().1;
+                self.add_jump(stmt, current, target)
+            }
+            Statement::ContinueStatement(_) => {
+                let target = self.loops.last().unwrap().0;
+                self.add_jump(stmt, current, target)
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.add_to_node(current, stmt);
+
+                let then_end = self.add_branch(&if_stmt.then_branch, current);
+                let else_end = match &if_stmt.else_branch {
+                    Some(else_branch) => self.add_branch(else_branch, current),
+                    None => current,
+                };
+
+                let merge = self.add_node();
+                self.add_edge(then_end, merge);
+                self.add_edge(else_end, merge);
+                merge
+            }
+            // The arms are exhaustive, one of them runs
+            Statement::MatchStatement(match_stmt) => {
+                self.add_to_node(current, stmt);
+
+                let merge = self.add_node();
+                for arm in &match_stmt.arms {
+                    let arm_end = self.add_branch(&arm.block, current);
+                    self.add_edge(arm_end, merge);
+                }
+                merge
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.add_loop(stmt, &while_stmt.condition, &while_stmt.loop_block, current)
+            }
+            Statement::ForStatement(for_stmt) => self.add_loop(
+                stmt,
+                &for_stmt.loop_condition,
+                &for_stmt.block_stmt,
+                current,
+            ),
+            _ => {
+                self.add_to_node(current, stmt);
+                current
+            }
+        }
+    }
+
+    fn add_branch(&mut self, block: &'a BlockStatement, from: NodeId) -> NodeId {
+        let start = self.add_node();
+        self.add_edge(from, start);
+        self.add_statements(&block.statements, start)
+    }
+
+    fn add_loop(
+        &mut self,
+        stmt: &'a Statement,
+        condition: &Expression,
+        body: &'a BlockStatement,
+        current: NodeId,
+    ) -> NodeId {
+        let test = self.add_node();
+        self.add_edge(current, test);
+        self.add_to_node(test, stmt);
+
+        // `loop` is a `while true`, only a break exits it
+        let exit = self.add_node();
+        if !Self::is_always_true(condition) {
+            self.add_edge(test, exit);
+        }
+
+        self.loops.push((test, exit));
+        let body_end = self.add_branch(body, test);
+        self.add_edge(body_end, test);
+        self.loops.pop();
+
+        exit
+    }
+
+    /// The statements following a return, break or continue are in a new
+    /// node without predecessor, they're unreachable
+    fn add_jump(&mut self, stmt: &'a Statement, current: NodeId, target: NodeId) -> NodeId {
+        self.add_to_node(current, stmt);
+        self.add_edge(current, target);
+        self.add_node()
+    }
+
+    fn is_always_true(condition: &Expression) -> bool {
+        match condition {
+            Expression::Literal(Literal::Bool(b)) => b.value,
+            Expression::Group(group) => Self::is_always_true(&group.inner_expression),
+            _ => false,
+        }
+    }
+}
+
+/// Initialisation of the local variables of a function body, a variable is
+/// initialised when every path reaching the current statement assigns it.
+/// The globals aren't tracked, they're always initialised.
+#[derive(Default)]
+struct Initialisation<'a> {
+    /// Variables in scope in their declaration order, an inner one hides the
+    /// outer ones with the same name
+    variables: Vec<(&'a str, bool)>,
+    /// Number of variables declared out of each enclosing loop, with their
+    /// state merged from the breaks of the loop
+    loops: Vec<(usize, Option<Vec<bool>>)>,
+    /// Identifiers reading a variable before its initialisation
+    uninitialised_reads: Vec<&'a Identifier>,
+}
+
+impl<'a> Initialisation<'a> {
+    /// Visits the statements of a block, returns false when none of its paths
+    /// reaches its end. The statements following a jump are unreachable.
+    fn visit_block(&mut self, statements: &'a [Statement]) -> bool {
+        let scope = self.variables.len();
+        let falls_through = statements.iter().all(|stmt| self.visit_statement(stmt));
+        self.variables.truncate(scope);
+
+        falls_through
+    }
+
+    fn visit_statement(&mut self, stmt: &'a Statement) -> bool {
+        match stmt {
+            Statement::Expression(expr) => self.visit_expr(expr),
+            Statement::VariableDeclaration(v) => self.visit_declaration(v),
+            Statement::VariableAssignment(a) => {
+                self.visit_expr(&a.new_value);
+
+                // An element or a member is assigned in a variable already set
+                match &a.identifier {
+                    Expression::Literal(Literal::Identifier(identifier)) => {
+                        self.initialise(&identifier.name)
+                    }
+                    target => self.visit_expr(target),
+                }
+            }
+            Statement::Block(block) => return self.visit_block(&block.statements),
+            Statement::Return(r) => {
+                self.visit_expr(&r.expr);
+                return false;
+            }
+            // The type checker rejects break and continue outside of loops
+            Statement::BreakStatement(_) => {
+                let state = self.state();
+                let (count, exit) = self.loops.last_mut().unwrap();
+                Self::merge(exit, &state[..*count]);
+                return false;
+            }
+            Statement::ContinueStatement(_) => return false,
+            Statement::IfStatement(if_stmt) => {
+                self.visit_expr(&if_stmt.condition);
+
+                let mut branches = vec![(Vec::new(), &if_stmt.then_branch)];
+                branches.extend(if_stmt.else_branch.iter().map(|b| (Vec::new(), b)));
+                return self.visit_branches(branches, if_stmt.else_branch.is_none());
+            }
+            // The arms are exhaustive, one of them runs
+            Statement::MatchStatement(match_stmt) => {
+                self.visit_expr(&match_stmt.scrutinee);
+
+                let branches = match_stmt
+                    .arms
+                    .iter()
+                    .map(|arm| {
+                        let bindings = match arm.patterns.as_slice() {
+                            [Pattern::EnumVariant(variant)] => variant
+                                .bindings
+                                .iter()
+                                .flatten()
+                                .map(|b| b.as_str())
+                                .collect(),
+                            _ => Vec::new(),
+                        };
+                        (bindings, &arm.block)
+                    })
+                    .collect();
+                return self.visit_branches(branches, false);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                return self.visit_loop(
+                    &while_stmt.condition,
+                    &while_stmt.loop_block,
+                    while_stmt.next_expr.as_deref(),
+                )
+            }
+            Statement::ForStatement(for_stmt) => {
+                let scope = self.variables.len();
+                self.visit_declaration(&for_stmt.init_expr);
+                let falls_through = self.visit_loop(
+                    &for_stmt.loop_condition,
+                    &for_stmt.block_stmt,
+                    Some(&for_stmt.next_expr),
+                );
+                self.variables.truncate(scope);
+                return falls_through;
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    fn visit_declaration(&mut self, declaration: &'a VariableDeclaration) {
+        declaration
+            .init_expr
+            .iter()
+            .for_each(|expr| self.visit_expr(expr));
+        self.variables.push((
+            declaration.identifier.as_str(),
+            declaration.init_expr.is_some(),
+        ));
+    }
+
+    /// Visits the `branches` starting from the current state, a variable is
+    /// then initialised when every branch reaching its end initialises it.
+    /// Returns false when none of them reaches its end.
+    fn visit_branches(
+        &mut self,
+        branches: Vec<(Vec<&'a str>, &'a BlockStatement)>,
+        may_skip: bool,
+    ) -> bool {
+        let before = self.state();
+        let mut after = may_skip.then(|| before.clone());
+
+        for (bindings, block) in branches {
+            self.restore(&before);
+
+            // The match bindings are set by the pattern
+            let scope = self.variables.len();
+            self.variables
+                .extend(bindings.into_iter().map(|name| (name, true)));
+            let falls_through = self.visit_block(&block.statements);
+            self.variables.truncate(scope);
+
+            if falls_through {
+                Self::merge(&mut after, &self.state());
+            }
+        }
+
+        match after {
+            Some(state) => {
+                self.restore(&state);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The body may run no time, the variables it initialises are only
+    /// initialised after a `loop`, when every break follows their assignment
+    fn visit_loop(
+        &mut self,
+        condition: &'a Expression,
+        body: &'a BlockStatement,
+        next: Option<&'a Statement>,
+    ) -> bool {
+        self.visit_expr(condition);
+        let before = self.state();
+
+        self.loops.push((before.len(), None));
+        self.visit_block(&body.statements);
+        next.iter().for_each(|stmt| {
+            self.visit_statement(stmt);
+        });
+        let (_, exit) = self.loops.pop().unwrap();
+
+        if !ControlFlowGraph::is_always_true(condition) {
+            self.restore(&before);
+            return true;
+        }
+
+        match exit {
+            Some(state) => {
+                self.restore(&state);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expression) {
+        match expr {
+            Expression::Literal(Literal::Identifier(identifier)) => self.read(identifier),
+            Expression::Literal(Literal::StructLiteral(literal)) => literal
+                .expressions
+                .iter()
+                .for_each(|expr| self.visit_expr(expr)),
+            Expression::Literal(_) | Expression::Null(_) | Expression::EnumVariant(_) => {}
+            Expression::Binary(
+                Binary::Plus(l, r)
+                | Binary::Minus(l, r)
+                | Binary::Multiply(l, r)
+                | Binary::Divide(l, r)
+                | Binary::Modulo(l, r),
+            )
+            | Expression::BinaryLogic(
+                BinaryLogic::And(l, r)
+                | BinaryLogic::Or(l, r)
+                | BinaryLogic::Equal(l, r)
+                | BinaryLogic::NotEqual(l, r)
+                | BinaryLogic::More(l, r)
+                | BinaryLogic::Less(l, r)
+                | BinaryLogic::MoreEqual(l, r)
+                | BinaryLogic::LessEqual(l, r),
+            ) => {
+                self.visit_expr(l);
+                self.visit_expr(r);
+            }
+            Expression::Unary(Unary::Not(_, e) | Unary::Negate(_, e)) => self.visit_expr(e),
+            Expression::Group(g) => self.visit_expr(&g.inner_expression),
+            Expression::Call(call) => call
+                .args
+                .iter()
+                .flatten()
+                .for_each(|expr| self.visit_expr(expr)),
+            Expression::ArrayAccess(a) => {
+                self.visit_expr(&a.identifier);
+                self.visit_expr(&a.index);
+            }
+            // The pointed variable can be initialised through the pointer
+            Expression::AddressOf(a) => match a.identifier.as_ref() {
+                Expression::Literal(Literal::Identifier(identifier)) => {
+                    self.initialise(&identifier.name)
+                }
+                expr => self.visit_expr(expr),
+            },
+            Expression::DeReference(d) => self.visit_expr(&d.identifier),
+            Expression::MemberAccess(m) => {
+                self.visit_expr(&m.object);
+                m.args
+                    .iter()
+                    .flatten()
+                    .for_each(|expr| self.visit_expr(expr));
+            }
+            Expression::Conditional(c) => {
+                self.visit_expr(&c.condition);
+                self.visit_expr(&c.then_expr);
+                self.visit_expr(&c.else_expr);
+            }
+            Expression::ArrayLiteral(a) => a.elements.iter().for_each(|expr| self.visit_expr(expr)),
+            Expression::ArrayRepeat(a) => self.visit_expr(&a.value),
+            // The item is named by the module, only its arguments are locals
+            Expression::ModuleAccess(access) => match access.item.as_ref() {
+                Expression::Literal(Literal::Identifier(_)) => {}
+                Expression::Call(call) => call
+                    .args
+                    .iter()
+                    .flatten()
+                    .for_each(|expr| self.visit_expr(expr)),
+                item => self.visit_expr(item),
+            },
+            Expression::Cast(c) => self.visit_expr(&c.expr),
+            Expression::UnionVariant(u) => u.payload.iter().for_each(|expr| self.visit_expr(expr)),
+        }
+    }
+
+    /// A variable is reported at its first read, it's then considered
+    /// initialised
+    fn read(&mut self, identifier: &'a Identifier) {
+        if let Some((_, initialised)) = self
+            .variables
+            .iter_mut()
+            .rev()
+            .find(|(name, _)| *name == identifier.name)
+        {
+            if !*initialised {
+                *initialised = true;
+                self.uninitialised_reads.push(identifier);
+            }
+        }
+    }
+
+    fn initialise(&mut self, name: &str) {
+        if let Some((_, initialised)) = self.variables.iter_mut().rev().find(|(n, _)| *n == name) {
+            *initialised = true;
+        }
+    }
+
+    fn state(&self) -> Vec<bool> {
+        self.variables
+            .iter()
+            .map(|(_, initialised)| *initialised)
+            .collect()
+    }
+
+    /// Sets the state of the variables declared before `state`
+    fn restore(&mut self, state: &[bool]) {
+        for ((_, initialised), state) in self.variables.iter_mut().zip(state) {
+            *initialised = *state;
+        }
+    }
+
+    /// Merges the state of a path reaching the same statement as the paths
+    /// of `merged`
+    fn merge(merged: &mut Option<Vec<bool>>, state: &[bool]) {
+        *merged = Some(match merged.take() {
+            Some(merged) => merged.iter().zip(state).map(|(a, b)| *a && *b).collect(),
+            None => state.to_vec(),
+        });
+    }
+}
+
+impl TypeChecker {
+    /// Reports the unreachable statements of the body of a function. Every
+    /// path of the body of a function returning a value must end with a
+    /// return statement or loop forever.
+    pub(super) fn check_control_flow(
+        &mut self,
+        function: &FunctionStatement,
+    ) -> Result<(), String> {
+        let body = match &function.block {
+            Some(body) => body,
+            None => return Ok(()),
+        };
+
+        let cfg = ControlFlowGraph::new(body);
+        for stmt in cfg.unreachable_statements() {
+            self.add_warning(Lint::UnreachableCode, "Unreachable statement.", stmt);
+        }
+
+        if function.return_type == ValueType::Void || !cfg.falls_through() {
+            return Ok(());
+        }
+
+        let msg = format!("missing return in function '{}'.", function.callee);
+        Err(match cfg.fall_through_statement() {
+            Some(stmt) => Self::build_error_message(msg.as_str(), stmt),
+            None => Self::build_error_message(msg.as_str(), function),
+        })
+    }
+
+    /// Reports the local variables read before being initialised, the
+    /// parameters are initialised by the callers
+    pub(super) fn check_initialisation(&mut self, function: &FunctionStatement) {
+        let body = match &function.block {
+            Some(body) => body,
+            None => return,
+        };
+
+        let mut initialisation = Initialisation::default();
+        initialisation.variables.extend(
+            function
+                .args
+                .iter()
+                .flatten()
+                .map(|(name, _)| (name.as_str(), true)),
+        );
+        initialisation.visit_block(&body.statements);
+
+        for identifier in initialisation.uninitialised_reads {
+            self.add_warning(
+                Lint::UninitialisedUse,
+                format!(
+                    "Variable '{}' is used before being initialised.",
+                    identifier.name
+                )
+                .as_str(),
+                identifier,
+            );
+        }
+    }
+}
@@ -3,5 +3,8 @@ pub mod typed;
 pub mod types_table;
 pub mod value_type;
 
+mod control_flow;
 mod type_check_expressions;
+mod type_check_generics;
+mod type_check_interfaces;
 mod type_check_statement;
@@ -1,12 +1,21 @@
 use crate::{
-    debug::LineDebugInfo,
+    debug::{LineDebugInfo, SpanDebugInfo},
+    lint::{Lint, Warning},
     parser::visitors::{
-        ArrayAccess, Expression, MutableExpressionVisitor, MutableStatementVisitor, Statement,
-        StructLiteral, StructStatement,
+        AddressOf, ArrayAccess, Binary, BinaryLogic, Call, Cast, DeReference, EnumPattern,
+        EnumStatement, EnumVariant, Expression, FunctionStatement, Identifier, InterfaceStatement,
+        Literal, ModuleAccess, MutableExpressionVisitor, MutableStatementVisitor, Pattern,
+        Statement, StructLiteral, StructStatement, Unary, UnionStatement, UnionVariant,
     },
-    type_system::value_type::ValueType,
+    type_system::{
+        typed::Typed,
+        value_type::{StaticArray, ValueType},
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
 };
-use std::{collections::HashMap, ops::Deref};
 
 use super::types_table::TypeTable;
 
@@ -16,14 +25,49 @@ pub struct FunctionSignature {
     pub args_type: Vec<ValueType>,
 }
 
+/// Variable of a scope of `variables_table`
+pub struct Variable {
+    pub value_type: ValueType,
+    /// Read at least once since its declaration
+    pub is_used: bool,
+    /// Reported when the scope is closed if the variable was never read,
+    /// `None` for the variables that can be left unused
+    pub unused_warning: Option<Warning>,
+    /// Declared with `const`, it can't be assigned
+    pub is_const: bool,
+}
+
 pub struct TypeChecker {
     pub(super) structs_table: HashMap<String, StructStatement>,
-    pub(super) variables_table: Vec<HashMap<String, ValueType>>,
+    pub(super) enums_table: HashMap<String, EnumStatement>,
+    pub(super) unions_table: HashMap<String, UnionStatement>,
+    pub(super) variables_table: Vec<HashMap<String, Variable>>,
     pub(super) function_table: HashMap<String, FunctionSignature>,
     pub(super) in_function: Option<ValueType>,
     pub(super) loop_count: u32,
     pub(super) type_table: TypeTable,
     pub(super) is_lvalue: bool,
+    /// The identifier being checked is the target of an assignment, it isn't
+    /// a read of the variable
+    pub(super) is_assigned: bool,
+    pub(super) warnings: Vec<Warning>,
+    /// Generic functions and structs by name, their body and fields are
+    /// checked for each combination of type arguments they're instantiated with
+    pub(super) generic_functions: HashMap<String, FunctionStatement>,
+    pub(super) generic_structs: HashMap<String, StructStatement>,
+    /// Generic struct and type arguments of the struct instances
+    pub(super) struct_instances: HashMap<String, (String, Vec<ValueType>)>,
+    /// Types given to the type parameters of the instance being checked
+    pub(super) type_arguments: HashMap<String, ValueType>,
+    pub(super) instantiation_depth: u32,
+    /// Instances required by the module level statement being checked, they're
+    /// inserted before it
+    pub(super) instances: Vec<Statement>,
+    /// Type of the `self` receiver of the methods by their name `Type::method`
+    pub(super) methods: HashMap<String, ValueType>,
+    pub(super) interfaces_table: HashMap<String, InterfaceStatement>,
+    /// (interface, struct) of the `impl Interface for Struct` blocks
+    pub(super) implementations: HashSet<(String, String)>,
 }
 
 pub type TypeCheckerReturn = Result<ValueType, String>;
@@ -32,12 +76,25 @@ impl TypeChecker {
     pub fn new() -> Self {
         let mut s = Self {
             structs_table: HashMap::new(),
+            enums_table: HashMap::new(),
+            unions_table: HashMap::new(),
             variables_table: Vec::new(),
             function_table: HashMap::new(),
             in_function: None,
             loop_count: 0,
             type_table: TypeTable::new(),
             is_lvalue: false,
+            is_assigned: false,
+            warnings: Vec::new(),
+            generic_functions: HashMap::new(),
+            generic_structs: HashMap::new(),
+            struct_instances: HashMap::new(),
+            type_arguments: HashMap::new(),
+            instantiation_depth: 0,
+            instances: Vec::new(),
+            methods: HashMap::new(),
+            interfaces_table: HashMap::new(),
+            implementations: HashSet::new(),
         };
 
         s.variables_table.push(HashMap::new()); // default global scope
@@ -48,9 +105,49 @@ impl TypeChecker {
         self.type_table.clone()
     }
 
+    /// Non fatal diagnostics found while checking
+    pub fn get_warnings(&self) -> &Vec<Warning> {
+        &self.warnings
+    }
+
     pub fn check_ast_type(&mut self, stmts: &mut Vec<Statement>) -> TypeCheckerReturn {
-        for mut stmt in stmts {
-            self.visit_statement(&mut stmt)?;
+        // Enums and unions can be used before their declaration, their types
+        // must be known to resolve the type annotations
+        for stmt in stmts.iter_mut() {
+            match stmt {
+                Statement::Enum(enm) => {
+                    self.visit_enum_statement(enm)?;
+                }
+                Statement::Union(union_stmt) => {
+                    self.visit_union_statement(union_stmt)?;
+                }
+                _ => {}
+            }
+        }
+
+        // A payload can hold a union declared after it
+        for stmt in stmts.iter_mut() {
+            if let Statement::Union(union_stmt) = stmt {
+                self.resolve_union_payloads(union_stmt)?;
+            }
+        }
+
+        let is_module = self.variables_table.len() == 1;
+        let mut i = 0;
+        while i < stmts.len() {
+            if !matches!(stmts[i], Statement::Enum(_) | Statement::Union(_)) {
+                self.visit_statement(&mut stmts[i])?;
+            }
+
+            // The instances of the generic functions and structs are emitted
+            // before the first statement using them
+            if is_module {
+                let count = self.instances.len();
+                stmts.splice(i..i, self.instances.drain(..));
+                i += count;
+            }
+
+            i += 1;
         }
 
         Ok(ValueType::Number)
@@ -58,8 +155,8 @@ impl TypeChecker {
 
     pub fn find_variable(&self, identifier: &String) -> Option<ValueType> {
         for frame in self.variables_table.iter().rev() {
-            if let Some(ty) = frame.get(identifier) {
-                return Some(ty.clone());
+            if let Some(variable) = frame.get(identifier) {
+                return Some(variable.value_type.clone());
             }
         }
 
@@ -77,13 +174,22 @@ impl TypeChecker {
             Statement::IfStatement(if_stmt) => self.visit_if_statement(if_stmt),
             Statement::WhileStatement(while_stmt) => self.visit_while_statement(while_stmt),
             Statement::ForStatement(for_stmt) => self.visit_for_statement(for_stmt),
+            Statement::MatchStatement(match_stmt) => self.visit_match_statement(match_stmt),
             Statement::BreakStatement(b) => self.visit_break_statement(b),
+            Statement::ContinueStatement(c) => self.visit_continue_statement(c),
             Statement::Struct(struct_stmt) => self.visit_struct_statement(struct_stmt),
+            Statement::Enum(enum_stmt) => self.visit_enum_statement(enum_stmt),
+            Statement::Union(union_stmt) => self.visit_union_statement(union_stmt),
+            Statement::Impl(impl_stmt) => self.visit_impl_statement(impl_stmt),
+            Statement::Interface(interface) => self.visit_interface_statement(interface),
             Statement::Import(_) => todo!(),
         }
     }
 
     pub fn check_expr(&mut self, expr: &mut Expression) -> TypeCheckerReturn {
+        self.resolve_variant(expr)?;
+        self.resolve_method_call(expr)?;
+
         match expr {
             Expression::Literal(e) => self.visit_literal(e),
             Expression::Binary(e) => self.visit_binary(e),
@@ -96,10 +202,20 @@ impl TypeChecker {
             Expression::AddressOf(address_of) => self.visit_address_of_expression(address_of),
             Expression::DeReference(deref) => self.visit_dereference_expression(deref),
             Expression::MemberAccess(member_access) => self.visit_member_access(member_access),
+            Expression::Conditional(conditional) => self.visit_conditional(conditional),
+            Expression::ArrayLiteral(array_literal) => self.visit_array_literal(array_literal),
+            Expression::ArrayRepeat(array_repeat) => self.visit_array_repeat(array_repeat),
+            Expression::ModuleAccess(module_access) => self.visit_module_access(module_access),
+            Expression::Cast(cast) => self.visit_cast(cast),
+            Expression::EnumVariant(enum_variant) => self.visit_enum_variant(enum_variant),
+            Expression::UnionVariant(union_variant) => self.visit_union_variant(union_variant),
         }
     }
 
     pub fn visit_boxed_expr(&mut self, expr: &mut Box<Expression>) -> TypeCheckerReturn {
+        self.resolve_variant(expr)?;
+        self.resolve_method_call(expr)?;
+
         match &mut **expr {
             Expression::Literal(e) => self.visit_literal(e),
             Expression::Binary(e) => self.visit_binary(e),
@@ -112,6 +228,13 @@ impl TypeChecker {
             Expression::AddressOf(address_of) => self.visit_address_of_expression(address_of),
             Expression::DeReference(deref) => self.visit_dereference_expression(deref),
             Expression::MemberAccess(member_access) => self.visit_member_access(member_access),
+            Expression::Conditional(conditional) => self.visit_conditional(conditional),
+            Expression::ArrayLiteral(array_literal) => self.visit_array_literal(array_literal),
+            Expression::ArrayRepeat(array_repeat) => self.visit_array_repeat(array_repeat),
+            Expression::ModuleAccess(module_access) => self.visit_module_access(module_access),
+            Expression::Cast(cast) => self.visit_cast(cast),
+            Expression::EnumVariant(enum_variant) => self.visit_enum_variant(enum_variant),
+            Expression::UnionVariant(union_variant) => self.visit_union_variant(union_variant),
         }
     }
 
@@ -132,6 +255,10 @@ impl TypeChecker {
 
         if let Ok(lhs_type) = &lhs_result {
             if let Ok(rhs_type) = &rhs_result {
+                // A literal operand takes the type of the other operand
+                let rhs_type = &self.coerce_literal(r, rhs_type.clone(), lhs_type)?;
+                let lhs_type = &self.coerce_literal(l, lhs_type.clone(), rhs_type)?;
+
                 if ValueType::is_compatible(lhs_type, rhs_type) {
                     Ok(lhs_type.clone())
                 } else {
@@ -149,22 +276,118 @@ impl TypeChecker {
     }
 
     pub fn find_variable_type(&self, name: &String) -> Option<&ValueType> {
-        for scope in self.variables_table.iter().rev() {
-            if scope.contains_key(name) {
-                return Some(scope.get(name).unwrap());
-            }
+        self.get_variable(name).map(|variable| &variable.value_type)
+    }
+
+    /// Variable `name` of the innermost scope declaring it
+    pub(super) fn get_variable(&self, name: &String) -> Option<&Variable> {
+        self.variables_table
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+    }
+
+    /// Marks the variable `name` of the innermost scope declaring it as read
+    pub(super) fn mark_variable_used(&mut self, name: &String) {
+        if let Some(variable) = self
+            .variables_table
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+        {
+            variable.is_used = true;
         }
+    }
 
-        None
+    /// Declares a variable in the innermost scope
+    pub(super) fn add_variable(
+        &mut self,
+        name: &String,
+        value_type: &ValueType,
+        is_const: bool,
+        unused_warning: Option<Warning>,
+    ) {
+        self.type_table.add_variable(name, value_type);
+        self.variables_table.last_mut().unwrap().insert(
+            name.to_string(),
+            Variable {
+                value_type: value_type.clone(),
+                is_used: false,
+                unused_warning,
+                is_const,
+            },
+        );
+    }
+
+    /// Closes the innermost scope, its variables never read are reported
+    pub(super) fn pop_scope(&mut self) {
+        let scope = self.variables_table.pop().unwrap();
+        let mut unused: Vec<Warning> = scope
+            .into_values()
+            .filter(|variable| !variable.is_used)
+            .filter_map(|variable| variable.unused_warning)
+            .collect();
+
+        // In declaration order
+        unused.sort_by_key(|warning| (warning.line, warning.column));
+        for warning in unused {
+            self.push_warning(warning);
+        }
     }
 
-    pub fn add_variables_in_scope(&mut self, args: &Vec<(String, ValueType)>) {
+    /// Opens a scope declaring the parameters of a function or the bindings
+    /// of a match arm. `unused_warning` gives the warning reported for the
+    /// ones never read.
+    pub fn add_variables_in_scope<T: LineDebugInfo>(
+        &mut self,
+        args: &Vec<(String, ValueType)>,
+        element: &T,
+        unused_warning: impl Fn(&String) -> Option<Warning>,
+    ) {
+        // The parameters can only shadow the globals of the module scope
+        let enclosing = match self.in_function {
+            Some(_) => &self.variables_table[..],
+            None => &self.variables_table[..1],
+        };
+
+        let shadowed: Vec<&String> = args
+            .iter()
+            .map(|(name, _)| name)
+            // The module scope holds the functions too, they aren't variables
+            .filter(|name| !self.function_table.contains_key(*name))
+            .filter(|name| enclosing.iter().any(|scope| scope.contains_key(*name)))
+            .collect();
+
+        for name in shadowed {
+            self.add_warning(
+                Lint::Shadowing,
+                format!("'{}' shadows a variable of an enclosing scope.", name).as_str(),
+                element,
+            );
+        }
+
         self.variables_table.push(HashMap::new());
-        let last = self.variables_table.last_mut().unwrap();
 
         for (name, arg_type) in args {
-            self.type_table.add_variable(name, arg_type);
-            last.insert(name.to_string(), arg_type.clone());
+            self.add_variable(name, arg_type, false, unused_warning(name));
+        }
+    }
+
+    /// Variable modified by assigning `target`: the root of its array and
+    /// member accesses, unless they go through a pointer.
+    pub(super) fn assigned_variable(target: &Expression) -> Option<&Identifier> {
+        let object = match target {
+            Expression::Literal(Literal::Identifier(identifier)) => return Some(identifier),
+            Expression::Group(group) => return Self::assigned_variable(&group.inner_expression),
+            Expression::ArrayAccess(access) => &access.identifier,
+            Expression::MemberAccess(member_access) => &member_access.object,
+            _ => return None,
+        };
+
+        let root = Self::assigned_variable(object)?;
+        match object.get_type() {
+            ValueType::Pointer(_) => None,
+            _ => Some(root),
         }
     }
 
@@ -174,10 +397,18 @@ impl TypeChecker {
         rhs: &mut Expression,
     ) -> TypeCheckerReturn {
         let rhs_ty = self.check_expr(rhs)?;
+        let access_ty = self.visit_boxed_expr(&mut access.identifier)?;
 
-        match self.visit_boxed_expr(&mut access.identifier)? {
+        let rhs_ty = match &access_ty {
+            ValueType::Array(array) => self.coerce_literal(rhs, rhs_ty, &array.array_type)?,
+            ValueType::Pointer(ptr) => self.coerce_literal(rhs, rhs_ty, ptr)?,
+            _ => rhs_ty,
+        };
+
+        match access_ty {
             ValueType::Array(array) => {
                 if ValueType::is_compatible(array.array_type.deref(), &rhs_ty) {
+                    access.set_type(*array.array_type);
                     Ok(rhs_ty)
                 } else {
                     Err(format!(
@@ -188,6 +419,7 @@ impl TypeChecker {
             }
             ValueType::Pointer(ptr) => {
                 if ValueType::is_compatible(&ptr, &rhs_ty) {
+                    access.set_type(*ptr);
                     Ok(rhs_ty)
                 } else {
                     Err(format!(
@@ -201,6 +433,7 @@ impl TypeChecker {
                 if rhs_ty != ValueType::Char {
                     Err(format!("Can't assign expression of type '{}' to string element. Expression must be a 'char'", rhs_ty))
                 } else {
+                    access.set_type(ValueType::Char);
                     Ok(rhs_ty)
                 }
             }
@@ -214,7 +447,11 @@ impl TypeChecker {
         rhs: &mut Expression,
     ) -> TypeCheckerReturn {
         let expr_type = self.check_expr(rhs)?;
-        let variable_type = self.check_expr(identifier)?;
+        self.is_assigned = true;
+        let variable_type = self.check_expr(identifier);
+        self.is_assigned = false;
+        let variable_type = variable_type?;
+        let expr_type = self.coerce_literal(rhs, expr_type, &variable_type)?;
 
         if !ValueType::is_compatible(&expr_type, &variable_type) {
             return Err(format!(
@@ -230,19 +467,53 @@ impl TypeChecker {
         &mut self,
         struct_literal: &mut StructLiteral,
     ) -> TypeCheckerReturn {
+        let is_generic = self.generic_structs.contains_key(&struct_literal.type_name);
         let struct_dec = if let Some(dec) = self.structs_table.get(&struct_literal.type_name) {
             dec.clone()
+        } else if let Some(generic) = self.generic_structs.get(&struct_literal.type_name) {
+            generic.clone()
         } else {
             return Err(format!("Undeclared struct '{}'", struct_literal.type_name));
         };
 
+        if !is_generic && !struct_literal.type_args.is_empty() {
+            return Err(Self::build_error_message(
+                format!("Struct '{}' is not generic.", struct_literal.type_name).as_str(),
+                struct_literal,
+            ));
+        }
+
+        if let Some(field_names) = struct_literal.field_names.take() {
+            struct_literal.expressions =
+                Self::order_struct_literal_fields(struct_literal, &struct_dec, field_names)?;
+        }
+
         if struct_dec.fields.len() != struct_literal.expressions.len() {
             return Err(format!("Incorrect number of expressions to init struct '{}', got {} expressions but {} are required.", struct_literal.type_name,
                                struct_literal.expressions.len(), struct_dec.fields.len()));
         }
 
-        for (i, expr) in struct_literal.expressions.iter_mut().enumerate() {
-            let expr_type = self.check_expr(expr)?;
+        let expr_types = struct_literal
+            .expressions
+            .iter_mut()
+            .map(|expr| self.check_expr(expr))
+            .collect::<Result<Vec<ValueType>, String>>()?;
+
+        // A generic struct is initialised through its instance
+        let struct_dec = if is_generic {
+            self.instantiate_struct_literal(struct_literal, &expr_types)?;
+            self.structs_table[&struct_literal.type_name].clone()
+        } else {
+            struct_dec
+        };
+
+        for (i, (expr, expr_type)) in struct_literal
+            .expressions
+            .iter_mut()
+            .zip(expr_types)
+            .enumerate()
+        {
+            let expr_type = self.coerce_literal(expr, expr_type, &struct_dec.fields[i].1)?;
 
             if !ValueType::is_compatible_for_init(&struct_dec.fields[i].1, &expr_type) {
                 return Err(format!(
@@ -258,6 +529,852 @@ impl TypeChecker {
         Ok(ValueType::Struct(struct_dec.type_name))
     }
 
+    /// Puts the expressions of a named fields literal in the declaration order,
+    /// the missing fields take their default value.
+    fn order_struct_literal_fields(
+        struct_literal: &mut StructLiteral,
+        struct_dec: &StructStatement,
+        field_names: Vec<String>,
+    ) -> Result<Vec<Expression>, String> {
+        let mut values: Vec<Option<Expression>> = vec![None; struct_dec.fields.len()];
+
+        for (name, expr) in field_names.iter().zip(struct_literal.expressions.drain(..)) {
+            let position = struct_dec.fields.iter().position(|f| &f.0 == name);
+
+            match position {
+                None => {
+                    return Err(Self::build_error_message(
+                        format!("Struct '{}' has no field '{}'.", struct_dec.type_name, name)
+                            .as_str(),
+                        &expr,
+                    ))
+                }
+                Some(i) if values[i].is_some() => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Field '{}' is initialised more than once in struct '{}' literal.",
+                            name, struct_dec.type_name
+                        )
+                        .as_str(),
+                        &expr,
+                    ))
+                }
+                Some(i) => values[i] = Some(expr),
+            }
+        }
+
+        let mut missing: Vec<String> = Vec::new();
+        let expressions: Vec<Expression> = values
+            .into_iter()
+            .zip(struct_dec.fields.iter())
+            .filter_map(|(value, field)| match value.or_else(|| field.3.clone()) {
+                Some(expr) => Some(expr),
+                None => {
+                    missing.push(format!("'{}'", field.0));
+                    None
+                }
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(Self::build_error_message(
+                format!(
+                    "Missing field{} {} in struct '{}' literal.",
+                    if missing.len() > 1 { "s" } else { "" },
+                    missing.join(", "),
+                    struct_dec.type_name
+                )
+                .as_str(),
+                struct_literal,
+            ));
+        }
+
+        Ok(expressions)
+    }
+
+    /// Prefixes the item of `module::item` with the module namespace, the
+    /// import resolver declares the imported items with their qualified name.
+    pub(super) fn qualify_module_access(
+        &self,
+        module_access: &mut ModuleAccess,
+    ) -> Result<(), String> {
+        let prefix = format!("{}::", module_access.module);
+        let is_imported = self
+            .function_table
+            .keys()
+            .chain(self.generic_functions.keys())
+            .chain(self.structs_table.keys())
+            .chain(self.generic_structs.keys())
+            .chain(self.variables_table[0].keys())
+            .any(|name| name.starts_with(&prefix));
+
+        if !is_imported {
+            return Err(Self::build_error_message(
+                format!("Module '{}' is not imported.", module_access.module).as_str(),
+                module_access,
+            ));
+        }
+
+        let mut item = &mut *module_access.item;
+        let name = loop {
+            match item {
+                Expression::Call(call) => break &mut call.name,
+                Expression::Literal(Literal::Identifier(identifier)) => break &mut identifier.name,
+                Expression::Literal(Literal::StructLiteral(struct_literal)) => {
+                    break &mut struct_literal.type_name
+                }
+                Expression::ArrayAccess(access) => item = &mut *access.identifier,
+                Expression::MemberAccess(access) => item = &mut *access.object,
+                _ => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Expected a function, a global or a struct after '{}'.",
+                            prefix
+                        )
+                        .as_str(),
+                        module_access,
+                    ))
+                }
+            }
+        };
+
+        // Already qualified if the item was checked before
+        if !name.contains("::") {
+            *name = format!("{}{}", prefix, name);
+        }
+
+        Ok(())
+    }
+
+    /// Gives its actual type to a type annotation: enum and union types are
+    /// parsed as struct types, the type parameters of the instance being
+    /// checked take their type argument and generic structs are instantiated.
+    pub(super) fn resolve_type<T: LineDebugInfo>(
+        &mut self,
+        ty: &ValueType,
+        element: &T,
+    ) -> TypeCheckerReturn {
+        match ty {
+            ValueType::Struct(name) if self.type_arguments.contains_key(name) => {
+                Ok(self.type_arguments[name].clone())
+            }
+            ValueType::Struct(name) if self.enums_table.contains_key(name) => {
+                Ok(ValueType::Enum(name.clone()))
+            }
+            ValueType::Struct(name) if self.unions_table.contains_key(name) => {
+                Ok(ValueType::Union(name.clone()))
+            }
+            ValueType::Struct(name) if self.interfaces_table.contains_key(name) => {
+                Err(Self::build_error_message(
+                    format!(
+                        "Interface '{}' can't be used as a type, 'dyn {}' is a value implementing it.",
+                        name, name
+                    )
+                    .as_str(),
+                    element,
+                ))
+            }
+            ValueType::Dyn(name) if !self.interfaces_table.contains_key(name) => {
+                Err(Self::build_error_message(
+                    format!("'{}' is not a declared interface.", name).as_str(),
+                    element,
+                ))
+            }
+            ValueType::Struct(name) if self.generic_structs.contains_key(name) => {
+                let generic = &self.generic_structs[name];
+                Self::check_type_arg_count(
+                    "Struct",
+                    name,
+                    &generic.type_params,
+                    0,
+                    generic,
+                    element,
+                )?;
+                Ok(ty.clone())
+            }
+            ValueType::Generic(name, type_args) => {
+                let type_args = type_args
+                    .iter()
+                    .map(|type_arg| self.resolve_type(type_arg, element))
+                    .collect::<Result<Vec<ValueType>, String>>()?;
+
+                Ok(ValueType::Struct(
+                    self.instantiate_struct(name, &type_args, element)?,
+                ))
+            }
+            ValueType::Pointer(inner) => Ok(ValueType::Pointer(Box::new(
+                self.resolve_type(inner, element)?,
+            ))),
+            ValueType::Array(array) => Ok(ValueType::Array(StaticArray {
+                size: array.size,
+                array_type: Box::new(self.resolve_type(&array.array_type, element)?),
+            })),
+            _ => Ok(ty.clone()),
+        }
+    }
+
+    /// `Enum::Variant`, `Union::Variant(payload, ...)` and their
+    /// `module::Type::Variant` forms are parsed as module accesses, replaces
+    /// them with the variant. Other module accesses are left as is.
+    fn resolve_variant(&self, expr: &mut Expression) -> Result<(), String> {
+        let module_access = match expr {
+            Expression::ModuleAccess(module_access) => module_access,
+            _ => return Ok(()),
+        };
+
+        let mut path = vec![module_access.module.as_str()];
+        let mut item = &*module_access.item;
+        while let Expression::ModuleAccess(access) = item {
+            path.push(access.module.as_str());
+            item = &*access.item;
+        }
+
+        let type_name = path.join("::");
+
+        // `Type::function(args)` calls a function of the impl blocks of `Type`,
+        // `Interface::method(value, args)` a method of a `dyn Interface` value
+        if let Expression::Call(call) = item {
+            if self.structs_table.contains_key(&type_name)
+                || self.interfaces_table.contains_key(&type_name)
+            {
+                let call = Call {
+                    name: format!("{}::{}", type_name, call.name),
+                    line: module_access.line,
+                    column: module_access.column,
+                    filename: module_access.filename.clone(),
+                    span: module_access.span,
+                    ..call.clone()
+                };
+
+                *expr = Expression::Call(call);
+                return Ok(());
+            }
+        }
+
+        if let Some(union_stmt) = self.unions_table.get(&type_name) {
+            let (variant, payload) = match item {
+                Expression::Literal(Literal::Identifier(identifier)) => {
+                    (identifier.name.clone(), Vec::new())
+                }
+                Expression::Call(call) => {
+                    (call.name.clone(), call.args.clone().unwrap_or_default())
+                }
+                _ => return Ok(()),
+            };
+
+            let tag = Self::union_variant_tag(union_stmt, &variant, &*module_access)?;
+            let union_variant = UnionVariant {
+                union_name: type_name,
+                variant,
+                tag,
+                payload,
+                line: module_access.line,
+                column: module_access.column,
+                filename: module_access.filename.clone(),
+                span: module_access.span,
+            };
+
+            *expr = Expression::UnionVariant(union_variant);
+            return Ok(());
+        }
+
+        let variant = match item {
+            Expression::Literal(Literal::Identifier(identifier)) => identifier.name.clone(),
+            _ => return Ok(()),
+        };
+
+        let enum_name = type_name;
+        let value = match self.enums_table.get(&enum_name) {
+            Some(enm) => Self::enum_variant_value(enm, &variant, &*module_access)?,
+            None => return Ok(()),
+        };
+
+        let enum_variant = EnumVariant {
+            enum_name,
+            variant,
+            value,
+            line: module_access.line,
+            column: module_access.column,
+            filename: module_access.filename.clone(),
+            span: module_access.span,
+        };
+
+        *expr = Expression::EnumVariant(enum_variant);
+        Ok(())
+    }
+
+    /// `value.method(args)` is parsed as a member access with arguments,
+    /// replaces it with the call `Type::method(value, args)`. The value is
+    /// given by address to a `self: ptr Type` receiver and dereferenced for a
+    /// `self: Type` one. A `dyn Interface` value is given as is to
+    /// `Interface::method`.
+    fn resolve_method_call(&mut self, expr: &mut Expression) -> Result<(), String> {
+        let member_access = match expr {
+            Expression::MemberAccess(member_access) if member_access.args.is_some() => {
+                member_access
+            }
+            _ => return Ok(()),
+        };
+
+        let object_type = self.visit_boxed_expr(&mut member_access.object)?;
+        let (type_name, is_pointer) = match &object_type {
+            ValueType::Struct(name) | ValueType::Dyn(name) => (name.clone(), false),
+            ValueType::Pointer(inner) if matches!(inner.as_ref(), ValueType::Struct(_)) => {
+                (inner.into_struct_type(), true)
+            }
+            _ => return Err(Self::build_error_message(
+                format!(
+                    "Method '{}' called on a value of type '{}', methods are declared for structs and interfaces.",
+                    member_access.member,
+                    object_type.source_name()
+                )
+                .as_str(),
+                &*member_access,
+            )),
+        };
+
+        let name = format!("{}::{}", type_name, member_access.member);
+        let receiver_type = match self.methods.get(&name) {
+            Some(receiver_type) => receiver_type.clone(),
+            None if self.function_table.contains_key(&name)
+                || self.generic_functions.contains_key(&name) =>
+            {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Function '{}' has no 'self' parameter, it's called as '{}(...)'.",
+                        name, name
+                    )
+                    .as_str(),
+                    &*member_access,
+                ))
+            }
+            None => {
+                return Err(Self::build_error_message(
+                    format!(
+                        "{} '{}' has no method '{}'.",
+                        if let ValueType::Dyn(_) = object_type {
+                            "Interface"
+                        } else {
+                            "Struct"
+                        },
+                        type_name,
+                        member_access.member
+                    )
+                    .as_str(),
+                    &*member_access,
+                ))
+            }
+        };
+
+        let object = (*member_access.object).clone();
+        let receiver = match (is_pointer, matches!(receiver_type, ValueType::Pointer(_))) {
+            (false, true) => match object {
+                Expression::Literal(Literal::Identifier(_))
+                | Expression::MemberAccess(_)
+                | Expression::DeReference(_) => Expression::AddressOf(AddressOf {
+                    identifier: Box::new(object),
+                    ty: None,
+                    line: member_access.line,
+                    column: member_access.column,
+                    filename: member_access.filename.clone(),
+                    span: member_access.span,
+                }),
+                _ => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Method '{}' takes 'self' by address, it must be called on a variable, a member or a dereferenced pointer.",
+                            name
+                        )
+                        .as_str(),
+                        &*member_access,
+                    ))
+                }
+            },
+            (true, false) => Expression::DeReference(DeReference {
+                identifier: Box::new(object),
+                ty: None,
+                is_lvalue: false,
+                line: member_access.line,
+                column: member_access.column,
+                filename: member_access.filename.clone(),
+                span: member_access.span,
+            }),
+            _ => object,
+        };
+
+        let mut args = vec![receiver];
+        args.extend(member_access.args.take().unwrap_or_default());
+
+        let call = Call {
+            name,
+            args: Some(args),
+            type_args: Vec::new(),
+            ty: None,
+            line: member_access.line,
+            column: member_access.column,
+            filename: member_access.filename.clone(),
+            span: member_access.span,
+        };
+
+        *expr = Expression::Call(call);
+        Ok(())
+    }
+
+    /// Sets the value of an `Enum::Variant` match pattern, the tag of a
+    /// `Union::Variant(...)` one. Returns the type of the values bound by the
+    /// pattern.
+    pub(super) fn resolve_variant_pattern(
+        &self,
+        pattern: &mut EnumPattern,
+    ) -> Result<Vec<(String, ValueType)>, String> {
+        if let Some(union_stmt) = self.unions_table.get(&pattern.enum_name) {
+            let tag = Self::union_variant_tag(union_stmt, &pattern.variant, pattern)?;
+            pattern.value = Some(tag);
+
+            // Without bindings the payload is ignored
+            if pattern.bindings.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let payload = &union_stmt.variants[tag as usize].1;
+            if pattern.bindings.len() != payload.len() {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Pattern '{}' binds {} value{} but variant '{}' holds {}.",
+                        Pattern::EnumVariant(pattern.clone()),
+                        pattern.bindings.len(),
+                        if pattern.bindings.len() > 1 { "s" } else { "" },
+                        pattern.variant,
+                        payload.len()
+                    )
+                    .as_str(),
+                    pattern,
+                ));
+            }
+
+            let mut bound: Vec<(String, ValueType)> = Vec::new();
+            for (binding, ty) in pattern.bindings.iter().zip(payload) {
+                if let Some(name) = binding {
+                    if bound.iter().any(|(bound_name, _)| bound_name == name) {
+                        return Err(Self::build_error_message(
+                            format!(
+                                "Variable '{}' is bound more than once in pattern '{}'.",
+                                name,
+                                Pattern::EnumVariant(pattern.clone())
+                            )
+                            .as_str(),
+                            pattern,
+                        ));
+                    }
+
+                    bound.push((name.clone(), ty.clone()));
+                }
+            }
+
+            return Ok(bound);
+        }
+
+        let enm = match self.enums_table.get(&pattern.enum_name) {
+            Some(enm) => enm,
+            None => {
+                return Err(Self::build_error_message(
+                    format!("Undeclared enum or union '{}'.", pattern.enum_name).as_str(),
+                    pattern,
+                ))
+            }
+        };
+
+        pattern.value = Some(Self::enum_variant_value(enm, &pattern.variant, pattern)?);
+
+        if !pattern.bindings.is_empty() {
+            return Err(Self::build_error_message(
+                format!(
+                    "Variant '{}' of enum '{}' has no payload to bind.",
+                    pattern.variant, pattern.enum_name
+                )
+                .as_str(),
+                pattern,
+            ));
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// Payload types are resolved once every union is declared
+    pub(super) fn resolve_union_payloads(
+        &mut self,
+        union_stmt: &mut UnionStatement,
+    ) -> Result<(), String> {
+        for i in 0..union_stmt.variants.len() {
+            for j in 0..union_stmt.variants[i].1.len() {
+                let ty = self.resolve_type(&union_stmt.variants[i].1[j], union_stmt)?;
+                let variant = &union_stmt.variants[i].0;
+
+                let is_valid = match &ty {
+                    ValueType::Void | ValueType::Null | ValueType::Function => false,
+                    ValueType::Union(name) => name != &union_stmt.type_name,
+                    _ => true,
+                };
+
+                if !is_valid {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Payload of variant '{}' in union '{}' can't have type '{}'.",
+                            variant, union_stmt.type_name, ty
+                        )
+                        .as_str(),
+                        union_stmt,
+                    ));
+                }
+
+                union_stmt.variants[i].1[j] = ty;
+            }
+        }
+
+        self.unions_table
+            .insert(union_stmt.type_name.clone(), union_stmt.clone());
+        self.type_table.add_union_type(union_stmt);
+
+        Ok(())
+    }
+
+    pub(super) fn union_variant_tag<T: LineDebugInfo>(
+        union_stmt: &UnionStatement,
+        variant: &str,
+        element: &T,
+    ) -> Result<i64, String> {
+        match union_stmt
+            .variants
+            .iter()
+            .position(|(name, _, _)| name == variant)
+        {
+            Some(tag) => Ok(tag as i64),
+            None => Err(Self::build_error_message(
+                format!(
+                    "Union '{}' has no variant '{}'.",
+                    union_stmt.type_name, variant
+                )
+                .as_str(),
+                element,
+            )),
+        }
+    }
+
+    fn enum_variant_value<T: LineDebugInfo>(
+        enm: &EnumStatement,
+        variant: &str,
+        element: &T,
+    ) -> Result<i64, String> {
+        match enm.variants.iter().find(|(name, _, _)| name == variant) {
+            Some((_, value, _)) => Ok(*value),
+            None => Err(Self::build_error_message(
+                format!("Enum '{}' has no variant '{}'.", enm.type_name, variant).as_str(),
+                element,
+            )),
+        }
+    }
+
+    /// Integer and real literals have the type `number` and `real` unless
+    /// they're used where a sized type is expected: the literal is then cast to
+    /// this type. A pointer to a struct is cast to a `dyn Interface` expected
+    /// for it when the struct implements the interface. Returns the type of
+    /// `expr` after the conversion.
+    pub(super) fn coerce_literal(
+        &self,
+        expr: &mut Expression,
+        ty: ValueType,
+        expected: &ValueType,
+    ) -> TypeCheckerReturn {
+        if &ty == expected {
+            return Ok(ty);
+        }
+
+        if let ValueType::Dyn(_) = expected {
+            if !self.is_dyn_convertible(&ty, expected) {
+                return Ok(ty);
+            }
+        } else if let Some(value) = Self::integer_literal_value(expr) {
+            let (min, max) = match expected.integer_range() {
+                Some(range) => range,
+                None => return Ok(ty),
+            };
+
+            if value < min || value > max {
+                return Err(Self::build_error_message(
+                    format!("Literal {} doesn't fit in type '{}'.", value, expected).as_str(),
+                    expr,
+                ));
+            }
+        } else if !(ty.is_float() && expected.is_float() && Self::is_real_literal(expr)) {
+            return Ok(ty);
+        }
+
+        *expr = Expression::Cast(Cast {
+            expr: Box::new(expr.clone()),
+            target: expected.clone(),
+            line: expr.line(),
+            column: expr.column(),
+            filename: expr.file_name().to_string(),
+            span: expr.span(),
+        });
+
+        Ok(expected.clone())
+    }
+
+    /// Whether a value of type `ty` can be converted to `dyn_type`, it must
+    /// point to a struct implementing the interface.
+    pub(super) fn is_dyn_convertible(&self, ty: &ValueType, dyn_type: &ValueType) -> bool {
+        match (ty, dyn_type) {
+            (ValueType::Pointer(inner), ValueType::Dyn(interface)) => match inner.as_ref() {
+                ValueType::Struct(name) => self
+                    .implementations
+                    .contains(&(interface.clone(), name.clone())),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether `ty` names the type `name`, through pointers, arrays and type
+    /// arguments
+    pub(super) fn names_type(ty: &ValueType, name: &str) -> bool {
+        match ty {
+            ValueType::Struct(type_name) => type_name == name,
+            ValueType::Pointer(inner) => Self::names_type(inner, name),
+            ValueType::Array(array) => Self::names_type(&array.array_type, name),
+            ValueType::Generic(type_name, type_args) => {
+                type_name == name || type_args.iter().any(|ty| Self::names_type(ty, name))
+            }
+            _ => false,
+        }
+    }
+
+    /// Value of an integer literal, possibly negated or parenthesized
+    pub(super) fn integer_literal_value(expr: &Expression) -> Option<i128> {
+        match expr {
+            Expression::Literal(Literal::Number(n)) => Some(n.value as i128),
+            Expression::Unary(Unary::Negate(_, e)) => Self::integer_literal_value(e).map(|v| -v),
+            Expression::Group(g) => Self::integer_literal_value(&g.inner_expression),
+            _ => None,
+        }
+    }
+
+    pub(super) fn is_real_literal(expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(Literal::Real(_)) => true,
+            Expression::Unary(Unary::Negate(_, e)) => Self::is_real_literal(e),
+            Expression::Group(g) => Self::is_real_literal(&g.inner_expression),
+            _ => false,
+        }
+    }
+
+    /// Checks if `expr` can be computed at compile time, global variables must
+    /// be initialised with such expressions. The address of a global is
+    /// known at link time so it's a constant too.
+    pub(super) fn is_constant_expression(expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(Literal::Identifier(_))
+            | Expression::Literal(Literal::StructLiteral(_)) => false,
+            Expression::Literal(_) | Expression::Null(_) | Expression::EnumVariant(_) => true,
+            Expression::Group(g) => Self::is_constant_expression(&g.inner_expression),
+            Expression::Unary(Unary::Not(_, e)) | Expression::Unary(Unary::Negate(_, e)) => {
+                Self::is_constant_expression(e)
+            }
+            Expression::Binary(Binary::Plus(l, r))
+            | Expression::Binary(Binary::Minus(l, r))
+            | Expression::Binary(Binary::Multiply(l, r))
+            | Expression::Binary(Binary::Divide(l, r))
+            | Expression::Binary(Binary::Modulo(l, r))
+            | Expression::BinaryLogic(BinaryLogic::And(l, r))
+            | Expression::BinaryLogic(BinaryLogic::Or(l, r))
+            | Expression::BinaryLogic(BinaryLogic::Equal(l, r))
+            | Expression::BinaryLogic(BinaryLogic::NotEqual(l, r))
+            | Expression::BinaryLogic(BinaryLogic::More(l, r))
+            | Expression::BinaryLogic(BinaryLogic::Less(l, r))
+            | Expression::BinaryLogic(BinaryLogic::MoreEqual(l, r))
+            | Expression::BinaryLogic(BinaryLogic::LessEqual(l, r)) => {
+                Self::is_constant_expression(l) && Self::is_constant_expression(r)
+            }
+            Expression::ArrayLiteral(array_literal) => array_literal
+                .elements
+                .iter()
+                .all(|e| Self::is_constant_expression(e)),
+            Expression::ArrayRepeat(array_repeat) => {
+                Self::is_constant_expression(&array_repeat.value)
+            }
+            Expression::Cast(cast) => Self::is_constant_expression(&cast.expr),
+            Expression::AddressOf(address_of) => matches!(
+                *address_of.identifier,
+                Expression::Literal(Literal::Identifier(_))
+            ),
+            _ => false,
+        }
+    }
+
+    pub(super) fn check_array_element_type(
+        ty: &ValueType,
+        element: &Expression,
+    ) -> Result<(), String> {
+        match ty {
+            ValueType::Void | ValueType::Function => Err(Self::build_error_message(
+                format!("Array elements can't have type '{}'.", ty).as_str(),
+                element,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the inclusive range of values matched by `pattern`, the bounds of
+    /// char and bool values are their integer codes. Number literals match
+    /// every integer type, in the range of the type.
+    pub(super) fn pattern_range(
+        &self,
+        pattern: &Pattern,
+        scrutinee_type: &ValueType,
+        domain: (i128, i128),
+    ) -> Result<(i128, i128), String> {
+        let literal_value = |literal: &Literal| {
+            let (ty, value) = match literal {
+                Literal::Number(n) if scrutinee_type.is_integer() => {
+                    (scrutinee_type.clone(), n.value as i128)
+                }
+                Literal::Number(n) => (ValueType::Number, n.value as i128),
+                Literal::Char(c) => (ValueType::Char, c.value as i128),
+                Literal::Bool(b) => (ValueType::Bool, b.value as i128),
+                _ => unreachable!("Match patterns only hold number, char or bool literals"),
+            };
+
+            if &ty != scrutinee_type {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Pattern '{}' has type '{}' but the matched expression has type '{}'.",
+                        pattern, ty, scrutinee_type
+                    )
+                    .as_str(),
+                    pattern,
+                ));
+            }
+
+            if ty.is_integer() && (value < domain.0 || value > domain.1) {
+                return Err(Self::build_error_message(
+                    format!("Literal {} doesn't fit in type '{}'.", value, ty).as_str(),
+                    literal,
+                ));
+            }
+
+            Ok(value)
+        };
+
+        match pattern {
+            Pattern::Wildcard(_) => Ok(domain),
+            Pattern::EnumVariant(enum_pattern) => {
+                let ty = if self.unions_table.contains_key(&enum_pattern.enum_name) {
+                    ValueType::Union(enum_pattern.enum_name.clone())
+                } else {
+                    ValueType::Enum(enum_pattern.enum_name.clone())
+                };
+
+                if &ty != scrutinee_type {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Pattern '{}' has type '{}' but the matched expression has type '{}'.",
+                            pattern, ty, scrutinee_type
+                        )
+                        .as_str(),
+                        pattern,
+                    ));
+                }
+
+                // Set by the type checker before computing the ranges
+                let value = enum_pattern.value.unwrap() as i128;
+                Ok((value, value))
+            }
+            Pattern::Literal(literal) => {
+                let value = literal_value(literal)?;
+                Ok((value, value))
+            }
+            Pattern::Range(range) => {
+                let start = literal_value(&range.start)?;
+                let end = literal_value(&range.end)?;
+
+                if *scrutinee_type == ValueType::Bool {
+                    return Err(Self::build_error_message(
+                        "Range patterns can't be used on bool values.",
+                        pattern,
+                    ));
+                }
+
+                if end < start || (!range.inclusive && end == start) {
+                    return Err(Self::build_error_message(
+                        format!("Range pattern '{}' matches no value.", pattern).as_str(),
+                        pattern,
+                    ));
+                }
+
+                Ok((start, if range.inclusive { end } else { end - 1 }))
+            }
+        }
+    }
+
+    /// Checks if every value of the inclusive range `(low, high)` is
+    /// in one of the `covered` ranges.
+    pub(super) fn is_range_covered(covered: &[(i128, i128)], (low, high): (i128, i128)) -> bool {
+        let mut ranges = covered.to_vec();
+        ranges.sort();
+
+        // Lowest value of (low, high) not found so far
+        let mut next = low;
+        for (start, end) in ranges {
+            if start > next {
+                break;
+            }
+
+            if end >= next {
+                if end >= high {
+                    return true;
+                }
+
+                next = end + 1;
+            }
+        }
+
+        false
+    }
+
+    pub(super) fn add_warning<T: LineDebugInfo>(&mut self, lint: Lint, msg: &str, element: &T) {
+        self.push_warning(Warning::new(lint, msg, element));
+    }
+
+    /// The body of a generic item is checked for each instance, its warnings
+    /// are reported once
+    fn push_warning(&mut self, warning: Warning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    #[inline]
+    pub fn build_warning_message<T: LineDebugInfo>(msg: &str, element: &T) -> String {
+        format!(
+            "{}:{}:{} Warning: {}",
+            element.file_name(),
+            element.line(),
+            element.column(),
+            msg
+        )
+    }
+
+    #[inline]
+    pub fn build_note_message<T: LineDebugInfo>(msg: &str, element: &T) -> String {
+        format!(
+            "{}:{}:{} Note: {}",
+            element.file_name(),
+            element.line(),
+            element.column(),
+            msg
+        )
+    }
+
     #[inline]
     pub fn build_error_message<T: LineDebugInfo>(msg: &str, element: &T) -> String {
         format!(
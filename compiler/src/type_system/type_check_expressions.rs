@@ -1,12 +1,13 @@
 use crate::parser::visitors::{
-    AddressOf, ArrayAccess, Binary, BinaryLogic, Call, DeReference, Group, Literal, MemberAccess,
-    MutableExpressionVisitor, StructLiteral, Unary,
+    AddressOf, ArrayAccess, ArrayLiteral, ArrayRepeat, Binary, BinaryLogic, Call, Cast,
+    Conditional, DeReference, EnumVariant, Group, Literal, MemberAccess, ModuleAccess,
+    MutableExpressionVisitor, StructLiteral, Unary, UnionVariant,
 };
 
 use super::{
     type_check::{TypeChecker, TypeCheckerReturn},
     typed::Typed,
-    value_type::ValueType,
+    value_type::{StaticArray, ValueType},
 };
 
 impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
@@ -18,10 +19,15 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             Literal::Char(_) => Ok(ValueType::Char),
             Literal::StringLiteral(_) => Ok(ValueType::String),
             Literal::Identifier(identifier) => {
-                if let Some(var_type) = self.find_variable_type(&identifier.name) {
+                if let Some(var_type) = self.find_variable_type(&identifier.name).cloned() {
                     identifier.is_lvalue = self.is_lvalue;
                     identifier.set_type(var_type.clone());
-                    Ok(var_type.clone())
+
+                    if !self.is_assigned {
+                        self.mark_variable_used(&identifier.name);
+                    }
+
+                    Ok(var_type)
                 } else {
                     Err(Self::build_error_message(
                         format!(
@@ -46,10 +52,30 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             Binary::Modulo(l, r) => self.are_expressions_compatible(l, r),
         };
 
-        if let Ok(t) = is_compatible {
-            Ok(t)
-        } else {
-            Err(is_compatible.unwrap_err())
+        match is_compatible {
+            // Enums can only be compared
+            Ok(ValueType::Enum(enum_name)) => Err(Self::build_error_message(
+                format!(
+                    "Arithmetic on enum '{}' values is not allowed, cast them to an integer type.",
+                    enum_name
+                )
+                .as_str(),
+                binary,
+            )),
+            Ok(ValueType::Union(union_name)) => Err(Self::build_error_message(
+                format!(
+                    "Arithmetic on union '{}' values is not allowed.",
+                    union_name
+                )
+                .as_str(),
+                binary,
+            )),
+            Ok(ValueType::Dyn(interface)) => Err(Self::build_error_message(
+                format!("Arithmetic on 'dyn {}' values is not allowed.", interface).as_str(),
+                binary,
+            )),
+            Ok(t) => Ok(t),
+            Err(msg) => Err(msg),
         }
     }
 
@@ -69,6 +95,24 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             BinaryLogic::NotEqual(l, r) => self.are_expressions_compatible(l, r),
         };
 
+        if let Ok(ValueType::Union(union_name)) = &is_compatible {
+            return Err(Self::build_error_message(
+                format!(
+                    "Union '{}' values can't be compared, match on them instead.",
+                    union_name
+                )
+                .as_str(),
+                binary,
+            ));
+        }
+
+        if let Ok(ValueType::Dyn(interface)) = &is_compatible {
+            return Err(Self::build_error_message(
+                format!("'dyn {}' values can't be compared.", interface).as_str(),
+                binary,
+            ));
+        }
+
         if let Ok(_) = is_compatible {
             Ok(ValueType::Bool)
         } else {
@@ -78,13 +122,18 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
 
     fn visit_unary(&mut self, unary: &mut Unary) -> TypeCheckerReturn {
         match unary {
-            Unary::Not(e) => self.visit_boxed_expr(e),
-            Unary::Negate(e) => self.visit_boxed_expr(e),
+            Unary::Not(_, e) => self.visit_boxed_expr(e),
+            Unary::Negate(_, e) => self.visit_boxed_expr(e),
         }
     }
 
     fn visit_call(&mut self, call_expr: &mut Call) -> TypeCheckerReturn {
-        if !self.function_table.contains_key(&call_expr.name) {
+        let generic_arg_count = self
+            .generic_functions
+            .get(&call_expr.name)
+            .map(|generic| generic.args.iter().flatten().count());
+
+        if generic_arg_count.is_none() && !self.function_table.contains_key(&call_expr.name) {
             return Err(Self::build_error_message(
                 format!(
                     "Function '{}' is not declared in this module.",
@@ -95,20 +144,16 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             ));
         }
 
+        if generic_arg_count.is_none() && !call_expr.type_args.is_empty() {
+            return Err(Self::build_error_message(
+                format!("Function '{}' is not generic.", &call_expr.name).as_str(),
+                call_expr,
+            ));
+        }
+
         if call_expr.args.is_some() {
-            let expected_arg_count = self
-                .function_table
-                .get(&call_expr.name)
-                .unwrap()
-                .args_type
-                .len();
-
-            let fn_name = self
-                .function_table
-                .get(&call_expr.name)
-                .unwrap()
-                .name
-                .to_string();
+            let expected_arg_count = generic_arg_count
+                .unwrap_or_else(|| self.function_table[&call_expr.name].args_type.len());
 
             let call_arg_count = call_expr.args.as_ref().unwrap().len();
 
@@ -116,29 +161,43 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
                 return Err(Self::build_error_message(
                     format!(
                         "Expected {} arguments for function '{}' call but got {} arguments.",
-                        expected_arg_count, fn_name, call_arg_count
+                        expected_arg_count, call_expr.name, call_arg_count
                     )
                     .as_str(),
                     call_expr,
                 ));
             }
+        }
 
-            for (i, arg_expr) in call_expr
-                .args
-                .as_deref_mut()
-                .unwrap()
-                .iter_mut()
-                .enumerate()
-            {
-                let expr_type = self.check_expr(arg_expr)?;
-                let fn_args = &self.function_table.get(&call_expr.name).unwrap().args_type;
-
-                if !ValueType::is_compatible(&expr_type, &fn_args[i]) {
-                    return Err(Self::build_error_message(format!(
-                        "Expression of type '{}' cannot be applied to function argument of type '{}' in the call to '{}'",
-                        expr_type, fn_args[i], fn_name
-                    ).as_str(), call_expr));
-                }
+        let arg_types = call_expr
+            .args
+            .iter_mut()
+            .flatten()
+            .map(|arg_expr| self.check_expr(arg_expr))
+            .collect::<Result<Vec<ValueType>, String>>()?;
+
+        // A generic function is called through its instance
+        if generic_arg_count.is_some() {
+            self.instantiate_call(call_expr, &arg_types)?;
+        }
+
+        let fn_name = self.function_table[&call_expr.name].name.to_string();
+        let fn_args = self.function_table[&call_expr.name].args_type.clone();
+
+        for ((arg_expr, expr_type), fn_arg) in call_expr
+            .args
+            .iter_mut()
+            .flatten()
+            .zip(arg_types)
+            .zip(fn_args.iter())
+        {
+            let expr_type = self.coerce_literal(arg_expr, expr_type, fn_arg)?;
+
+            if !ValueType::is_compatible(&expr_type, fn_arg) {
+                return Err(Self::build_error_message(format!(
+                    "Expression of type '{}' cannot be applied to function argument of type '{}' in the call to '{}'",
+                    expr_type, fn_arg, fn_name
+                ).as_str(), call_expr));
             }
         }
 
@@ -191,20 +250,38 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             ValueType::Real => ValueType::Pointer(Box::new(ValueType::Real)),
             ValueType::String => ValueType::Pointer(Box::new(ValueType::String)),
             ValueType::Char => ValueType::Pointer(Box::new(ValueType::Char)),
-            ValueType::Function => return Err(Self::build_error_message(
-                format!("Function pointers are not supported yet.").as_str(),
-                address_of,
-                )),
+            ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64
+            | ValueType::F32
+            | ValueType::Enum(_) => ValueType::Pointer(Box::new(identifier_ty)),
+            ValueType::Function => {
+                return Err(Self::build_error_message(
+                    format!("Function pointers are not supported yet.").as_str(),
+                    address_of,
+                ))
+            }
             ValueType::Pointer(ptr) => ValueType::Pointer(Box::new(ValueType::Pointer(ptr))),
             ValueType::Struct(strct) => ValueType::Pointer(Box::new(ValueType::Struct(strct))),
-            ValueType::Void => return Err(Self::build_error_message(
-                format!("Addrof cannot be applied to void types.").as_str(),
-                address_of,
-            )),
-            ValueType::Null => return Err(Self::build_error_message(
-                format!("Addrof 'null' is forbidden.").as_str(),
-                address_of,
-            )),
+            ValueType::Union(u) => ValueType::Pointer(Box::new(ValueType::Union(u))),
+            ValueType::Dyn(i) => ValueType::Pointer(Box::new(ValueType::Dyn(i))),
+            ValueType::Void => {
+                return Err(Self::build_error_message(
+                    format!("Addrof cannot be applied to void types.").as_str(),
+                    address_of,
+                ))
+            }
+            ValueType::Null => {
+                return Err(Self::build_error_message(
+                    format!("Addrof 'null' is forbidden.").as_str(),
+                    address_of,
+                ))
+            }
+            ValueType::Generic(..) => unreachable!("Generic types are instantiated"),
         };
 
         address_of.set_type(ty.clone());
@@ -249,11 +326,31 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
                 member_access.set_type(ValueType::Struct(s.to_string()));
                 self.structs_table.get(s).unwrap()
             }
+            ValueType::Union(union_name) => {
+                return Err(Self::build_error_message(
+                    format!(
+                        "The payload of union '{}' can only be accessed with a match pattern.",
+                        union_name
+                    )
+                    .as_str(),
+                    member_access,
+                ));
+            }
             ValueType::Pointer(ty) => match ty.as_ref() {
                 ValueType::Struct(s) => {
                     member_access.set_type(ValueType::Struct(s.to_string()));
                     self.structs_table.get(s).unwrap()
                 }
+                ValueType::Union(union_name) => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "The payload of union '{}' can only be accessed with a match pattern.",
+                            union_name
+                        )
+                        .as_str(),
+                        member_access,
+                    ));
+                }
                 _ => {
                     return Err(Self::build_error_message(
                         format!("Member access on a non-struct type").as_str(),
@@ -286,4 +383,180 @@ impl MutableExpressionVisitor<Result<ValueType, String>> for TypeChecker {
             ))
         }
     }
+
+    fn visit_conditional(&mut self, conditional: &mut Conditional) -> TypeCheckerReturn {
+        let condition_type = self.visit_boxed_expr(&mut conditional.condition)?;
+
+        if condition_type != ValueType::Bool {
+            return Err(Self::build_error_message(
+                format!(
+                    "Conditional expression has type '{}' but the type bool is needed.",
+                    condition_type
+                )
+                .as_str(),
+                conditional,
+            ));
+        }
+
+        let then_type = self.visit_boxed_expr(&mut conditional.then_expr)?;
+        let else_type = self.visit_boxed_expr(&mut conditional.else_expr)?;
+
+        if let Some(ty) = ValueType::unify(&then_type, &else_type) {
+            conditional.set_type(ty.clone());
+            Ok(ty)
+        } else {
+            Err(Self::build_error_message(
+                format!(
+                    "Conditional branches have incompatible types '{}' and '{}'.",
+                    then_type, else_type
+                )
+                .as_str(),
+                conditional,
+            ))
+        }
+    }
+
+    fn visit_array_literal(&mut self, array_literal: &mut ArrayLiteral) -> TypeCheckerReturn {
+        let mut element_type: Option<ValueType> = None;
+
+        for element in array_literal.elements.iter_mut() {
+            let ty = self.check_expr(element)?;
+            Self::check_array_element_type(&ty, element)?;
+
+            element_type = match element_type {
+                None => Some(ty),
+                Some(previous) => match ValueType::unify(&previous, &ty) {
+                    Some(unified) => Some(unified),
+                    None => {
+                        return Err(Self::build_error_message(
+                            format!(
+                                "Array literal elements have incompatible types '{}' and '{}'.",
+                                previous, ty
+                            )
+                            .as_str(),
+                            element,
+                        ))
+                    }
+                },
+            };
+        }
+
+        // The parser rejects empty array literals
+        let ty = ValueType::Array(StaticArray {
+            size: array_literal.elements.len(),
+            array_type: Box::new(element_type.unwrap()),
+        });
+
+        array_literal.set_type(ty.clone());
+        Ok(ty)
+    }
+
+    fn visit_array_repeat(&mut self, array_repeat: &mut ArrayRepeat) -> TypeCheckerReturn {
+        if array_repeat.count == 0 {
+            return Err(Self::build_error_message(
+                "Array literals can't be empty.",
+                array_repeat,
+            ));
+        }
+
+        let element_type = self.visit_boxed_expr(&mut array_repeat.value)?;
+        Self::check_array_element_type(&element_type, &array_repeat.value)?;
+
+        let ty = ValueType::Array(StaticArray {
+            size: array_repeat.count,
+            array_type: Box::new(element_type),
+        });
+
+        array_repeat.set_type(ty.clone());
+        Ok(ty)
+    }
+
+    fn visit_module_access(&mut self, module_access: &mut ModuleAccess) -> TypeCheckerReturn {
+        self.qualify_module_access(module_access)?;
+        self.visit_boxed_expr(&mut module_access.item)
+    }
+
+    fn visit_cast(&mut self, cast: &mut Cast) -> TypeCheckerReturn {
+        let from = self.visit_boxed_expr(&mut cast.expr)?;
+        cast.target = self.resolve_type(&cast.target, cast)?;
+
+        if let ValueType::Dyn(interface) = &cast.target {
+            if !self.is_dyn_convertible(&from, &cast.target) {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Invalid cast from '{}' to 'dyn {}', only a pointer to a struct implementing '{}' can be cast to it.",
+                        from.source_name(),
+                        interface,
+                        interface
+                    )
+                    .as_str(),
+                    cast,
+                ));
+            }
+
+            Ok(cast.get_type())
+        } else if ValueType::can_cast(&from, &cast.target) {
+            Ok(cast.get_type())
+        } else {
+            Err(Self::build_error_message(
+                format!("Invalid cast from '{}' to '{}'.", from, cast.target).as_str(),
+                cast,
+            ))
+        }
+    }
+
+    fn visit_enum_variant(&mut self, enum_variant: &mut EnumVariant) -> TypeCheckerReturn {
+        Ok(enum_variant.get_type())
+    }
+
+    fn visit_union_variant(&mut self, union_variant: &mut UnionVariant) -> TypeCheckerReturn {
+        let payload_types = self.unions_table[&union_variant.union_name].variants
+            [union_variant.tag as usize]
+            .1
+            .clone();
+
+        let (expected, given) = (payload_types.len(), union_variant.payload.len());
+        if given != expected {
+            return Err(Self::build_error_message(
+                format!(
+                    "Variant '{}::{}' holds {} value{} but {} {} given.",
+                    union_variant.union_name,
+                    union_variant.variant,
+                    expected,
+                    if expected > 1 { "s" } else { "" },
+                    given,
+                    if given > 1 { "were" } else { "was" }
+                )
+                .as_str(),
+                union_variant,
+            ));
+        }
+
+        for (i, (expr, ty)) in union_variant
+            .payload
+            .iter_mut()
+            .zip(&payload_types)
+            .enumerate()
+        {
+            let expr_type = self.check_expr(expr)?;
+            let expr_type = self.coerce_literal(expr, expr_type, ty)?;
+
+            if !ValueType::is_compatible_for_init(ty, &expr_type) {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Value {} of variant '{}::{}' has type '{}' but '{}' is required.",
+                        i + 1,
+                        union_variant.union_name,
+                        union_variant.variant,
+                        expr_type,
+                        ty
+                    )
+                    .as_str(),
+                    expr,
+                ));
+            }
+        }
+
+        Ok(union_variant.get_type())
+    }
 }
@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+
+use crate::{
+    debug::LineDebugInfo,
+    parser::visitors::{
+        Call, Expression, FunctionStatement, MutableStatementVisitor, Statement, StructLiteral,
+    },
+    type_system::value_type::ValueType,
+};
+
+use super::type_check::{TypeChecker, TypeCheckerReturn};
+
+/// Instances can require other instances, a generic function calling itself
+/// with other type arguments would require new ones forever
+const MAX_INSTANTIATION_DEPTH: u32 = 64;
+
+impl TypeChecker {
+    /// `name<type, ...>`, name of the instance of a generic function or struct
+    pub(super) fn instance_name(name: &str, type_args: &[ValueType]) -> String {
+        let type_args: Vec<String> = type_args.iter().map(|ty| ty.source_name()).collect();
+        format!("{}<{}>", name, type_args.join(", "))
+    }
+
+    pub(super) fn check_type_params<T: LineDebugInfo>(
+        kind: &str,
+        name: &str,
+        type_params: &[String],
+        element: &T,
+    ) -> Result<(), String> {
+        for (i, param) in type_params.iter().enumerate() {
+            if type_params[..i].contains(param) {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Type parameter '{}' is declared more than once in {} '{}'.",
+                        param, kind, name
+                    )
+                    .as_str(),
+                    element,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn check_type_arg_count<T: LineDebugInfo, U: LineDebugInfo>(
+        kind: &str,
+        name: &str,
+        type_params: &[String],
+        given: usize,
+        definition: &T,
+        element: &U,
+    ) -> Result<(), String> {
+        let expected = type_params.len();
+        if given == expected {
+            return Ok(());
+        }
+
+        Err(format!(
+            "{}\n{}",
+            Self::build_error_message(
+                format!(
+                    "{} '{}' has {} type parameter{} but {} type argument{} {} given.",
+                    kind,
+                    name,
+                    expected,
+                    if expected != 1 { "s" } else { "" },
+                    given,
+                    if given != 1 { "s" } else { "" },
+                    if given != 1 { "were" } else { "was" }
+                )
+                .as_str(),
+                element,
+            ),
+            Self::build_note_message(format!("'{}' is declared here.", name).as_str(), definition)
+        ))
+    }
+
+    /// Calls the instance of a generic function for the type arguments of the
+    /// call, they're inferred from the arguments when they're not given.
+    pub(super) fn instantiate_call(
+        &mut self,
+        call_expr: &mut Call,
+        arg_types: &[ValueType],
+    ) -> Result<(), String> {
+        let generic = self.generic_functions[&call_expr.name].clone();
+
+        let type_args = if call_expr.type_args.is_empty() {
+            let param_types: Vec<ValueType> = generic
+                .args
+                .iter()
+                .flatten()
+                .map(|arg| arg.1.clone())
+                .collect();
+
+            self.infer_type_args(
+                format!("function '{}'", generic.callee).as_str(),
+                &generic.type_params,
+                &param_types,
+                call_expr.args.as_deref().unwrap_or(&[]),
+                arg_types,
+                call_expr,
+            )?
+        } else {
+            Self::check_type_arg_count(
+                "Function",
+                &generic.callee,
+                &generic.type_params,
+                call_expr.type_args.len(),
+                &generic,
+                call_expr,
+            )?;
+
+            // Given in the scope of the caller
+            call_expr
+                .type_args
+                .iter()
+                .map(|type_arg| self.resolve_type(type_arg, call_expr))
+                .collect::<Result<Vec<ValueType>, String>>()?
+        };
+
+        call_expr.name = self.instantiate_function(&generic, type_args, call_expr)?;
+        call_expr.type_args.clear();
+
+        Ok(())
+    }
+
+    fn instantiate_function(
+        &mut self,
+        generic: &FunctionStatement,
+        type_args: Vec<ValueType>,
+        call_expr: &Call,
+    ) -> Result<String, String> {
+        self.check_type_bounds(generic, &type_args, call_expr)?;
+
+        let name = Self::instance_name(&generic.callee, &type_args);
+        if self.function_table.contains_key(&name) {
+            return Ok(name);
+        }
+
+        let mut instance = FunctionStatement {
+            callee: name.clone(),
+            mangled_name: generic
+                .mangled_name
+                .as_ref()
+                .map(|symbol| Self::instance_name(symbol, &type_args)),
+            // Each module has the instances it uses
+            is_exported: false,
+            type_params: Vec::new(),
+            type_bounds: Vec::new(),
+            ..generic.clone()
+        };
+
+        let type_arguments = generic.type_params.iter().cloned().zip(type_args).collect();
+        self.check_instance(type_arguments, &name, call_expr, |type_check| {
+            type_check.visit_function_statement(&mut instance)
+        })?;
+
+        self.instances.push(Statement::Function(instance));
+        Ok(name)
+    }
+
+    /// Name of the instance of the generic struct `name` for `type_args`, it's
+    /// checked the first time it's used.
+    pub(super) fn instantiate_struct<T: LineDebugInfo>(
+        &mut self,
+        name: &str,
+        type_args: &[ValueType],
+        element: &T,
+    ) -> Result<String, String> {
+        let generic = match self.generic_structs.get(name) {
+            Some(generic) => generic.clone(),
+            None if self.structs_table.contains_key(name)
+                || self.enums_table.contains_key(name)
+                || self.unions_table.contains_key(name) =>
+            {
+                return Err(Self::build_error_message(
+                    format!("Type '{}' is not generic.", name).as_str(),
+                    element,
+                ))
+            }
+            None => {
+                return Err(Self::build_error_message(
+                    format!("Undeclared generic struct '{}'.", name).as_str(),
+                    element,
+                ))
+            }
+        };
+
+        Self::check_type_arg_count(
+            "Struct",
+            name,
+            &generic.type_params,
+            type_args.len(),
+            &generic,
+            element,
+        )?;
+
+        let instance_name = Self::instance_name(name, type_args);
+        if self.struct_instances.contains_key(&instance_name) {
+            return Ok(instance_name);
+        }
+
+        // Known before its fields are checked, a field can point to the struct
+        self.struct_instances.insert(
+            instance_name.clone(),
+            (name.to_string(), type_args.to_vec()),
+        );
+
+        let mut instance = generic.clone();
+        instance.type_name = instance_name.clone();
+        instance.type_params = Vec::new();
+
+        let type_arguments = generic
+            .type_params
+            .iter()
+            .cloned()
+            .zip(type_args.iter().cloned())
+            .collect();
+        self.check_instance(type_arguments, &instance_name, element, |type_check| {
+            type_check.visit_struct_statement(&mut instance)
+        })?;
+
+        self.instances.push(Statement::Struct(instance));
+        Ok(instance_name)
+    }
+
+    /// Initialises the instance of a generic struct for the type arguments of
+    /// the literal, they're inferred from the fields when they're not given.
+    pub(super) fn instantiate_struct_literal(
+        &mut self,
+        struct_literal: &mut StructLiteral,
+        field_types: &[ValueType],
+    ) -> Result<(), String> {
+        let generic = self.generic_structs[&struct_literal.type_name].clone();
+
+        let type_args = if struct_literal.type_args.is_empty() {
+            let param_types: Vec<ValueType> =
+                generic.fields.iter().map(|field| field.1.clone()).collect();
+
+            self.infer_type_args(
+                format!("struct '{}'", generic.type_name).as_str(),
+                &generic.type_params,
+                &param_types,
+                &struct_literal.expressions,
+                field_types,
+                struct_literal,
+            )?
+        } else {
+            struct_literal
+                .type_args
+                .iter()
+                .map(|type_arg| self.resolve_type(type_arg, struct_literal))
+                .collect::<Result<Vec<ValueType>, String>>()?
+        };
+
+        struct_literal.type_name =
+            self.instantiate_struct(&generic.type_name, &type_args, struct_literal)?;
+        struct_literal.type_args.clear();
+
+        Ok(())
+    }
+
+    /// Checks an instance with its type parameters replaced by
+    /// `type_arguments`. It doesn't see the scopes of the function using it,
+    /// its errors point to the instantiation in the code being checked.
+    fn check_instance<T, F>(
+        &mut self,
+        type_arguments: HashMap<String, ValueType>,
+        name: &str,
+        element: &T,
+        check: F,
+    ) -> TypeCheckerReturn
+    where
+        T: LineDebugInfo,
+        F: FnOnce(&mut Self) -> TypeCheckerReturn,
+    {
+        if self.instantiation_depth == MAX_INSTANTIATION_DEPTH {
+            return Err(Self::build_error_message(
+                format!(
+                    "Instantiation of '{}' is nested too deeply, a generic item can't require itself with other type arguments.",
+                    name
+                )
+                .as_str(),
+                element,
+            ));
+        }
+
+        let scopes = self.variables_table.split_off(1);
+        let in_function = self.in_function.take();
+        let loop_count = std::mem::replace(&mut self.loop_count, 0);
+        let is_lvalue = std::mem::replace(&mut self.is_lvalue, false);
+        let type_arguments = std::mem::replace(&mut self.type_arguments, type_arguments);
+        self.instantiation_depth += 1;
+
+        let result = check(self);
+
+        self.instantiation_depth -= 1;
+        self.type_arguments = type_arguments;
+        self.is_lvalue = is_lvalue;
+        self.loop_count = loop_count;
+        self.in_function = in_function;
+        self.variables_table.truncate(1);
+        self.variables_table.extend(scopes);
+
+        // The instantiations nested in this one are in the generic items
+        if self.instantiation_depth > 0 {
+            return result;
+        }
+
+        result.map_err(|msg| {
+            format!(
+                "{}\n{}",
+                msg,
+                Self::build_note_message(
+                    format!("Required by the instantiation '{}' here.", name).as_str(),
+                    element
+                )
+            )
+        })
+    }
+
+    /// Infers the type arguments of a generic item from the values given to
+    /// its parameters. A number literal takes the type required by the other
+    /// values, it's only used for a type parameter no other value infers.
+    fn infer_type_args<T: LineDebugInfo>(
+        &self,
+        item: &str,
+        type_params: &[String],
+        param_types: &[ValueType],
+        values: &[Expression],
+        value_types: &[ValueType],
+        element: &T,
+    ) -> Result<Vec<ValueType>, String> {
+        let mut inferred: HashMap<String, ValueType> = HashMap::new();
+
+        for from_literals in [false, true] {
+            for ((param_type, value), value_type) in param_types.iter().zip(values).zip(value_types)
+            {
+                let is_literal =
+                    Self::integer_literal_value(value).is_some() || Self::is_real_literal(value);
+                if is_literal != from_literals {
+                    continue;
+                }
+
+                self.infer_from(
+                    param_type,
+                    value_type,
+                    type_params,
+                    &mut inferred,
+                    is_literal,
+                )
+                .map_err(|(param, previous, ty)| {
+                    Self::build_error_message(
+                        format!(
+                            "Type parameter '{}' of {} is inferred as both '{}' and '{}'.",
+                            param, item, previous, ty
+                        )
+                        .as_str(),
+                        value,
+                    )
+                })?;
+            }
+        }
+
+        type_params
+            .iter()
+            .map(|param| {
+                inferred.remove(param).ok_or_else(|| {
+                    Self::build_error_message(
+                        format!(
+                            "Type parameter '{}' of {} can't be inferred, its type arguments must be given.",
+                            param, item
+                        )
+                        .as_str(),
+                        element,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Matches the type of a parameter with the type of its value, the type
+    /// parameters found in it take the matching part of the value type. On
+    /// a conflict gives the type parameter and its two types.
+    fn infer_from(
+        &self,
+        param_type: &ValueType,
+        value_type: &ValueType,
+        type_params: &[String],
+        inferred: &mut HashMap<String, ValueType>,
+        keep_inferred: bool,
+    ) -> Result<(), (String, ValueType, ValueType)> {
+        match (param_type, value_type) {
+            // Tell nothing about the pointed type
+            (_, ValueType::Null) | (_, ValueType::Void) => Ok(()),
+            (ValueType::Struct(name), _) if type_params.contains(name) => {
+                match inferred.get(name) {
+                    None => {
+                        inferred.insert(name.clone(), value_type.clone());
+                        Ok(())
+                    }
+                    Some(previous) if previous == value_type || keep_inferred => Ok(()),
+                    Some(previous) => Err((name.clone(), previous.clone(), value_type.clone())),
+                }
+            }
+            (ValueType::Pointer(param), ValueType::Pointer(value)) => {
+                self.infer_from(param, value, type_params, inferred, keep_inferred)
+            }
+            (ValueType::Array(param), ValueType::Array(value)) => self.infer_from(
+                &param.array_type,
+                &value.array_type,
+                type_params,
+                inferred,
+                keep_inferred,
+            ),
+            (ValueType::Generic(name, param_args), ValueType::Struct(instance)) => {
+                match self.struct_instances.get(instance) {
+                    Some((generic, type_args)) if generic == name => {
+                        for (param, value) in param_args.iter().zip(type_args) {
+                            self.infer_from(param, value, type_params, inferred, keep_inferred)?;
+                        }
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
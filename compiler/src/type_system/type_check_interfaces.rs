@@ -0,0 +1,308 @@
+use crate::{
+    parser::visitors::{Call, FunctionStatement, ImplStatement, InterfaceStatement},
+    type_system::value_type::ValueType,
+};
+
+use super::type_check::{FunctionSignature, TypeChecker, TypeCheckerReturn};
+
+/// Type of the `self` receiver of the interface methods, it's replaced by the
+/// implementing struct
+const SELF_TYPE: &str = "Self";
+
+impl TypeChecker {
+    /// Checks the methods of an interface and declares them as
+    /// `Interface::method`, called with a `dyn Interface` receiver.
+    pub(super) fn declare_interface(
+        &mut self,
+        interface: &mut InterfaceStatement,
+    ) -> TypeCheckerReturn {
+        let type_name = interface.type_name.clone();
+        let self_receiver = ValueType::Pointer(Box::new(ValueType::Struct(SELF_TYPE.into())));
+
+        for i in 0..interface.methods.len() {
+            let method = &interface.methods[i];
+            let error = |msg: &str| {
+                Self::build_error_message(
+                    format!(
+                        "Method '{}' of interface '{}' {}",
+                        method.callee, type_name, msg
+                    )
+                    .as_str(),
+                    method,
+                )
+            };
+
+            if interface.methods[..i]
+                .iter()
+                .any(|other| other.callee == method.callee)
+            {
+                return Err(error("is declared more than once."));
+            }
+
+            if method.block.is_some() {
+                return Err(error("can't have a body, the structs implement it."));
+            }
+
+            if !method.type_params.is_empty() {
+                return Err(error("can't be generic."));
+            }
+
+            match method.args.as_deref() {
+                Some([(name, ty), ..]) if name == "self" && ty == &self_receiver => {}
+                _ => return Err(error("must take 'self: ptr Self' as first parameter.")),
+            }
+
+            let mut types = method.args.iter().flatten().skip(1).map(|arg| &arg.1);
+            if types.any(|ty| Self::names_type(ty, SELF_TYPE))
+                || Self::names_type(&method.return_type, SELF_TYPE)
+            {
+                return Err(error("can only use 'Self' as the type of 'self'."));
+            }
+
+            let arg_types = method
+                .args
+                .iter()
+                .flatten()
+                .skip(1)
+                .map(|(_, ty)| self.resolve_type(ty, method))
+                .collect::<Result<Vec<ValueType>, String>>()?;
+            let return_type = self.resolve_type(&method.return_type, method)?;
+
+            let method = &mut interface.methods[i];
+            for ((_, ty), resolved) in method.args.iter_mut().flatten().skip(1).zip(arg_types) {
+                *ty = resolved;
+            }
+            method.return_type = return_type;
+        }
+
+        let dyn_type = ValueType::Dyn(type_name.clone());
+        for method in &interface.methods {
+            // The receiver is the `dyn Interface` value, the IR generator calls
+            // the method of its vtable
+            let mut args = method.args.clone().unwrap_or_default();
+            args[0].1 = dyn_type.clone();
+
+            let name = format!("{}::{}", type_name, method.callee);
+            let dispatch = FunctionStatement {
+                callee: name.clone(),
+                args: Some(args),
+                ..method.clone()
+            };
+
+            self.type_table.add_function_type(&dispatch);
+            self.function_table.insert(
+                name.clone(),
+                FunctionSignature {
+                    name: name.clone(),
+                    return_type: dispatch.return_type.clone(),
+                    args_type: dispatch
+                        .args
+                        .iter()
+                        .flatten()
+                        .map(|a| a.1.clone())
+                        .collect(),
+                },
+            );
+            self.methods.insert(name, dyn_type.clone());
+        }
+
+        self.interfaces_table
+            .insert(type_name.clone(), interface.clone());
+        self.type_table.add_interface_type(interface);
+
+        Ok(dyn_type)
+    }
+
+    /// `impl Interface for Type` has exactly the methods of the interface,
+    /// with `Self` replaced by the type. They're sorted in the order of the
+    /// interface, it's the order of the vtable.
+    pub(super) fn check_implementation(
+        &mut self,
+        impl_stmt: &mut ImplStatement,
+        interface_name: &str,
+    ) -> Result<(), String> {
+        let interface = match self.interfaces_table.get(interface_name) {
+            Some(interface) => interface.clone(),
+            None => {
+                return Err(Self::build_error_message(
+                    format!("'{}' is not a declared interface.", interface_name).as_str(),
+                    impl_stmt,
+                ))
+            }
+        };
+
+        let type_name = impl_stmt.type_name.clone();
+        let implementation = (interface_name.to_string(), type_name.clone());
+        if self.implementations.contains(&implementation) {
+            return Err(Self::build_error_message(
+                format!(
+                    "Interface '{}' is implemented more than once for struct '{}'.",
+                    interface_name, type_name
+                )
+                .as_str(),
+                impl_stmt,
+            ));
+        }
+
+        let struct_type = ValueType::Struct(type_name.clone());
+        for method in &impl_stmt.methods {
+            let required = match interface.methods.iter().find(|m| m.callee == method.callee) {
+                Some(required) => required,
+                None => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Method '{}' is not a method of interface '{}'.",
+                            method.callee, interface_name
+                        )
+                        .as_str(),
+                        method,
+                    ))
+                }
+            };
+
+            if !method.type_params.is_empty() {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Method '{}' implementing interface '{}' can't be generic.",
+                        method.callee, interface_name
+                    )
+                    .as_str(),
+                    method,
+                ));
+            }
+
+            let mut required_types: Vec<ValueType> = required
+                .args
+                .iter()
+                .flatten()
+                .map(|a| a.1.clone())
+                .collect();
+            required_types[0] = ValueType::Pointer(Box::new(struct_type.clone()));
+            required_types.push(required.return_type.clone());
+
+            let mut types = method
+                .args
+                .iter()
+                .flatten()
+                .map(|(_, ty)| self.resolve_type(ty, method))
+                .collect::<Result<Vec<ValueType>, String>>()?;
+            types.push(self.resolve_type(&method.return_type, method)?);
+
+            if types != required_types {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Method '{}' has signature '{}' but interface '{}' requires '{}'.",
+                        method.callee,
+                        Self::signature_name(&types),
+                        interface_name,
+                        Self::signature_name(&required_types)
+                    )
+                    .as_str(),
+                    method,
+                ));
+            }
+        }
+
+        let missing: Vec<String> = interface
+            .methods
+            .iter()
+            .filter(|m| !impl_stmt.methods.iter().any(|i| i.callee == m.callee))
+            .map(|m| format!("'{}'", m.callee))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Self::build_error_message(
+                format!(
+                    "Struct '{}' doesn't implement interface '{}', missing method{} {}.",
+                    type_name,
+                    interface_name,
+                    if missing.len() > 1 { "s" } else { "" },
+                    missing.join(", ")
+                )
+                .as_str(),
+                impl_stmt,
+            ));
+        }
+
+        impl_stmt.methods.sort_by_key(|method| {
+            interface
+                .methods
+                .iter()
+                .position(|m| m.callee == method.callee)
+        });
+
+        self.implementations.insert(implementation);
+        Ok(())
+    }
+
+    /// The interfaces bounding the type parameters of a generic function must
+    /// be declared
+    pub(super) fn check_bound_interfaces(
+        &self,
+        function: &FunctionStatement,
+    ) -> Result<(), String> {
+        for (_, interface) in &function.type_bounds {
+            if !self.interfaces_table.contains_key(interface) {
+                return Err(Self::build_error_message(
+                    format!("'{}' is not a declared interface.", interface).as_str(),
+                    function,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The type arguments of a generic function implement the interfaces
+    /// bounding their type parameter, the instance calls the methods of the
+    /// structs directly.
+    pub(super) fn check_type_bounds(
+        &self,
+        generic: &FunctionStatement,
+        type_args: &[ValueType],
+        call_expr: &Call,
+    ) -> Result<(), String> {
+        for (param, interface) in &generic.type_bounds {
+            let type_arg = generic
+                .type_params
+                .iter()
+                .position(|p| p == param)
+                .and_then(|i| type_args.get(i));
+
+            let implemented = match type_arg {
+                Some(ValueType::Struct(name)) => self
+                    .implementations
+                    .contains(&(interface.clone(), name.clone())),
+                _ => false,
+            };
+
+            if !implemented {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Type '{}' doesn't implement interface '{}' required by type parameter '{}' of function '{}'.",
+                        type_arg.map(|ty| ty.source_name()).unwrap_or_default(),
+                        interface,
+                        param,
+                        generic.callee
+                    )
+                    .as_str(),
+                    call_expr,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `fn(type, ...): type` of the parameter types followed by the return type
+    fn signature_name(types: &[ValueType]) -> String {
+        let (return_type, args) = types.split_last().unwrap();
+        format!(
+            "fn({}): {}",
+            args.iter()
+                .map(|ty| ty.source_name())
+                .collect::<Vec<String>>()
+                .join(", "),
+            return_type.source_name()
+        )
+    }
+}
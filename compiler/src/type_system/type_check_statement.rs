@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 
-use crate::parser::visitors::{
-    BlockStatement, BreakStatement, Expression, ForStatement, FunctionStatement, IfStatement,
-    ImportStatement, MutableExpressionVisitor, MutableStatementVisitor, ReturnStatement, Statement,
-    StructStatement, VariableAssignment, VariableDeclaration, WhileStatement,
+use crate::{
+    lint::{Lint, Warning},
+    parser::visitors::{
+        BlockStatement, BreakStatement, ContinueStatement, EnumStatement, Expression, ForStatement,
+        FunctionStatement, IfStatement, ImplStatement, ImportStatement, InterfaceStatement,
+        MatchStatement, MutableExpressionVisitor, MutableStatementVisitor, Pattern,
+        ReturnStatement, StructStatement, UnionStatement, VariableAssignment, VariableDeclaration,
+        WhileStatement,
+    },
 };
 
 use super::{
-    type_check::{FunctionSignature, TypeChecker, TypeCheckerReturn},
+    type_check::{FunctionSignature, TypeChecker, TypeCheckerReturn, Variable},
     value_type::ValueType,
 };
 
@@ -17,39 +22,181 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
     }
 
     fn visit_declaration_statement(&mut self, expr: &mut VariableDeclaration) -> TypeCheckerReturn {
-        let init_type = self.check_expr(&mut expr.init_expr)?;
+        // Only the module scope is opened outside functions and blocks
+        let is_global = self.variables_table.len() == 1;
 
-        if !ValueType::is_compatible_for_init(&expr.variable_type, &init_type) {
+        if expr.is_const && !is_global {
             return Err(Self::build_error_message(
                 format!(
-                    "variable '{}' is declared as '{}' but init expression has type '{}'",
-                    expr.identifier, expr.variable_type, init_type
+                    "Constant '{}' must be declared at module level.",
+                    expr.identifier
                 )
                 .as_str(),
                 expr,
             ));
         }
 
-        if let Some(_) = self.find_variable_type(&expr.identifier) {
+        if expr.is_exported && expr.variable_type.is_none() {
             return Err(Self::build_error_message(
-                format!("Redifinition of variable '{}'.", expr.identifier).as_str(),
+                format!(
+                    "Exported global '{}' must have a type annotation.",
+                    expr.identifier
+                )
+                .as_str(),
                 expr,
             ));
         }
 
-        self.variables_table
-            .last_mut()
+        if let Some(ty) = &expr.variable_type {
+            expr.variable_type = Some(self.resolve_type(ty, expr)?);
+        }
+
+        let init_type = match &mut expr.init_expr {
+            Some(init_expr) => {
+                // Checked first, enum variants are only known once checked
+                let init_type = self.check_expr(init_expr)?;
+
+                if is_global && !Self::is_constant_expression(init_expr) {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Global variable '{}' must be initialised with a constant expression.",
+                            expr.identifier
+                        )
+                        .as_str(),
+                        expr,
+                    ));
+                }
+
+                match &expr.variable_type {
+                    Some(ty) => self.coerce_literal(init_expr, init_type, ty)?,
+                    None => init_type,
+                }
+            }
+            // Declared in another module, or a local assigned later
+            None => match &expr.variable_type {
+                Some(ty) => ty.clone(),
+                None => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Exported global '{}' must have a type annotation.",
+                            expr.identifier
+                        )
+                        .as_str(),
+                        expr,
+                    ))
+                }
+            },
+        };
+
+        let variable_type = match &expr.variable_type {
+            Some(ty) => {
+                if let (ValueType::Array(declared), ValueType::Array(init)) = (ty, &init_type) {
+                    if declared.size != init.size {
+                        return Err(Self::build_error_message(
+                            format!(
+                                "Array '{}' has {} elements but its init expression has {}.",
+                                expr.identifier, declared.size, init.size
+                            )
+                            .as_str(),
+                            expr,
+                        ));
+                    }
+                }
+
+                if !ValueType::is_compatible_for_init(ty, &init_type) {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "variable '{}' is declared as '{}' but init expression has type '{}'",
+                            expr.identifier, ty, init_type
+                        )
+                        .as_str(),
+                        expr,
+                    ));
+                }
+
+                ty.clone()
+            }
+            None => match &init_type {
+                ValueType::Null | ValueType::Void | ValueType::Function => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Can't infer the type of '{}' from an init expression of type '{}', a type annotation is required.",
+                            expr.identifier, init_type
+                        )
+                        .as_str(),
+                        expr,
+                    ))
+                }
+                ValueType::Array(a) if matches!(*a.array_type, ValueType::Null) => {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Can't infer the element type of array '{}', a type annotation is required.",
+                            expr.identifier
+                        )
+                        .as_str(),
+                        expr,
+                    ))
+                }
+                _ => init_type.clone(),
+            },
+        };
+
+        if self
+            .variables_table
+            .last()
             .unwrap()
-            .insert(expr.identifier.clone(), expr.variable_type.clone());
+            .contains_key(&expr.identifier)
+        {
+            return Err(Self::build_error_message(
+                format!("Redifinition of variable '{}'.", expr.identifier).as_str(),
+                expr,
+            ));
+        }
 
-        self.type_table
-            .add_variable(&expr.identifier, &expr.variable_type);
+        // The module scope holds the functions too, they aren't variables
+        if self.find_variable_type(&expr.identifier).is_some()
+            && !self.function_table.contains_key(&expr.identifier)
+        {
+            self.add_warning(
+                Lint::Shadowing,
+                format!(
+                    "'{}' shadows a variable of an enclosing scope.",
+                    expr.identifier
+                )
+                .as_str(),
+                expr,
+            );
+        }
+
+        // Globals can be used by the other modules
+        let unused_warning = if is_global || expr.identifier.starts_with('_') {
+            None
+        } else {
+            Some(Warning::new(
+                Lint::UnusedVariables,
+                format!("Unused variable '{}'.", expr.identifier).as_str(),
+                expr,
+            ))
+        };
+        self.add_variable(
+            &expr.identifier,
+            &variable_type,
+            expr.is_const,
+            unused_warning,
+        );
+        expr.variable_type = Some(variable_type);
 
         Ok(init_type)
     }
 
     fn visit_assignment_statement(&mut self, expr: &mut VariableAssignment) -> TypeCheckerReturn {
-        match &mut expr.identifier {
+        // `module::global = value` assigns the qualified global
+        if let Expression::ModuleAccess(module_access) = &mut expr.identifier {
+            self.qualify_module_access(module_access)?;
+            expr.identifier = (*module_access.item).clone();
+        }
+
+        let assigned_type = match &mut expr.identifier {
             Expression::ArrayAccess(access) => {
                 access.is_lvalue = true;
                 self.check_array_element_assignment(access, &mut expr.new_value)
@@ -58,6 +205,7 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
                 deref.is_lvalue = true;
                 let deref_ty = self.visit_dereference_expression(deref)?;
                 let init_ty = self.check_expr(&mut expr.new_value)?;
+                let init_ty = self.coerce_literal(&mut expr.new_value, init_ty, &deref_ty)?;
 
                 if !ValueType::is_compatible(&deref_ty, &init_ty) {
                     return Err(Self::build_error_message(
@@ -72,10 +220,21 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
 
                 Ok(deref_ty)
             }
+            Expression::MemberAccess(member_access) if member_access.args.is_some() => {
+                Err(Self::build_error_message(
+                    format!(
+                        "Cannot assign to the result of method call '{}'.",
+                        member_access.member
+                    )
+                    .as_str(),
+                    expr,
+                ))
+            }
             Expression::MemberAccess(member_access) => {
                 self.is_lvalue = true;
                 let member_ty = self.visit_member_access(member_access)?;
                 let init_ty = self.check_expr(&mut expr.new_value)?;
+                let init_ty = self.coerce_literal(&mut expr.new_value, init_ty, &member_ty)?;
                 self.is_lvalue = false;
 
                 if !ValueType::is_compatible(&member_ty, &init_ty) {
@@ -99,7 +258,23 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
 
                 result
             }
+        }?;
+
+        // Constants are resolved like the other variables, a parameter or a
+        // local can hide them
+        if let Some(identifier) = Self::assigned_variable(&expr.identifier) {
+            if self
+                .get_variable(&identifier.name)
+                .map_or(false, |variable| variable.is_const)
+            {
+                return Err(Self::build_error_message(
+                    format!("Cannot assign to constant '{}'.", identifier.name).as_str(),
+                    expr,
+                ));
+            }
         }
+
+        Ok(assigned_type)
     }
 
     fn visit_function_statement(&mut self, expr: &mut FunctionStatement) -> TypeCheckerReturn {
@@ -110,10 +285,36 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
             ));
         }
 
-        self.variables_table
-            .first_mut()
-            .unwrap()
-            .insert(expr.callee.to_string(), expr.return_type.clone());
+        if !expr.type_params.is_empty() {
+            Self::check_type_params("function", &expr.callee, &expr.type_params, expr)?;
+            self.check_bound_interfaces(expr)?;
+
+            // The body is checked for each instance, see `instantiate_call`
+            self.generic_functions
+                .insert(expr.callee.to_string(), expr.clone());
+            return Ok(expr.return_type.clone());
+        }
+
+        let arg_types = expr
+            .args
+            .iter()
+            .flatten()
+            .map(|(_, arg_type)| self.resolve_type(arg_type, expr))
+            .collect::<Result<Vec<ValueType>, String>>()?;
+        for ((_, arg_type), resolved) in expr.args.iter_mut().flatten().zip(arg_types) {
+            *arg_type = resolved;
+        }
+        expr.return_type = self.resolve_type(&expr.return_type, expr)?;
+
+        self.variables_table.first_mut().unwrap().insert(
+            expr.callee.to_string(),
+            Variable {
+                value_type: expr.return_type.clone(),
+                is_used: false,
+                unused_warning: None,
+                is_const: false,
+            },
+        );
 
         self.type_table.add_function_type(expr);
 
@@ -139,8 +340,21 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
             },
         );
 
-        if expr.args.is_some() {
-            self.add_variables_in_scope(&expr.args.as_ref().unwrap());
+        if let Some(args) = &expr.args {
+            let has_body = expr.block.is_some();
+            self.add_variables_in_scope(args, expr, |name| {
+                if !has_body || name == "self" || name.starts_with('_') {
+                    return None;
+                }
+
+                // Reported at the function, the same for all the instances
+                // of a generic one
+                Some(Warning::new(
+                    Lint::UnusedVariables,
+                    format!("Unused parameter '{}'.", name).as_str(),
+                    expr,
+                ))
+            });
         }
 
         self.in_function = Some(expr.return_type.clone());
@@ -149,43 +363,22 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
             self.visit_block_statement(b)?;
 
             if expr.args.is_some() {
-                self.variables_table.pop(); // remove arguments scope if any
-            }
-
-            // TODO:
-            // for now we only check if the function body has a return statement if
-            // the return type is not 'void'. In the future it may be better to have
-            // a smarter way to check that every path in the function leads to a
-            // valid return statement
-            if expr.return_type != ValueType::Void {
-                for stmt in &b.statements {
-                    match stmt {
-                        Statement::Return(_) => {
-                            self.in_function = None;
-                            return Ok(expr.return_type.clone());
-                        }
-                        _ => continue,
-                    }
-                }
-
-                self.in_function = None;
-                return Err(Self::build_error_message(
-                    format!("Function '{}' returns no values", expr.callee).as_str(),
-                    expr,
-                ));
+                self.pop_scope(); // remove arguments scope if any
             }
-        } else {
-            self.in_function = None;
         }
 
         self.in_function = None;
+
+        self.check_control_flow(expr)?;
+        self.check_initialisation(expr);
+
         Ok(expr.return_type.clone())
     }
 
     fn visit_block_statement(&mut self, expr: &mut BlockStatement) -> TypeCheckerReturn {
         self.variables_table.push(HashMap::new());
         self.check_ast_type(&mut expr.statements)?;
-        self.variables_table.pop().unwrap();
+        self.pop_scope();
 
         // A block has no return type
         Ok(ValueType::Void)
@@ -201,6 +394,7 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
 
         let expr_type = self.check_expr(&mut return_stmt.expr)?;
         let return_type = self.in_function.as_ref().unwrap();
+        let expr_type = self.coerce_literal(&mut return_stmt.expr, expr_type, return_type)?;
 
         if !ValueType::is_compatible(&expr_type, &return_type) {
             return Err(Self::build_error_message(
@@ -275,13 +469,13 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
 
         // Pop the for's variable scope here it's not needed and can lead to
         // false positive variable redefinitions errors
-        self.variables_table.pop();
+        self.pop_scope();
         self.loop_count -= 1;
 
-        if init_type != ValueType::Number && init_type != ValueType::Real {
+        if !init_type.is_numeric() {
             return Err(Self::build_error_message(
                 format!(
-                    "For init declaration has type '{}' but type 'number' or 'real' is required.",
+                    "For init declaration has type '{}' but a numeric type is required.",
                     init_type
                 )
                 .as_str(),
@@ -303,6 +497,153 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
         Ok(ValueType::Void)
     }
 
+    fn visit_match_statement(&mut self, match_stmt: &mut MatchStatement) -> TypeCheckerReturn {
+        let scrutinee_type = self.check_expr(&mut match_stmt.scrutinee)?;
+
+        // (name, value) of the variants of a matched enum or union, the values
+        // between their discriminants can't be matched
+        let mut variants: Option<Vec<(String, i128)>> = None;
+
+        // Every value the matched expression can take
+        let domain = match &scrutinee_type {
+            ty if ty.is_integer() => ty.integer_range().unwrap(),
+            // Chars are lowered to bytes compared as unsigned codes
+            ValueType::Char => ValueType::U8.integer_range().unwrap(),
+            ValueType::Bool => (0, 1),
+            ValueType::Enum(enum_name) => {
+                let enum_variants: Vec<(String, i128)> = self.enums_table[enum_name]
+                    .variants
+                    .iter()
+                    .map(|(name, value, _)| (name.clone(), *value as i128))
+                    .collect();
+                let values = enum_variants.iter().map(|(_, value)| *value);
+                let domain = (values.clone().min().unwrap(), values.max().unwrap());
+
+                variants = Some(enum_variants);
+                domain
+            }
+            // The tags of the variants are their indices
+            ValueType::Union(union_name) => {
+                let union_variants: Vec<(String, i128)> = self.unions_table[union_name]
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(|(tag, (name, _, _))| (name.clone(), tag as i128))
+                    .collect();
+                let domain = (0, union_variants.len() as i128 - 1);
+
+                variants = Some(union_variants);
+                domain
+            }
+            _ => {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Match expression has type '{}' but an integer type, 'char', 'bool', an enum or a union is required.",
+                        scrutinee_type
+                    )
+                    .as_str(),
+                    &match_stmt.scrutinee,
+                ))
+            }
+        };
+
+        let is_covered = |covered: &[(i128, i128)], (low, high): (i128, i128)| match &variants {
+            Some(variants) => variants
+                .iter()
+                .filter(|(_, value)| low <= *value && *value <= high)
+                .all(|(_, value)| Self::is_range_covered(covered, (*value, *value))),
+            None => Self::is_range_covered(covered, (low, high)),
+        };
+
+        // Values matched by the previous arms
+        let mut covered: Vec<(i128, i128)> = Vec::new();
+
+        for arm in &mut match_stmt.arms {
+            let mut unreachable_patterns = Vec::new();
+
+            // Variables bound to the payload of a union variant
+            let mut bindings = Vec::new();
+            let pattern_count = arm.patterns.len();
+
+            for pattern in arm.patterns.iter_mut() {
+                if let Pattern::EnumVariant(enum_pattern) = pattern {
+                    bindings = self.resolve_variant_pattern(enum_pattern)?;
+
+                    if !enum_pattern.bindings.is_empty() && pattern_count > 1 {
+                        return Err(Self::build_error_message(
+                            format!(
+                                "Pattern '{}' binds its payload, it can't be combined with other patterns.",
+                                Pattern::EnumVariant(enum_pattern.clone())
+                            )
+                            .as_str(),
+                            &*enum_pattern,
+                        ));
+                    }
+                }
+            }
+
+            for pattern in &arm.patterns {
+                let range = self.pattern_range(pattern, &scrutinee_type, domain)?;
+
+                if is_covered(&covered, range) {
+                    unreachable_patterns.push(pattern);
+                }
+
+                covered.push(range);
+            }
+
+            if unreachable_patterns.len() == arm.patterns.len() {
+                self.add_warning(Lint::UnreachablePatterns, "Unreachable match arm.", &*arm);
+            } else {
+                for pattern in unreachable_patterns {
+                    self.add_warning(
+                        Lint::UnreachablePatterns,
+                        format!("Unreachable pattern '{}'.", pattern).as_str(),
+                        pattern,
+                    );
+                }
+            }
+
+            // The payload bindings can be left unused
+            self.add_variables_in_scope(&bindings, &*arm, |_| None);
+            let result = self.visit_block_statement(&mut arm.block);
+            self.pop_scope();
+            result?;
+        }
+
+        if let Some(variants) = &variants {
+            let missing: Vec<String> = variants
+                .iter()
+                .filter(|(_, value)| !Self::is_range_covered(&covered, (*value, *value)))
+                .map(|(name, _)| format!("'{}'", name))
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Match on type '{}' is not exhaustive, missing variant{} {}.",
+                        scrutinee_type,
+                        if missing.len() > 1 { "s" } else { "" },
+                        missing.join(", ")
+                    )
+                    .as_str(),
+                    match_stmt,
+                ));
+            }
+        } else if !Self::is_range_covered(&covered, domain) {
+            return Err(Self::build_error_message(
+                format!(
+                    "Match on type '{}' is not exhaustive, a '_' arm is required.",
+                    scrutinee_type
+                )
+                .as_str(),
+                match_stmt,
+            ));
+        }
+
+        Ok(ValueType::Void)
+    }
+
     fn visit_break_statement(&mut self, break_stmt: &mut BreakStatement) -> TypeCheckerReturn {
         if self.loop_count == 0 {
             return Err(Self::build_error_message(
@@ -314,21 +655,286 @@ impl MutableStatementVisitor<TypeCheckerReturn> for TypeChecker {
         Ok(ValueType::Void)
     }
 
-    fn visit_struct_statement(&mut self, stct: &StructStatement) -> TypeCheckerReturn {
-        if self.structs_table.contains_key(&stct.type_name) {
+    fn visit_continue_statement(
+        &mut self,
+        continue_stmt: &mut ContinueStatement,
+    ) -> TypeCheckerReturn {
+        if self.loop_count == 0 {
+            return Err(Self::build_error_message(
+                "Continue statement outside a loop.",
+                continue_stmt,
+            ));
+        }
+
+        Ok(ValueType::Void)
+    }
+
+    fn visit_struct_statement(&mut self, stct: &mut StructStatement) -> TypeCheckerReturn {
+        if self.structs_table.contains_key(&stct.type_name)
+            || self.generic_structs.contains_key(&stct.type_name)
+            || self.enums_table.contains_key(&stct.type_name)
+            || self.unions_table.contains_key(&stct.type_name)
+            || self.interfaces_table.contains_key(&stct.type_name)
+        {
             return Err(Self::build_error_message(
                 format!("Redefinition of struct '{}'", &stct.type_name).as_str(),
                 stct,
             ));
         }
 
+        if !stct.type_params.is_empty() {
+            Self::check_type_params("struct", &stct.type_name, &stct.type_params, stct)?;
+
+            // The fields are checked for each instance, see `instantiate_struct`
+            self.generic_structs
+                .insert(stct.type_name.clone(), stct.clone());
+            return Ok(ValueType::Struct(stct.type_name.clone()));
+        }
+
+        for i in 0..stct.fields.len() {
+            stct.fields[i].1 = self.resolve_type(&stct.fields[i].1, stct)?;
+        }
+
+        // Default values are copied in the literals, keep them typed
+        let mut checked_stct = stct.clone();
+        for (field_name, field_type, _, default_value) in checked_stct.fields.iter_mut() {
+            if let Some(default_value) = default_value {
+                let default_type = self.check_expr(default_value)?;
+
+                if !Self::is_constant_expression(default_value) {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Default value of field '{}' must be a constant expression.",
+                            field_name
+                        )
+                        .as_str(),
+                        default_value,
+                    ));
+                }
+                let default_type = self.coerce_literal(default_value, default_type, field_type)?;
+
+                if !ValueType::is_compatible_for_init(field_type, &default_type) {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Default value of field '{}' has type '{}' but the field has type '{}'.",
+                            field_name, default_type, field_type
+                        )
+                        .as_str(),
+                        default_value,
+                    ));
+                }
+            }
+        }
+
         self.structs_table
-            .insert(stct.type_name.clone(), stct.clone());
+            .insert(stct.type_name.clone(), checked_stct);
         self.type_table.add_struct_type(stct);
 
         Ok(ValueType::Struct(stct.type_name.clone()))
     }
 
+    fn visit_enum_statement(&mut self, enm: &mut EnumStatement) -> TypeCheckerReturn {
+        if self.enums_table.contains_key(&enm.type_name)
+            || self.structs_table.contains_key(&enm.type_name)
+            || self.unions_table.contains_key(&enm.type_name)
+            || self.interfaces_table.contains_key(&enm.type_name)
+        {
+            return Err(Self::build_error_message(
+                format!("Redefinition of enum '{}'.", &enm.type_name).as_str(),
+                enm,
+            ));
+        }
+
+        if enm.variants.is_empty() {
+            return Err(Self::build_error_message(
+                format!("Enum '{}' has no variants.", &enm.type_name).as_str(),
+                enm,
+            ));
+        }
+
+        for (i, (name, value, _)) in enm.variants.iter().enumerate() {
+            for (previous_name, previous_value, _) in &enm.variants[..i] {
+                if previous_name == name {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Variant '{}' is declared more than once in enum '{}'.",
+                            name, enm.type_name
+                        )
+                        .as_str(),
+                        enm,
+                    ));
+                }
+
+                if previous_value == value {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "Variants '{}' and '{}' of enum '{}' have the same value {}.",
+                            previous_name, name, enm.type_name, value
+                        )
+                        .as_str(),
+                        enm,
+                    ));
+                }
+            }
+        }
+
+        self.enums_table.insert(enm.type_name.clone(), enm.clone());
+
+        Ok(ValueType::Enum(enm.type_name.clone()))
+    }
+
+    fn visit_union_statement(&mut self, union_stmt: &mut UnionStatement) -> TypeCheckerReturn {
+        if self.unions_table.contains_key(&union_stmt.type_name)
+            || self.enums_table.contains_key(&union_stmt.type_name)
+            || self.structs_table.contains_key(&union_stmt.type_name)
+            || self.interfaces_table.contains_key(&union_stmt.type_name)
+        {
+            return Err(Self::build_error_message(
+                format!("Redefinition of union '{}'.", &union_stmt.type_name).as_str(),
+                union_stmt,
+            ));
+        }
+
+        if union_stmt.variants.is_empty() {
+            return Err(Self::build_error_message(
+                format!("Union '{}' has no variants.", &union_stmt.type_name).as_str(),
+                union_stmt,
+            ));
+        }
+
+        for (i, (name, _, _)) in union_stmt.variants.iter().enumerate() {
+            if union_stmt.variants[..i]
+                .iter()
+                .any(|(previous_name, _, _)| previous_name == name)
+            {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Variant '{}' is declared more than once in union '{}'.",
+                        name, union_stmt.type_name
+                    )
+                    .as_str(),
+                    union_stmt,
+                ));
+            }
+        }
+
+        // The payload types are resolved by `resolve_union_payloads` once
+        // every union is declared
+        self.unions_table
+            .insert(union_stmt.type_name.clone(), union_stmt.clone());
+
+        Ok(ValueType::Union(union_stmt.type_name.clone()))
+    }
+
+    fn visit_impl_statement(&mut self, impl_stmt: &mut ImplStatement) -> TypeCheckerReturn {
+        let type_name = impl_stmt.type_name.clone();
+
+        if self.generic_structs.contains_key(&type_name) {
+            return Err(Self::build_error_message(
+                format!(
+                    "Methods can't be declared for generic struct '{}'.",
+                    type_name
+                )
+                .as_str(),
+                impl_stmt,
+            ));
+        }
+
+        if !self.structs_table.contains_key(&type_name) {
+            return Err(Self::build_error_message(
+                format!(
+                    "Methods can only be declared for a struct, '{}' is not a declared struct.",
+                    type_name
+                )
+                .as_str(),
+                impl_stmt,
+            ));
+        }
+
+        if let Some(interface) = impl_stmt.interface.clone() {
+            self.check_implementation(impl_stmt, &interface)?;
+        }
+
+        let struct_type = ValueType::Struct(type_name.clone());
+        for method in impl_stmt.methods.iter_mut() {
+            let name = format!("{}::{}", type_name, method.callee);
+
+            if self.function_table.contains_key(&name) || self.generic_functions.contains_key(&name)
+            {
+                return Err(Self::build_error_message(
+                    format!(
+                        "Method '{}' is declared more than once for struct '{}'.",
+                        method.callee, type_name
+                    )
+                    .as_str(),
+                    method,
+                ));
+            }
+
+            // `self` is the receiver of `value.method(...)`, by value or by address
+            for (i, (arg_name, arg_type)) in method.args.iter().flatten().enumerate() {
+                if arg_name != "self" {
+                    continue;
+                }
+
+                if i > 0 {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "'self' must be the first parameter of method '{}'.",
+                            method.callee
+                        )
+                        .as_str(),
+                        method,
+                    ));
+                }
+
+                let receiver_type = self.resolve_type(arg_type, method)?;
+                if receiver_type != struct_type
+                    && receiver_type != ValueType::Pointer(Box::new(struct_type.clone()))
+                {
+                    return Err(Self::build_error_message(
+                        format!(
+                            "'self' of method '{}' has type '{}' but '{}' or 'ptr {}' is required.",
+                            method.callee,
+                            receiver_type.source_name(),
+                            type_name,
+                            type_name
+                        )
+                        .as_str(),
+                        method,
+                    ));
+                }
+
+                self.methods.insert(name.clone(), receiver_type);
+            }
+
+            method.callee = name;
+            self.visit_function_statement(method)?;
+        }
+
+        Ok(struct_type)
+    }
+
+    fn visit_interface_statement(
+        &mut self,
+        interface: &mut InterfaceStatement,
+    ) -> TypeCheckerReturn {
+        let type_name = &interface.type_name;
+        if self.structs_table.contains_key(type_name)
+            || self.generic_structs.contains_key(type_name)
+            || self.enums_table.contains_key(type_name)
+            || self.unions_table.contains_key(type_name)
+            || self.interfaces_table.contains_key(type_name)
+        {
+            return Err(Self::build_error_message(
+                format!("Redefinition of interface '{}'", type_name).as_str(),
+                interface,
+            ));
+        }
+
+        self.declare_interface(interface)
+    }
+
     fn visit_import_statement(&mut self, _import_stmt: &mut ImportStatement) -> TypeCheckerReturn {
         unreachable!("Import statememts presents in type check stage!");
     }
@@ -1,8 +1,9 @@
 use crate::parser::{
     literals::{Bool, Char, Number, Real, StringLiteral},
     visitors::{
-        AddressOf, ArrayAccess, Binary, BinaryLogic, Call, DeReference, Expression, Group,
-        Identifier, Literal, MemberAccess, StructLiteral, Unary,
+        AddressOf, ArrayAccess, ArrayLiteral, ArrayRepeat, Binary, BinaryLogic, Call, Cast,
+        Conditional, DeReference, EnumVariant, Expression, Group, Identifier, Literal,
+        MemberAccess, ModuleAccess, StructLiteral, Unary, UnionVariant,
     },
 };
 
@@ -149,8 +150,8 @@ impl Typed for BinaryLogic {
 impl Typed for Unary {
     fn get_type(&self) -> ValueType {
         match self {
-            Unary::Not(e) => e.get_type(),
-            Unary::Negate(e) => e.get_type(),
+            Unary::Not(_, e) => e.get_type(),
+            Unary::Negate(_, e) => e.get_type(),
         }
     }
 
@@ -209,18 +210,76 @@ impl Typed for MemberAccess {
     }
 }
 
-// TODO: Namespace support
-// impl Typed for ModuleAccess {
-//     // In a module type access we usually want to return the type of the expression's right hand side
-//     // since they're supposed to be struct types or functions
-//     fn get_type(&self) -> ValueType {
-//         self.right.get_type()
-//     }
-//
-//     fn set_type(&mut self, _new_type: ValueType) {
-//         unreachable!()
-//     }
-// }
+impl Typed for Conditional {
+    fn get_type(&self) -> ValueType {
+        self.ty.as_ref().unwrap().clone()
+    }
+
+    fn set_type(&mut self, new_type: ValueType) {
+        self.ty = Some(new_type);
+    }
+}
+
+impl Typed for ArrayLiteral {
+    fn get_type(&self) -> ValueType {
+        self.ty.as_ref().unwrap().clone()
+    }
+
+    fn set_type(&mut self, new_type: ValueType) {
+        self.ty = Some(new_type);
+    }
+}
+
+impl Typed for ArrayRepeat {
+    fn get_type(&self) -> ValueType {
+        self.ty.as_ref().unwrap().clone()
+    }
+
+    fn set_type(&mut self, new_type: ValueType) {
+        self.ty = Some(new_type);
+    }
+}
+
+impl Typed for ModuleAccess {
+    // The type of a module access is the type of the accessed item
+    fn get_type(&self) -> ValueType {
+        self.item.get_type()
+    }
+
+    fn set_type(&mut self, _new_type: ValueType) {
+        unreachable!()
+    }
+}
+
+impl Typed for Cast {
+    fn get_type(&self) -> ValueType {
+        self.target.clone()
+    }
+
+    fn set_type(&mut self, new_type: ValueType) {
+        self.target = new_type;
+    }
+}
+
+impl Typed for EnumVariant {
+    fn get_type(&self) -> ValueType {
+        ValueType::Enum(self.enum_name.clone())
+    }
+
+    fn set_type(&mut self, _new_type: ValueType) {
+        unreachable!()
+    }
+}
+
+impl Typed for UnionVariant {
+    fn get_type(&self) -> ValueType {
+        ValueType::Union(self.union_name.clone())
+    }
+
+    fn set_type(&mut self, _new_type: ValueType) {
+        unreachable!()
+    }
+}
 
 impl Typed for Expression {
     fn get_type(&self) -> ValueType {
@@ -235,7 +294,13 @@ impl Typed for Expression {
             Expression::AddressOf(ao) => ao.get_type(),
             Expression::DeReference(dr) => dr.get_type(),
             Expression::MemberAccess(ma) => ma.get_type(),
-            // Expression::ModuleAccess(ma) => ma.get_type(), // TODO Namespace support
+            Expression::Conditional(c) => c.get_type(),
+            Expression::ArrayLiteral(a) => a.get_type(),
+            Expression::ArrayRepeat(a) => a.get_type(),
+            Expression::ModuleAccess(ma) => ma.get_type(),
+            Expression::Cast(c) => c.get_type(),
+            Expression::EnumVariant(e) => e.get_type(),
+            Expression::UnionVariant(u) => u.get_type(),
             Expression::Null(_) => ValueType::Null,
         }
     }
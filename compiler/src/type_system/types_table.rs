@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::parser::visitors::{FunctionStatement, StructStatement};
+use crate::parser::visitors::{
+    FunctionStatement, InterfaceStatement, StructStatement, UnionStatement,
+};
 
 use super::value_type::ValueType;
 
@@ -8,7 +10,9 @@ use super::value_type::ValueType;
 pub struct TypeTable {
     types: HashMap<String, ValueType>,
     struct_type: HashMap<String, StructStatement>,
+    union_type: HashMap<String, UnionStatement>,
     function_type: HashMap<String, FunctionStatement>,
+    interface_type: HashMap<String, InterfaceStatement>,
 }
 
 impl TypeTable {
@@ -16,7 +20,9 @@ impl TypeTable {
         TypeTable {
             types: HashMap::new(),
             struct_type: HashMap::new(),
+            union_type: HashMap::new(),
             function_type: HashMap::new(),
+            interface_type: HashMap::new(),
         }
     }
 
@@ -31,6 +37,13 @@ impl TypeTable {
         );
     }
 
+    pub fn add_union_type(&mut self, union_statement: &UnionStatement) {
+        self.union_type.insert(
+            union_statement.type_name.to_string(),
+            union_statement.clone(),
+        );
+    }
+
     pub fn add_function_type(&mut self, function_statement: &FunctionStatement) {
         self.function_type.insert(
             function_statement.callee.clone(),
@@ -38,6 +51,11 @@ impl TypeTable {
         );
     }
 
+    pub fn add_interface_type(&mut self, interface: &InterfaceStatement) {
+        self.interface_type
+            .insert(interface.type_name.to_string(), interface.clone());
+    }
+
     pub fn find_variable_type(&self, name: &str) -> Option<ValueType> {
         match self.types.get(name.into()) {
             Some(vt) => Some(vt.clone()),
@@ -52,6 +70,14 @@ impl TypeTable {
         }
     }
 
+    pub fn find_union_type(&self, type_name: &str) -> Option<UnionStatement> {
+        self.union_type.get(type_name).cloned()
+    }
+
+    pub fn find_interface_type(&self, type_name: &str) -> Option<InterfaceStatement> {
+        self.interface_type.get(type_name).cloned()
+    }
+
     pub fn find_function_type(&self, function_name: &str) -> Option<FunctionStatement> {
         match self.function_type.get(function_name) {
             Some(f) => Some(f.clone()),
@@ -13,13 +13,33 @@ pub struct StaticArray {
 #[derive(Clone)]
 pub enum ValueType {
     Array(StaticArray),
+    // `number` and `real` are `i64` and `f64`
     Number,
     Real,
+    I8,
+    I16,
+    I32,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
     Bool,
     String,
     Function,
     Pointer(Box<ValueType>),
     Struct(String),
+    // Enums are `i64` values
+    Enum(String),
+    // Tagged unions are an `i64` tag followed by a payload sized to their
+    // largest variant
+    Union(String),
+    // `dyn Interface`, a pointer to a struct implementing the interface and
+    // the vtable of the implementation
+    Dyn(String),
+    // `Name<type, ...>`, the type checker replaces it with the struct
+    // instantiated from the generic struct `Name`
+    Generic(String, Vec<ValueType>),
     Char,
     Void,
     Null,
@@ -30,6 +50,14 @@ impl PartialEq for ValueType {
         match (self, other) {
             (ValueType::Number, ValueType::Number) => true,
             (ValueType::Real, ValueType::Real) => true,
+            (ValueType::I8, ValueType::I8) => true,
+            (ValueType::I16, ValueType::I16) => true,
+            (ValueType::I32, ValueType::I32) => true,
+            (ValueType::U8, ValueType::U8) => true,
+            (ValueType::U16, ValueType::U16) => true,
+            (ValueType::U32, ValueType::U32) => true,
+            (ValueType::U64, ValueType::U64) => true,
+            (ValueType::F32, ValueType::F32) => true,
             (ValueType::Bool, ValueType::Bool) => true,
             (ValueType::Char, ValueType::Char) => true,
             (ValueType::String, ValueType::String) => true,
@@ -54,6 +82,12 @@ impl PartialEq for ValueType {
             (ValueType::Pointer(_), ValueType::Null) => true,
             (ValueType::Null, ValueType::Pointer(_)) => true,
             (ValueType::Struct(lhs), ValueType::Struct(rhs)) => lhs == rhs,
+            (ValueType::Enum(lhs), ValueType::Enum(rhs)) => lhs == rhs,
+            (ValueType::Union(lhs), ValueType::Union(rhs)) => lhs == rhs,
+            (ValueType::Dyn(lhs), ValueType::Dyn(rhs)) => lhs == rhs,
+            (ValueType::Generic(lhs, lhs_args), ValueType::Generic(rhs, rhs_args)) => {
+                lhs == rhs && lhs_args == rhs_args
+            }
             _ => false,
         }
     }
@@ -64,6 +98,14 @@ impl Display for ValueType {
         match self {
             ValueType::Number => f.write_str("Number"),
             ValueType::Real => f.write_str("Real"),
+            ValueType::I8 => f.write_str("i8"),
+            ValueType::I16 => f.write_str("i16"),
+            ValueType::I32 => f.write_str("i32"),
+            ValueType::U8 => f.write_str("u8"),
+            ValueType::U16 => f.write_str("u16"),
+            ValueType::U32 => f.write_str("u32"),
+            ValueType::U64 => f.write_str("u64"),
+            ValueType::F32 => f.write_str("f32"),
             ValueType::Bool => f.write_str("Bool"),
             ValueType::String => f.write_str("String"),
             ValueType::Function => f.write_str("Function"),
@@ -78,6 +120,18 @@ impl Display for ValueType {
             }
             ValueType::Pointer(ptr) => f.write_fmt(format_args!("Pointer of {}", ptr)),
             ValueType::Struct(struct_stmt) => f.write_fmt(format_args!("Struct {}", struct_stmt)),
+            ValueType::Enum(enum_name) => f.write_fmt(format_args!("Enum {}", enum_name)),
+            ValueType::Union(union_name) => f.write_fmt(format_args!("Union {}", union_name)),
+            ValueType::Dyn(interface) => f.write_fmt(format_args!("dyn {}", interface)),
+            ValueType::Generic(name, type_args) => f.write_fmt(format_args!(
+                "{}<{}>",
+                name,
+                type_args
+                    .iter()
+                    .map(|ty| ty.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
         }
     }
 }
@@ -96,12 +150,140 @@ impl ValueType {
 
     pub fn is_compatible_for_init(ltype: &ValueType, rtype: &ValueType) -> bool {
         match (ltype, rtype) {
+            (ValueType::Array(_), ValueType::Array(_)) => ltype == rtype,
             (ValueType::Array(lhs), rhs) => lhs.array_type.deref() == rhs,
             (lhs, ValueType::Array(rhs)) => lhs == rhs.array_type.deref(),
             _ => rtype == ltype,
         }
     }
 
+    /// Common type of two values, used for both branches of a conditional.
+    /// The most precise one wins when a value is null or a void pointer.
+    pub fn unify(ltype: &ValueType, rtype: &ValueType) -> Option<ValueType> {
+        match (ltype, rtype) {
+            (ValueType::Null, ValueType::Null) => Some(ValueType::Null),
+            (ValueType::Null, _) if rtype == ltype => Some(rtype.clone()),
+            (ValueType::Pointer(ptr_ty), ValueType::Pointer(_))
+                if ptr_ty.deref() == &ValueType::Void =>
+            {
+                Some(rtype.clone())
+            }
+            _ if ltype == rtype => Some(ltype.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match self {
+            ValueType::Number
+            | ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::U8
+            | ValueType::U16
+            | ValueType::U32
+            | ValueType::U64 => true,
+            _ => false,
+        }
+    }
+
+    /// Signed integers and enums are divided, compared and extended as signed
+    /// values, every other integer (chars and bools included) as unsigned ones.
+    pub fn is_signed(&self) -> bool {
+        match self {
+            ValueType::Number
+            | ValueType::I8
+            | ValueType::I16
+            | ValueType::I32
+            | ValueType::Enum(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        match self {
+            ValueType::Real | ValueType::F32 => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// Inclusive range of the values of an integer type
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        match self {
+            ValueType::Number => Some((i64::MIN as i128, i64::MAX as i128)),
+            ValueType::I8 => Some((i8::MIN as i128, i8::MAX as i128)),
+            ValueType::I16 => Some((i16::MIN as i128, i16::MAX as i128)),
+            ValueType::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+            ValueType::U8 => Some((0, u8::MAX as i128)),
+            ValueType::U16 => Some((0, u16::MAX as i128)),
+            ValueType::U32 => Some((0, u32::MAX as i128)),
+            ValueType::U64 => Some((0, u64::MAX as i128)),
+            _ => None,
+        }
+    }
+
+    /// Explicit `as` conversions: between numeric types, chars, bools and
+    /// enums to and from integers and between pointers. `null` can be cast to
+    /// any pointer.
+    pub fn can_cast(from: &ValueType, to: &ValueType) -> bool {
+        match (from, to) {
+            _ if from.is_numeric() && to.is_numeric() => true,
+            (ValueType::Char | ValueType::Bool | ValueType::Enum(_), to) if to.is_integer() => true,
+            (from, ValueType::Char | ValueType::Bool | ValueType::Enum(_)) if from.is_integer() => {
+                true
+            }
+            (ValueType::Char, ValueType::Char) | (ValueType::Bool, ValueType::Bool) => true,
+            (ValueType::Enum(lhs), ValueType::Enum(rhs)) => lhs == rhs,
+            (
+                ValueType::Pointer(_) | ValueType::String | ValueType::Null,
+                ValueType::Pointer(_) | ValueType::String,
+            ) => true,
+            _ => false,
+        }
+    }
+
+    /// The type as written in the source, the instances of generic functions
+    /// and structs are named after their type arguments `Pair<number, ptr real>`.
+    pub fn source_name(&self) -> String {
+        match self {
+            ValueType::Number => "number".to_string(),
+            ValueType::Real => "real".to_string(),
+            ValueType::I8 => "i8".to_string(),
+            ValueType::I16 => "i16".to_string(),
+            ValueType::I32 => "i32".to_string(),
+            ValueType::U8 => "u8".to_string(),
+            ValueType::U16 => "u16".to_string(),
+            ValueType::U32 => "u32".to_string(),
+            ValueType::U64 => "u64".to_string(),
+            ValueType::F32 => "f32".to_string(),
+            ValueType::Bool => "bool".to_string(),
+            ValueType::Char => "char".to_string(),
+            ValueType::String => "string".to_string(),
+            ValueType::Void => "void".to_string(),
+            ValueType::Null => "null".to_string(),
+            ValueType::Function => "fn".to_string(),
+            ValueType::Pointer(ptr) => format!("ptr {}", ptr.source_name()),
+            ValueType::Array(a) => format!("[{}; {}]", a.array_type.source_name(), a.size),
+            ValueType::Struct(name) | ValueType::Enum(name) | ValueType::Union(name) => {
+                name.clone()
+            }
+            ValueType::Dyn(interface) => format!("dyn {}", interface),
+            ValueType::Generic(name, type_args) => format!(
+                "{}<{}>",
+                name,
+                type_args
+                    .iter()
+                    .map(|ty| ty.source_name())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
     pub fn into_struct_type(&self) -> String {
         match self {
             ValueType::Struct(s) => s.to_string(),
@@ -117,6 +299,16 @@ impl FromStr for ValueType {
         match s {
             "number" => Ok(ValueType::Number),
             "real" => Ok(ValueType::Real),
+            "i8" => Ok(ValueType::I8),
+            "i16" => Ok(ValueType::I16),
+            "i32" => Ok(ValueType::I32),
+            "i64" => Ok(ValueType::Number),
+            "u8" => Ok(ValueType::U8),
+            "u16" => Ok(ValueType::U16),
+            "u32" => Ok(ValueType::U32),
+            "u64" => Ok(ValueType::U64),
+            "f32" => Ok(ValueType::F32),
+            "f64" => Ok(ValueType::Real),
             "bool" => Ok(ValueType::Bool),
             "char" => Ok(ValueType::Char),
             "string" => Ok(ValueType::String),
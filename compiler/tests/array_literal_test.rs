@@ -0,0 +1,128 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Statement},
+    },
+};
+
+mod common;
+
+use common::check;
+
+#[test]
+fn parse_array_literals() {
+    let source = "let a: [number; 3] = [1, 2, 3,]; let b: [number; 8] = [0; 8];";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::VariableDeclaration(v) => match v.init_expr.as_ref().unwrap() {
+            Expression::ArrayLiteral(a) => assert_eq!(a.elements.len(), 3),
+            _ => panic!("Expected an array literal"),
+        },
+        _ => panic!("Expected a variable declaration"),
+    }
+
+    match &ast[1] {
+        Statement::VariableDeclaration(v) => match v.init_expr.as_ref().unwrap() {
+            Expression::ArrayRepeat(a) => assert_eq!(a.count, 8),
+            _ => panic!("Expected an array repeat literal"),
+        },
+        _ => panic!("Expected a variable declaration"),
+    }
+}
+
+#[test]
+fn parse_invalid_array_literals() {
+    for source in [
+        "let a: [number; 1] = [];",
+        "let a: [number; 2] = [1, 2;",
+        "let a: [number; 2] = [1; n];",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn array_literal_type_check() {
+    let source = "fn f(): void { \
+                    let a: [number; 3] = [1, 2, 3]; \
+                    let b: [real; 2] = [0.5; 2]; \
+                    let c = ['a', 'b']; \
+                    a = [4, 5, 6]; \
+                    c[0] == 'a'; \
+                  }";
+
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn array_literal_length_mismatch() {
+    let source = "fn f(): void { let a: [number; 3] = [1, 2]; }";
+    let error = check(source).err().unwrap();
+
+    assert!(error.contains("has 3 elements but its init expression has 2"));
+}
+
+#[test]
+fn array_literal_element_mismatch() {
+    assert!(check("fn f(): void { let a = [1, 2.5]; }").is_err());
+    assert!(check("fn f(): void { let a: [real; 2] = [1, 2]; }").is_err());
+}
+
+#[test]
+fn nested_array_literal() {
+    let source = "fn f(): number { \
+                    let m: [[number; 2]; 3] = [[1, 2], [3, 4], [0; 2]]; \
+                    m[1][0] = 5; \
+                    return m[2][1]; \
+                  }";
+
+    assert!(check(source).is_ok());
+    assert!(check("fn f(): void { let m = [[1, 2], [3]]; }").is_err());
+}
+
+#[test]
+fn struct_array_literal() {
+    let source = "struct Point { x: number; y: number; } \
+                  fn f(): number { \
+                    let points = [struct Point { 1, 2 }, struct Point { 3, 4 }]; \
+                    let origins: [Point; 4] = [struct Point { 0, 0 }; 4]; \
+                    let p: Point = points[1]; \
+                    let q: Point = origins[3]; \
+                    let x = p.x; \
+                    let y = q.y; \
+                    return x + y; \
+                  }";
+
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn array_literal_argument() {
+    let source =
+        "fn sum(values: [number; 3]): number { return values[0] + values[1] + values[2]; } \
+                  fn f(): number { return sum([1, 2, 3]); }";
+
+    assert!(check(source).is_ok());
+    assert!(check(&source.replace("[1, 2, 3]", "[1, 2]")).is_err());
+}
+
+#[test]
+fn array_literal_pointers() {
+    let source = "fn f(): void { let n: number = 1; let ptrs = [addrof n, null]; }";
+
+    assert!(check(source).is_ok());
+    assert!(check("fn f(): void { let ptrs = [null, null]; }").is_err());
+}
+
+#[test]
+fn global_array_literal() {
+    assert!(check("const PRIMES: [number; 4] = [2, 3, 5, 7];").is_ok());
+    assert!(check("let n: number = 1; let a: [number; 2] = [n, 2];").is_err());
+}
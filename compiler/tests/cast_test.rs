@@ -0,0 +1,104 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Statement},
+    },
+    type_system::value_type::ValueType,
+};
+
+mod common;
+
+use common::check;
+
+#[test]
+fn numeric_casts() {
+    let source = "fn f(n: number, r: real): void { \
+                  let a: real = n as real; let b: number = r as number; \
+                  let c: number = n as number; let d: real = r as real; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn char_and_bool_casts() {
+    let source = "fn f(c: char, b: bool): void { \
+                  let n: number = c as number; let m: number = b as number; \
+                  let c2: char = n as char; let b2: bool = m as bool; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn pointer_casts() {
+    let source = "fn malloc(size: number): ptr void; fn f(): void { \
+                  let p = malloc(8) as ptr number; let s = malloc(8) as string; \
+                  let v = s as ptr char; let n = null as ptr real; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn cast_precedence() {
+    // `as` binds tighter than the binary operators
+    let source = "fn f(n: number): real { return 1.5 * n as real + 2.0; }";
+    let ast = check(source).unwrap();
+
+    match &ast[0] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[0] {
+            Statement::Return(r) => assert!(matches!(r.expr, Expression::Binary(_))),
+            _ => panic!("Expected a return statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn cast_type_is_the_target_type() {
+    let source = "fn f(n: number): void { let r = n as real; let c = (n as char) as number; }";
+    let ast = check(source).unwrap();
+
+    match &ast[0] {
+        Statement::Function(f) => {
+            let types: Vec<ValueType> = f
+                .block
+                .as_ref()
+                .unwrap()
+                .statements
+                .iter()
+                .filter_map(|stmt| match stmt {
+                    Statement::VariableDeclaration(v) => v.variable_type.clone(),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(types, vec![ValueType::Real, ValueType::Number]);
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn invalid_casts() {
+    let sources = [
+        "fn f(s: string): void { let n = s as number; }",
+        "fn f(n: number): void { let p = n as ptr number; }",
+        "fn f(r: real): void { let c = r as char; }",
+        "fn f(c: char): void { let b = c as bool; }",
+        "struct S { x: number; } fn f(s: S): void { let n = s as number; }",
+        "fn f(n: number): void { let a: [number; 2] = [1, 2]; let b = a as number; }",
+    ];
+
+    for source in sources {
+        let err = check(source).err().unwrap();
+        assert!(err.contains("Invalid cast from"), "{}", err);
+    }
+}
+
+#[test]
+fn cast_in_global_initializer() {
+    assert!(check("const HALF: real = 1 as real / 2.0;").is_ok());
+}
+
+#[test]
+fn missing_cast_type() {
+    let tokens = Token::lex_string("fn f(n: number): void { let r = n as; }");
+    let mut parser = Parser::new(tokens, "", "");
+    assert!(parser.parse().is_err());
+}
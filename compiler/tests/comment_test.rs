@@ -0,0 +1,88 @@
+use compiler::{
+    lexer::Token,
+    parser::{parser::Parser, visitors::Statement},
+    type_system::type_check::TypeChecker,
+};
+
+#[test]
+fn block_comments_are_skipped() {
+    let source = "/* header /* nested */ */ fn dummy(): number { /* body */ return 42; }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn unclosed_block_comment() {
+    let source = "fn dummy(): void {} /* unclosed";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn function_doc_comment() {
+    let source =
+        "/// Returns the answer\n/// to everything.\nexport fn answer(): number { return 42; }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    let ast = parser.parse().expect("Parser failed!");
+    match &ast[0] {
+        Statement::Function(f) => {
+            assert_eq!(f.doc.as_deref(), Some("Returns the answer\nto everything."))
+        }
+        _ => assert!(false, "Expected a function statement"),
+    }
+}
+
+#[test]
+fn struct_and_field_doc_comments() {
+    let source = "/// A user\nstruct User {\n/// Its name\nname: string;\nage: number; }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    let ast = parser.parse().expect("Parser failed!");
+    match &ast[0] {
+        Statement::Struct(s) => {
+            assert_eq!(s.doc.as_deref(), Some("A user"));
+            assert_eq!(s.fields[0].2.as_deref(), Some("Its name"));
+            assert_eq!(s.fields[1].2, None);
+        }
+        _ => assert!(false, "Expected a struct statement"),
+    }
+}
+
+#[test]
+fn undocumented_function() {
+    let source = "// Not a doc comment\nfn dummy(): void {}";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    let ast = parser.parse().expect("Parser failed!");
+    match &ast[0] {
+        Statement::Function(f) => assert!(f.doc.is_none()),
+        _ => assert!(false, "Expected a function statement"),
+    }
+}
+
+#[test]
+fn doc_comment_in_block_is_ignored() {
+    let source = "fn dummy(): void { /// Nothing to document\n let a: number = 0; }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
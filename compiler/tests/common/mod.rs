@@ -0,0 +1,40 @@
+//! Helpers shared by the integration tests, a test file includes them with
+//! `mod common;`.
+#![allow(dead_code)]
+
+use compiler::{
+    lexer::Token, lint::Warning, parser::parser::Parser, parser::visitors::Statement,
+    type_system::type_check::TypeChecker,
+};
+
+/// Parses and type checks `source`, returns the checked AST
+pub fn check(source: &str) -> Result<Vec<Statement>, String> {
+    check_with_warnings(source).0
+}
+
+/// Same as `check`, with the messages of the warnings
+pub fn check_with_warnings(source: &str) -> (Result<Vec<Statement>, String>, Vec<String>) {
+    let (result, warnings) = type_check(source);
+    (result, messages(&warnings))
+}
+
+/// Warnings of `source`, it must type check
+pub fn check_warnings(source: &str) -> Vec<Warning> {
+    let (result, warnings) = type_check(source);
+    result.expect("Type check failed!");
+    warnings
+}
+
+pub fn messages(warnings: &[Warning]) -> Vec<String> {
+    warnings.iter().map(|w| w.to_string()).collect()
+}
+
+fn type_check(source: &str) -> (Result<Vec<Statement>, String>, Vec<Warning>) {
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let mut ast = parser.parse().expect("Parser failed!");
+
+    let mut type_check = TypeChecker::new();
+    let result = type_check.check_ast_type(&mut ast).map(|_| ast);
+    (result, type_check.get_warnings().clone())
+}
@@ -0,0 +1,207 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Pattern, Statement},
+    },
+    type_system::{type_check::TypeChecker, value_type::ValueType},
+};
+
+mod common;
+
+use common::{check, check_with_warnings};
+
+#[test]
+fn parse_enum_discriminants() {
+    let source = "enum Color { Red, Green = 5, Blue, Black = -1, }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::Enum(e) => {
+            let variants: Vec<(&str, i64)> = e
+                .variants
+                .iter()
+                .map(|(name, value, _)| (name.as_str(), *value))
+                .collect();
+            assert_eq!(
+                variants,
+                vec![("Red", 0), ("Green", 5), ("Blue", 6), ("Black", -1)]
+            );
+        }
+        _ => panic!("Expected an enum statement"),
+    }
+}
+
+#[test]
+fn parse_enum_errors() {
+    for source in [
+        "enum Color { Red Green }",
+        "enum Color { Red = Green }",
+        "enum { Red }",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn enum_variants_and_comparison() {
+    let source = "enum Color { Red, Green, Blue }
+                  fn f(c: Color): bool { let d: Color = Color::Red; let e = Color::Blue; \
+                  return c == d or d != e; }";
+    let ast = check(source).unwrap();
+
+    match &ast[1] {
+        Statement::Function(f) => {
+            assert_eq!(
+                f.args.as_ref().unwrap()[0].1,
+                ValueType::Enum("Color".into())
+            );
+
+            match &f.block.as_ref().unwrap().statements[1] {
+                Statement::VariableDeclaration(v) => {
+                    assert_eq!(v.variable_type, Some(ValueType::Enum("Color".into())));
+                    match &v.init_expr {
+                        Some(Expression::EnumVariant(variant)) => assert_eq!(variant.value, 2),
+                        _ => panic!("Expected an enum variant"),
+                    }
+                }
+                _ => panic!("Expected a variable declaration"),
+            }
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn enums_are_distinct_types() {
+    let source = "enum Color { Red } enum Shape { Circle }
+                  fn f(): bool { return Color::Red == Shape::Circle; }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(): void { let c: Color = 0; }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(): void { let n: number = Color::Red; }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(): void { let c = Color::Red + Color::Red; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Arithmetic on enum 'Color' values is not allowed"));
+}
+
+#[test]
+fn enum_casts() {
+    let source = "enum Color { Red, Green } \
+                  fn f(n: i32): void { let c = n as Color; let m = Color::Green as number; \
+                  let b = Color::Red as u8; }";
+    assert!(check(source).is_ok());
+
+    let source = "enum Color { Red } fn f(): void { let r = Color::Red as real; }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn enum_declaration_errors() {
+    let source = "enum Color { Red, Green, Red }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variant 'Red' is declared more than once in enum 'Color'."));
+
+    let source = "enum Color { Red = 1, Green = 0, Blue }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variants 'Red' and 'Blue' of enum 'Color' have the same value 1."));
+
+    let source = "enum Color { Red } enum Color { Blue }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color {}";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(): void { let c = Color::Yellow; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Enum 'Color' has no variant 'Yellow'."));
+}
+
+#[test]
+fn enum_named_like_an_interface() {
+    let source = "interface Color { fn f(self: ptr Self): void; } enum Color { Red }";
+    assert!(check(source).is_err());
+
+    // The interface is already declared when the enum is checked
+    let mut type_check = TypeChecker::new();
+    let mut check_next = |source: &str| {
+        let mut parser = Parser::new(Token::lex_string(source), "", "");
+        let mut ast = parser.parse().expect("Parser failed!");
+        type_check.check_ast_type(&mut ast)
+    };
+
+    assert!(check_next("interface Color { fn f(self: ptr Self): void; }").is_ok());
+    let err = check_next("enum Color { Red }").err().unwrap();
+    assert!(err.contains("Redefinition of enum 'Color'."));
+}
+
+#[test]
+fn enum_used_before_declaration() {
+    let source = "struct Pixel { color: Color = Color::Blue; }
+                  fn f(p: Pixel): Color { return p.color; }
+                  let DEFAULT: Color = Color::Red;
+                  enum Color { Red, Blue }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn match_enum() {
+    let source = "enum Color { Red, Green, Blue }
+                  fn f(c: Color): void { match c { Color::Red | Color::Green => {} Color::Blue => {} } }";
+    let (result, warnings) = check_with_warnings(source);
+    let ast = result.unwrap();
+    assert!(warnings.is_empty());
+
+    match &ast[1] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[0] {
+            Statement::MatchStatement(m) => match &m.arms[1].patterns[0] {
+                Pattern::EnumVariant(p) => assert_eq!(p.value, Some(2)),
+                _ => panic!("Expected an enum pattern"),
+            },
+            _ => panic!("Expected a match statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn match_enum_exhaustiveness() {
+    let source = "enum Color { Red, Green = 10, Blue }
+                  fn f(c: Color): void { match c { Color::Green => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("is not exhaustive, missing variants 'Red', 'Blue'."));
+
+    let source = "enum Color { Red, Green = 10, Blue }
+                  fn f(c: Color): void { match c { Color::Green => {} _ => {} } }";
+    assert!(check(source).is_ok());
+
+    // The values between the discriminants don't have to be matched
+    let source = "enum Color { Red, Green = 10 }
+                  fn f(c: Color): void { match c { Color::Red => {} Color::Green => {} _ => {} } }";
+    let (result, warnings) = check_with_warnings(source);
+    assert!(result.is_ok());
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].ends_with("Warning: Unreachable match arm."));
+}
+
+#[test]
+fn match_enum_pattern_errors() {
+    let source = "enum Color { Red } enum Shape { Circle }
+                  fn f(c: Color): void { match c { Shape::Circle => {} _ => {} } }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(c: Color): void { match c { 0 => {} _ => {} } }";
+    assert!(check(source).is_err());
+
+    let source = "enum Color { Red } fn f(c: Color): void { match c { Color::Blue => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Enum 'Color' has no variant 'Blue'."));
+}
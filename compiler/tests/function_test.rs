@@ -6,7 +6,7 @@ fn empty_void_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -20,7 +20,7 @@ fn number_return_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -34,7 +34,7 @@ fn bool_return_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -48,7 +48,7 @@ fn real_return_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -62,7 +62,7 @@ fn void_one_arg_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -76,7 +76,7 @@ fn void_multi_args_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -90,7 +90,7 @@ fn return_number_arg_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -104,7 +104,7 @@ fn return_real_arg_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -118,7 +118,7 @@ fn return_bool_arg_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -132,7 +132,7 @@ fn wrong_return_type() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -146,7 +146,7 @@ fn wrong_arg_type() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -160,7 +160,7 @@ fn main_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -175,7 +175,7 @@ fn simple_program_with_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -189,7 +189,7 @@ fn simple_program_without_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -203,7 +203,7 @@ fn function_declaration_no_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -217,7 +217,7 @@ fn function_declaration_with_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -231,7 +231,7 @@ fn exported_function_no_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -245,7 +245,7 @@ fn exported_function_with_args() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -259,7 +259,7 @@ fn exported_function_missing_body() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -273,7 +273,7 @@ fn pass_array_as_parameter() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -287,7 +287,7 @@ fn pass_array_as_parameter_wrong_size() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -301,7 +301,7 @@ fn pass_array_element_as_parameter() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -0,0 +1,246 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Literal, Statement},
+    },
+    type_system::value_type::ValueType,
+};
+
+mod common;
+
+use common::check;
+
+/// Names of the module level functions and structs, in emission order
+fn item_names(ast: &[Statement]) -> Vec<String> {
+    ast.iter()
+        .filter_map(|stmt| match stmt {
+            Statement::Function(f) => Some(format!("fn {}", f.callee)),
+            Statement::Struct(s) => Some(format!("struct {}", s.type_name)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn parse_generic_items() {
+    let source = "struct Pair<A, B> { first: A; second: ptr Pair<A, B>; }
+                  fn swap<T>(a: T): Pair<T, [T; 2]> { return max::<T, number>(a); }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::Struct(s) => {
+            assert_eq!(s.type_params, vec!["A".to_string(), "B".to_string()]);
+            assert_eq!(
+                s.fields[1].1,
+                ValueType::Pointer(Box::new(ValueType::Generic(
+                    "Pair".into(),
+                    vec![ValueType::Struct("A".into()), ValueType::Struct("B".into())]
+                )))
+            );
+        }
+        _ => panic!("Expected a struct statement"),
+    }
+
+    match &ast[1] {
+        Statement::Function(f) => {
+            assert_eq!(f.type_params, vec!["T".to_string()]);
+            match &f.block.as_ref().unwrap().statements[0] {
+                Statement::Return(r) => match &r.expr {
+                    Expression::Call(call) => assert_eq!(
+                        call.type_args,
+                        vec![ValueType::Struct("T".into()), ValueType::Number]
+                    ),
+                    _ => panic!("Expected a call"),
+                },
+                _ => panic!("Expected a return statement"),
+            }
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn parse_generic_errors() {
+    for source in [
+        "fn f<>(a: number): void {}",
+        "fn f<T(a: T): void {}",
+        "struct Pair<A, { a: A; }",
+        "fn f(p: Pair<number): void {}",
+        "fn f(): void { g::<number>; }",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn generic_function_instances() {
+    let source = "fn max<T>(a: T, b: T): T { if (a > b) { return a; } return b; }
+                  fn main(): number { let a = max(1, 2); let b = max(1.5, 2.5); \
+                  let c = max::<u8>(1, 2); let d = max(a, 3); return a; }";
+    let ast = check(source).unwrap();
+
+    // An instance per type arguments, emitted before their first use
+    assert_eq!(
+        item_names(&ast),
+        vec![
+            "fn max",
+            "fn max<number>",
+            "fn max<real>",
+            "fn max<u8>",
+            "fn main"
+        ]
+    );
+
+    match &ast[3] {
+        Statement::Function(f) => {
+            assert_eq!(f.return_type, ValueType::U8);
+            assert!(!f.is_exported);
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn generic_inference() {
+    // Literals take the type inferred from the other arguments
+    let source = "fn max<T>(a: T, b: T): T { if (a > b) { return a; } return b; }
+                  fn f(x: i32): i32 { return max(7, x); }";
+    assert!(check(source).is_ok());
+
+    let source = "fn max<T>(a: T, b: T): T { if (a > b) { return a; } return b; }
+                  fn f(x: number, y: real): void { let z = max(x, y); }";
+    let err = check(source).err().unwrap();
+    assert!(err
+        .contains("Type parameter 'T' of function 'max' is inferred as both 'Number' and 'Real'."));
+
+    // Through pointers and generic structs
+    let source = "struct Box<T> { value: T; }
+                  fn get<T>(b: ptr Box<T>): T { return b.value; }
+                  fn f(): bool { let b = Box { value: true }; return get(addrof b); }";
+    assert!(check(source).is_ok());
+
+    let source = "fn zero<T>(): T { return 0 as T; } fn f(): void { let z = zero(); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Type parameter 'T' of function 'zero' can't be inferred"));
+
+    let source = "fn zero<T>(): T { return 0 as T; } fn f(): real { return zero::<real>(); }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn generic_structs() {
+    let source = "struct Pair<A, B> { first: A; second: B; }
+                  fn f(): real { let p: Pair<number, real> = Pair { first: 1, second: 2.0 }; \
+                  let q = struct Pair<u8, bool> { 1, true }; return p.second; }";
+    let ast = check(source).unwrap();
+
+    assert_eq!(
+        item_names(&ast),
+        vec![
+            "struct Pair",
+            "struct Pair<number, real>",
+            "struct Pair<u8, bool>",
+            "fn f"
+        ]
+    );
+
+    match &ast[3] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[0] {
+            Statement::VariableDeclaration(v) => {
+                assert_eq!(
+                    v.variable_type,
+                    Some(ValueType::Struct("Pair<number, real>".into()))
+                );
+                match &v.init_expr {
+                    Some(Expression::Literal(Literal::StructLiteral(s))) => {
+                        assert_eq!(s.type_name, "Pair<number, real>");
+                        assert!(s.type_args.is_empty());
+                    }
+                    _ => panic!("Expected a struct literal"),
+                }
+            }
+            _ => panic!("Expected a variable declaration"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn recursive_generic_struct() {
+    let source = "struct Node<T> { value: T; next: ptr Node<T>; }
+                  fn len<T>(n: ptr Node<T>): number { if (n == null) { return 0; } return 1 + len(n.next); }
+                  fn f(): number { let n = Node { value: 1.5, next: null }; return len(addrof n); }";
+    let ast = check(source).unwrap();
+    assert_eq!(
+        item_names(&ast),
+        vec![
+            "struct Node",
+            "fn len",
+            "struct Node<real>",
+            "fn len<real>",
+            "fn f"
+        ]
+    );
+}
+
+#[test]
+fn type_argument_count() {
+    let source = "struct Pair<A, B> { first: A; second: B; } fn f(p: Pair<number>): void {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Struct 'Pair' has 2 type parameters but 1 type argument was given."));
+    assert!(err.contains("Note: 'Pair' is declared here."));
+
+    let source = "struct Pair<A, B> { first: A; second: B; } fn f(p: Pair): void {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Struct 'Pair' has 2 type parameters but 0 type arguments were given."));
+
+    let source = "fn id<T>(a: T): T { return a; } fn f(): void { let a = id::<number, real>(1); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Function 'id' has 1 type parameter but 2 type arguments were given."));
+}
+
+#[test]
+fn generic_errors() {
+    let source = "fn id(a: number): number { return a; } fn f(): void { let a = id::<number>(1); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Function 'id' is not generic."));
+
+    let source = "struct P { a: number; } fn f(p: P<number>): void {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Type 'P' is not generic."));
+
+    let source = "struct P { a: number; } fn f(): void { let p = struct P<number> { 1 }; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Struct 'P' is not generic."));
+
+    let source = "fn f<T, T>(a: T): T { return a; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Type parameter 'T' is declared more than once in function 'f'."));
+
+    let source = "struct Pair<A> { a: A; } struct Pair { b: number; }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn instance_errors() {
+    // The body is checked for each instance, errors point to the instantiation
+    let source = "struct Point { x: number; }
+                  fn get_x<T>(p: T): number { return p.x; }
+                  fn f(): number { return get_x(Point { x: 1 }); }
+                  fn g(): number { return get_x(2); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Note: Required by the instantiation 'get_x<number>' here."));
+
+    // Unused generic functions aren't checked
+    let source = "fn get_x<T>(p: T): number { return p.x; }";
+    assert!(check(source).is_ok());
+
+    let source = "fn f<T>(a: T): number { return f(addrof a); } fn g(): number { return f(1); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("is nested too deeply"));
+}
@@ -0,0 +1,143 @@
+use std::fs;
+
+use compiler::{
+    desugar::import_resolver::ImportResolver,
+    lexer::Token,
+    parser::{parser::Parser, visitors::Statement},
+    type_system::type_check::TypeChecker,
+};
+
+mod common;
+
+use common::check;
+
+#[test]
+fn parse_exported_globals() {
+    let source =
+        "export const MAX: number = 10; export let count: number = 0; let s: string = \"\";";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let flags: Vec<(bool, bool)> = ast
+        .iter()
+        .map(|stmt| match stmt {
+            Statement::VariableDeclaration(v) => (v.is_const, v.is_exported),
+            _ => panic!("Expected a variable declaration"),
+        })
+        .collect();
+
+    assert_eq!(flags, vec![(true, true), (false, true), (false, false)]);
+}
+
+#[test]
+fn export_without_declaration() {
+    let tokens = Token::lex_string("export 1;");
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn global_constant_init() {
+    let source = "const SIZE: number = (4 + 2) * -2; \
+                  let ratio: real = 1.5 / 2.0; \
+                  let flag: bool = 1 > 0 and true; \
+                  let size_ptr: ptr number = addrof SIZE; \
+                  fn f(): number { return SIZE; }";
+
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn global_init_with_variable() {
+    let source = "const SIZE: number = 4; let twice: number = SIZE * 2;";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn global_non_constant_init() {
+    let source = "fn one(): number { return 1; } let a: number = one();";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn assign_constant() {
+    let source = "const A: number = 1; let b: number = 1; fn f(): void { b = 2; A = 2; }";
+
+    assert!(check(source).is_err());
+    assert!(check(&source.replace(" A = 2;", "")).is_ok());
+}
+
+#[test]
+fn assign_constant_element() {
+    let source = "const ARR: [number; 2] = [1, 2]; const PTR: ptr number = null;";
+
+    let err = check(&format!("{} fn f(): void {{ (ARR)[0] = 2; }}", source))
+        .err()
+        .unwrap();
+    assert!(err.contains("Cannot assign to constant 'ARR'."));
+
+    // The pointed value isn't constant
+    assert!(check(&format!("{} fn f(): void {{ PTR[0] = 2; }}", source)).is_ok());
+}
+
+#[test]
+fn assign_variable_hiding_constant() {
+    let source = "const A: number = 1; fn f(A: number): void { A = 2; }";
+
+    assert!(check(source).is_ok());
+
+    let source = "const A: number = 1; fn f(): void { if (true) { let A = 1; A = 2; } A = 3; }";
+
+    assert!(check(source).is_err());
+
+    let source = "const A: number = 1; fn f(): void { if (true) { let A = 1; A = 2; } }";
+
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn local_constant() {
+    let source = "fn f(): void { const A: number = 1; }";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn exported_globals_are_forwarded() {
+    let dir = std::env::temp_dir().join("light_global_test");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("lib.lht"),
+        "export const SIZE: number = 4; let hidden: number = 1;",
+    )
+    .unwrap();
+
+    let module_path = dir.to_str().unwrap();
+    let tokens = Token::lex_string("import \"lib\"; fn f(): number { return lib::SIZE; }");
+    let mut parser = Parser::new(tokens, module_path, "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let mut resolver = ImportResolver::new();
+    let mut ast = resolver.resolve_imports(&ast, "main.lht").unwrap();
+
+    let globals: Vec<_> = ast
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Statement::VariableDeclaration(v) => Some(v),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(globals.len(), 1);
+    assert_eq!(globals[0].identifier, "lib::SIZE");
+    assert_eq!(globals[0].mangled_name.as_deref(), Some("lib::SIZE"));
+    assert!(globals[0].init_expr.is_none());
+    assert!(globals[0].is_const && !globals[0].is_exported);
+
+    let mut type_check = TypeChecker::new();
+    assert!(type_check.check_ast_type(&mut ast).is_ok());
+}
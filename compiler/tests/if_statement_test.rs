@@ -6,7 +6,7 @@ fn minimal_valid_if() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -20,7 +20,7 @@ fn minimal_valid_if_else() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -40,7 +40,7 @@ fn return_if_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -62,7 +62,7 @@ fn return_if_else_function() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -84,7 +84,7 @@ fn valid_nested_if() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -99,7 +99,7 @@ fn no_condition_if() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -109,7 +109,7 @@ fn no_body_then_if() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -119,5 +119,98 @@ fn no_body_else_if() {
     let tokens = Token::lex_string(source);
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn else_if_chain() {
+    let source = "fn sign(x: number): number { \
+                    if x > 0 { \
+                        return 1; \
+                    } else if x < 0 { \
+                        return -1; \
+                    } else if x == 0 { \
+                        return 0; \
+                    } else { \
+                        return 2; \
+                    } \
+                    return 0; \
+                  }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn else_if_bad_condition() {
+    let source = "if true {} else if 1 {}";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_err());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn conditional_expression() {
+    let source = "fn max(a: number, b: number): number { \
+                    let m: number = a > b ? a : b; \
+                    return a == b ? 0 : a < 0 ? -m : m; \
+                  }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn conditional_expression_null_branch() {
+    let source = "fn f(p: ptr number): ptr number { return p == null ? null : p; }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn conditional_expression_incompatible_branches() {
+    let source = "let a: number = true ? 1 : 2.0;";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_err());
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
+
+#[test]
+fn conditional_expression_missing_colon() {
+    let source = "let a: number = true ? 1;";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_err());
 }
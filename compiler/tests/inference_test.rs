@@ -0,0 +1,110 @@
+use compiler::{
+    lexer::Token,
+    parser::{parser::Parser, visitors::Statement},
+    type_system::{type_check::TypeChecker, value_type::ValueType},
+};
+
+// Returns the types of the variables declared in the body of the last function
+fn infer(source: &str) -> Result<Vec<ValueType>, String> {
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let mut ast = parser.parse().expect("Parser failed!");
+
+    let mut type_check = TypeChecker::new();
+    type_check.check_ast_type(&mut ast)?;
+
+    match ast.last() {
+        Some(Statement::Function(f)) => Ok(f
+            .block
+            .as_ref()
+            .unwrap()
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::VariableDeclaration(v) => v.variable_type.clone(),
+                _ => None,
+            })
+            .collect()),
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn infer_literals() {
+    let source =
+        "fn f(): void { let a = 1; let b = 1.5; let c = 'c'; let d = \"d\"; let e = true; }";
+
+    assert_eq!(
+        infer(source).unwrap(),
+        vec![
+            ValueType::Number,
+            ValueType::Real,
+            ValueType::Char,
+            ValueType::String,
+            ValueType::Bool
+        ]
+    );
+}
+
+#[test]
+fn infer_struct_literal() {
+    let source = "struct Point { x: number; y: number; } \
+                  fn f(): void { let p = struct Point { 1, 2 }; let x = p.x; }";
+
+    assert_eq!(
+        infer(source).unwrap(),
+        vec![ValueType::Struct("Point".to_string()), ValueType::Number]
+    );
+}
+
+#[test]
+fn infer_call_and_pointers() {
+    let source = "fn malloc(size: number): ptr void; \
+                  fn f(): void { \
+                    let a = 1; \
+                    let p = addrof a; \
+                    let v = deref p; \
+                    let m = malloc(8); \
+                  }";
+
+    assert_eq!(
+        infer(source).unwrap(),
+        vec![
+            ValueType::Number,
+            ValueType::Pointer(Box::new(ValueType::Number)),
+            ValueType::Number,
+            ValueType::Pointer(Box::new(ValueType::Void)),
+        ]
+    );
+}
+
+#[test]
+fn infer_null() {
+    let source = "fn f(): void { let p = null; }";
+    let error = infer(source).unwrap_err();
+
+    assert!(error.contains("a type annotation is required"));
+    assert!(infer("fn f(): void { let p: ptr number = null; }").is_ok());
+}
+
+#[test]
+fn infer_void_call() {
+    let source = "fn g(): void {} fn f(): void { let v = g(); }";
+
+    assert!(infer(source).is_err());
+}
+
+#[test]
+fn infer_global() {
+    let source = "let count = 0; fn f(): void { count = count + 1; }";
+
+    assert!(infer(source).is_ok());
+    assert!(infer("export let count = 0; fn f(): void {}").is_err());
+}
+
+#[test]
+fn infer_mismatch_after_inference() {
+    let source = "fn f(): void { let a = 1; a = 1.5; }";
+
+    assert!(infer(source).is_err());
+}
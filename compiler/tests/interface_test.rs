@@ -0,0 +1,288 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Binary, Expression, Statement},
+    },
+    type_system::{typed::Typed, value_type::ValueType},
+};
+
+mod common;
+
+use common::check;
+
+/// Expression of the return statement ending the body of `f`
+fn returned_expr(f: &Statement) -> &Expression {
+    match f {
+        Statement::Function(f) => match f.block.as_ref().unwrap().statements.last() {
+            Some(Statement::Return(r)) => &r.expr,
+            _ => panic!("Expected a return statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+const SHAPE: &str = "interface Shape {
+                         fn area(self: ptr Self): number;
+                         fn scale(self: ptr Self, factor: number): void;
+                     }
+                     struct Square { side: number; }
+                     impl Shape for Square {
+                         fn scale(self: ptr Square, factor: number): void { self.side = self.side * factor; }
+                         fn area(self: ptr Square): number { return self.side * self.side; }
+                     }";
+
+#[test]
+fn parse_interface() {
+    let tokens = Token::lex_string(SHAPE);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::Interface(i) => {
+            assert_eq!(i.type_name, "Shape");
+            let methods: Vec<&str> = i.methods.iter().map(|m| m.callee.as_str()).collect();
+            assert_eq!(methods, vec!["area", "scale"]);
+            assert!(i.methods.iter().all(|m| m.block.is_none()));
+        }
+        _ => panic!("Expected an interface statement"),
+    }
+
+    match &ast[2] {
+        Statement::Impl(i) => {
+            assert_eq!(i.type_name, "Square");
+            assert_eq!(i.interface, Some("Shape".to_string()));
+        }
+        _ => panic!("Expected an impl statement"),
+    }
+
+    let tokens = Token::lex_string("fn f<T: Shape, U>(t: ptr T, d: dyn Shape): void {}");
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::Function(f) => {
+            assert_eq!(f.type_params, vec!["T".to_string(), "U".to_string()]);
+            assert_eq!(f.type_bounds, vec![("T".to_string(), "Shape".to_string())]);
+            assert_eq!(
+                f.args.as_ref().unwrap()[1].1,
+                ValueType::Dyn("Shape".into())
+            );
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn parse_interface_errors() {
+    for source in [
+        "interface { fn f(self: ptr Self): void; }",
+        "interface Shape { let a = 1; }",
+        "interface Shape { fn f(self: ptr Self): void;",
+        "impl Shape for { fn f(): void {} }",
+        "struct Box<T: Shape> { value: T; }",
+        "fn f(s: dyn): void {}",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn interface_declaration_errors() {
+    let source = "interface I { fn f(self: ptr Self): void; fn f(self: ptr Self): void; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Method 'f' of interface 'I' is declared more than once."));
+
+    let source = "interface I { fn f(self: ptr Self): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Method 'f' of interface 'I' can't have a body"));
+
+    let source = "interface I { fn f(a: number): void; }";
+    let err = check(source).err().unwrap();
+    assert!(
+        err.contains("Method 'f' of interface 'I' must take 'self: ptr Self' as first parameter.")
+    );
+
+    let source = "interface I { fn f(self: ptr Self, other: ptr Self): void; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Method 'f' of interface 'I' can only use 'Self' as the type of 'self'."));
+
+    let source = "interface I { fn f(self: ptr Self): void; } fn g(i: I): void {}";
+    let err = check(source).err().unwrap();
+    assert!(
+        err.contains("Interface 'I' can't be used as a type, 'dyn I' is a value implementing it.")
+    );
+
+    let source = "fn g(i: dyn I): void {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'I' is not a declared interface."));
+}
+
+#[test]
+fn implementation_errors() {
+    let source = "struct S { a: number; } impl I for S { fn f(self: ptr S): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'I' is not a declared interface."));
+
+    let source = format!(
+        "{} struct Circle {{ r: number; }}
+            impl Shape for Circle {{ fn area(self: ptr Circle): number {{ return 3; }} }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err
+        .contains("Struct 'Circle' doesn't implement interface 'Shape', missing method 'scale'."));
+
+    let source = format!(
+        "{} struct Circle {{ r: number; }}
+            impl Shape for Circle {{ fn area(self: Circle): number {{ return 3; }} }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains(
+        "Method 'area' has signature 'fn(Circle): number' but interface 'Shape' requires 'fn(ptr Circle): number'."
+    ));
+
+    let source = format!(
+        "{} struct Circle {{ r: number; }}
+            impl Shape for Circle {{ fn perimeter(self: ptr Circle): number {{ return 3; }} }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Method 'perimeter' is not a method of interface 'Shape'."));
+
+    let source = format!(
+        "{} impl Shape for Square {{
+            fn area(self: ptr Square): number {{ return 0; }}
+            fn scale(self: ptr Square, factor: number): void {{}}
+        }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Interface 'Shape' is implemented more than once for struct 'Square'."));
+}
+
+#[test]
+fn implementation_in_interface_order() {
+    // The vtable is laid out in the order of the interface
+    let ast = check(SHAPE).unwrap();
+
+    match &ast[2] {
+        Statement::Impl(i) => {
+            let methods: Vec<&str> = i.methods.iter().map(|m| m.callee.as_str()).collect();
+            assert_eq!(methods, vec!["Square::area", "Square::scale"]);
+        }
+        _ => panic!("Expected an impl statement"),
+    }
+}
+
+#[test]
+fn dynamic_dispatch() {
+    let source = format!(
+        "{} fn area(s: dyn Shape): number {{ s.scale(2); return s.area(); }}
+            fn f(): number {{ let sq = Square {{ side: 2 }}; let s: dyn Shape = addrof sq; return area(s); }}
+            fn g(sq: ptr Square): number {{ return area(sq as dyn Shape); }}",
+        SHAPE
+    );
+    let ast = check(&source).unwrap();
+
+    // The receiver is the dyn value itself
+    match returned_expr(&ast[3]) {
+        Expression::Call(call) => {
+            assert_eq!(call.name, "Shape::area");
+            assert_eq!(
+                call.args.as_ref().unwrap()[0].get_type(),
+                ValueType::Dyn("Shape".into())
+            );
+        }
+        _ => panic!("Expected a method call"),
+    }
+
+    // Pointers to implementing structs are converted implicitly
+    match &ast[4] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[1] {
+            Statement::VariableDeclaration(v) => match &v.init_expr {
+                Some(Expression::Cast(cast)) => {
+                    assert_eq!(cast.target, ValueType::Dyn("Shape".into()))
+                }
+                _ => panic!("Expected a cast"),
+            },
+            _ => panic!("Expected a variable declaration"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn dyn_errors() {
+    let source = format!(
+        "{} fn f(n: ptr number): number {{ let s: dyn Shape = n; return s.area(); }}",
+        SHAPE
+    );
+    assert!(check(&source).is_err());
+
+    let source = format!(
+        "{} struct Circle {{ r: number; }} fn f(c: ptr Circle): void {{ let s = c as dyn Shape; }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains(
+        "Invalid cast from 'ptr Circle' to 'dyn Shape', only a pointer to a struct implementing 'Shape' can be cast to it."
+    ));
+
+    let source = format!(
+        "{} fn f(s: dyn Shape): number {{ return s.perimeter(); }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Interface 'Shape' has no method 'perimeter'."));
+
+    let source = format!(
+        "{} fn f(a: dyn Shape, b: dyn Shape): bool {{ return a == b; }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("'dyn Shape' values can't be compared."));
+}
+
+#[test]
+fn static_dispatch() {
+    // Bounded type parameters are instantiated, the instance calls the
+    // methods of the struct
+    let source = format!(
+        "{} fn twice<T: Shape>(s: ptr T): number {{ return s.area() * 2; }}
+            fn f(): number {{ let sq = Square {{ side: 2 }}; return twice(addrof sq); }}",
+        SHAPE
+    );
+    let ast = check(&source).unwrap();
+
+    match &ast[4] {
+        Statement::Function(f) => {
+            assert_eq!(f.callee, "twice<Square>");
+            match returned_expr(&ast[4]) {
+                Expression::Binary(Binary::Multiply(left, _)) => match &**left {
+                    Expression::Call(call) => assert_eq!(call.name, "Square::area"),
+                    _ => panic!("Expected a method call"),
+                },
+                _ => panic!("Expected a binary expression"),
+            }
+        }
+        _ => panic!("Expected a function statement"),
+    }
+
+    let source = format!(
+        "{} fn twice<T: Shape>(s: ptr T): number {{ return s.area() * 2; }}
+            fn f(): number {{ let n = 2; return twice(addrof n); }}",
+        SHAPE
+    );
+    let err = check(&source).err().unwrap();
+    assert!(err.contains(
+        "Type 'number' doesn't implement interface 'Shape' required by type parameter 'T' of function 'twice'."
+    ));
+
+    let source = "fn f<T: Shape>(s: T): void {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'Shape' is not a declared interface."));
+}
@@ -12,7 +12,7 @@ fn minimal_while() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -31,7 +31,7 @@ fn while_10_iteration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -47,7 +47,7 @@ fn while_no_condition() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -58,7 +58,7 @@ fn while_no_body() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -69,7 +69,7 @@ fn while_condition_type_mismatch() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -89,7 +89,7 @@ fn nested_while_10_iteration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -105,7 +105,7 @@ fn simple_for_loop() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -121,7 +121,7 @@ fn simple_for_loop_with_body() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -137,7 +137,7 @@ fn for_desugar_with_body() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
         desugar_ast(&mut ast);
@@ -158,7 +158,7 @@ fn for_desugar_no_body() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
         desugar_ast(&mut ast);
@@ -179,7 +179,7 @@ fn for_missing_semicolon() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -190,7 +190,7 @@ fn for_no_loop_condition() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -201,7 +201,7 @@ fn for_no_next_statement() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -215,7 +215,7 @@ fn two_for_statement() {
     let mut checker = TypeChecker::new();
     let stmts = parser.parse();
 
-    assert!(stmts.is_some());
+    assert!(stmts.is_ok());
     assert!(checker.check_ast_type(&mut stmts.unwrap()).is_ok());
 }
 
@@ -229,7 +229,7 @@ fn parse_loop_statement() {
     let mut checker = TypeChecker::new();
     let stmts = parser.parse();
 
-    assert!(stmts.is_some());
+    assert!(stmts.is_ok());
     assert!(checker.check_ast_type(&mut stmts.unwrap()).is_ok());
 }
 
@@ -243,7 +243,7 @@ fn parse_loop_statement_with_break() {
     let mut checker = TypeChecker::new();
     let stmts = parser.parse();
 
-    assert!(stmts.is_some());
+    assert!(stmts.is_ok());
     assert!(checker.check_ast_type(&mut stmts.unwrap()).is_ok());
 }
 
@@ -257,6 +257,66 @@ fn parse_break_outside_loop() {
     let mut checker = TypeChecker::new();
     let stmts = parser.parse();
 
-    assert!(stmts.is_some());
+    assert!(stmts.is_ok());
     assert!(checker.check_ast_type(&mut stmts.unwrap()).is_err());
 }
+
+#[test]
+fn parse_while_statement_with_continue() {
+    let source = "while true { if false { continue; } break; }";
+
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "", "");
+    let mut checker = TypeChecker::new();
+    let stmts = parser.parse();
+
+    assert!(stmts.is_ok());
+    assert!(checker.check_ast_type(&mut stmts.unwrap()).is_ok());
+}
+
+#[test]
+fn parse_continue_outside_loop() {
+    let source = "fn dummy(): void { continue; }";
+
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "", "");
+    let mut checker = TypeChecker::new();
+    let stmts = parser.parse();
+
+    assert!(stmts.is_ok());
+    assert!(checker.check_ast_type(&mut stmts.unwrap()).is_err());
+}
+
+#[test]
+fn for_desugar_with_continue() {
+    let source = "for let i: number = 0; i < 10; i = i + 1; { continue; }";
+
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "", "");
+
+    if let Ok(mut ast) = parser.parse() {
+        let mut type_check = TypeChecker::new();
+        assert!(type_check.check_ast_type(&mut ast).is_ok());
+        desugar_ast(&mut ast);
+
+        let while_stmt = match &ast[0] {
+            Statement::Block(b) => match &b.statements[1] {
+                Statement::WhileStatement(w) => w,
+                _ => panic!("Expected a while statement"),
+            },
+            _ => panic!("Expected a block statement"),
+        };
+
+        // The next expression must not be skipped by the continue statement
+        assert_eq!(while_stmt.loop_block.statements.len(), 1);
+        assert!(matches!(
+            while_stmt.next_expr.as_deref(),
+            Some(Statement::VariableAssignment(_))
+        ));
+    } else {
+        assert!(false, "Parser failed!");
+    }
+}
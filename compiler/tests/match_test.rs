@@ -0,0 +1,156 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Pattern, Statement},
+    },
+};
+
+mod common;
+
+use common::{check, check_with_warnings};
+
+#[test]
+fn parse_match_patterns() {
+    let source = "fn f(n: number): void { \
+                    match n { \
+                        1 | 2 => {} \
+                        -10..0 => {}, \
+                        3..=9 => {} \
+                        _ => {} \
+                    } \
+                  }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let match_stmt = match &ast[0] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[0] {
+            Statement::MatchStatement(m) => m.clone(),
+            _ => panic!("Expected a match statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    };
+
+    assert_eq!(match_stmt.arms.len(), 4);
+    assert_eq!(match_stmt.arms[0].patterns.len(), 2);
+    assert_eq!(match_stmt.arms[1].patterns[0].to_string(), "-10..0");
+    assert_eq!(match_stmt.arms[2].patterns[0].to_string(), "3..=9");
+    assert!(matches!(
+        match_stmt.arms[3].patterns[0],
+        Pattern::Wildcard(_)
+    ));
+}
+
+#[test]
+fn match_missing_arrow() {
+    let source = "fn f(n: number): void { match n { 1 {} _ => {} } }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn match_number_with_default() {
+    let source = "fn f(n: number): void { match n { 1 => {} 2..5 => {} _ => {} } }";
+    let (result, warnings) = check_with_warnings(source);
+
+    assert!(result.is_ok());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn match_char() {
+    let source = "fn f(c: char): void { match c { 'a'..='z' | 'A'..='Z' => {} _ => {} } }";
+
+    assert!(check(source).is_ok());
+
+    // Chars are bytes, the codes above the ASCII literals must be covered too
+    let source = "fn f(c: char): void { match c { '\\0'..='\\x7F' => {} } }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_bool_is_exhaustive() {
+    let source = "fn f(b: bool): void { match b { true => {} false => {} } }";
+
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn match_non_exhaustive() {
+    let source = "fn f(n: number): void { match n { 1 => {} 2 => {} } }";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_pattern_type_mismatch() {
+    let source = "fn f(n: number): void { match n { 'a' => {} _ => {} } }";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_sized_integers() {
+    // The domain is the range of the type
+    let source = "fn f(b: u8, n: i32): void { \
+                    match b { 0..128 => {} 128..=255 => {} } \
+                    match n { -5..=5 => {} _ => {} } \
+                  }";
+    assert!(check(source).is_ok());
+
+    let source = "fn f(b: u8): void { match b { 0..=254 => {} } }";
+    assert!(check(source).is_err());
+
+    let source = "fn f(b: i8): void { match b { 200 => {} _ => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Literal 200 doesn't fit in type 'i8'."));
+}
+
+#[test]
+fn match_on_real() {
+    let source = "fn f(r: real): void { match r { _ => {} } }";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_empty_range() {
+    let source = "fn f(n: number): void { match n { 5..5 => {} _ => {} } }";
+
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_unreachable_arms() {
+    let source = "fn f(n: number): void { \
+                    match n { \
+                        0..=10 => {} \
+                        5 | 11 => {} \
+                        2..4 => {} \
+                        _ => {} \
+                        12 => {} \
+                    } \
+                  }";
+    let (result, warnings) = check_with_warnings(source);
+
+    assert!(result.is_ok());
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].ends_with("Warning: Unreachable pattern '5'."));
+    assert!(warnings[1].ends_with("Warning: Unreachable match arm."));
+    assert!(warnings[2].ends_with("Warning: Unreachable match arm."));
+}
+
+#[test]
+fn match_arm_scopes() {
+    let source = "fn f(n: number): void { \
+                    match n { \
+                        1 => { let a: number = 1; } \
+                        _ => { let a: number = 2; } \
+                    } \
+                  }";
+
+    assert!(check(source).is_ok());
+}
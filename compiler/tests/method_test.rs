@@ -0,0 +1,179 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Statement},
+    },
+    type_system::value_type::ValueType,
+};
+
+mod common;
+
+use common::check;
+
+/// Expression of the return statement ending the body of `f`
+fn returned_expr(f: &Statement) -> &Expression {
+    match f {
+        Statement::Function(f) => match f.block.as_ref().unwrap().statements.last() {
+            Some(Statement::Return(r)) => &r.expr,
+            _ => panic!("Expected a return statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+const USER: &str = "struct User { age: number; }
+                    impl User {
+                        fn new(age: number): User { return User { age: age }; }
+                        fn birthday(self: ptr User): void { self.age = self.age + 1; }
+                        fn age(self: User): number { return self.age; }
+                    }";
+
+#[test]
+fn parse_impl_block() {
+    let tokens = Token::lex_string(USER);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[1] {
+        Statement::Impl(i) => {
+            assert_eq!(i.type_name, "User");
+            let methods: Vec<&str> = i.methods.iter().map(|m| m.callee.as_str()).collect();
+            assert_eq!(methods, vec!["new", "birthday", "age"]);
+        }
+        _ => panic!("Expected an impl statement"),
+    }
+}
+
+#[test]
+fn parse_impl_errors() {
+    for source in [
+        "impl { fn f(): void {} }",
+        "impl User { let a = 1; }",
+        "impl User { fn f(): void {}",
+        "export impl User { fn f(): void {} }",
+        "fn f(): void { u.; }",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn member_access_in_expression() {
+    // The member is a single identifier, the rest of the expression follows
+    let source = "struct P { x: number; } fn f(p: P): number { return p.x + p.x * 2; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn method_calls() {
+    let source = format!(
+        "{} fn f(): number {{ let u = User::new(1); u.birthday(); return u.age(); }}
+            fn g(u: ptr User): number {{ u.birthday(); return u.age(); }}",
+        USER
+    );
+    let ast = check(&source).unwrap();
+
+    match &ast[1] {
+        Statement::Impl(i) => {
+            assert_eq!(i.methods[1].callee, "User::birthday");
+            assert_eq!(
+                i.methods[1].args.as_ref().unwrap()[0].1,
+                ValueType::Pointer(Box::new(ValueType::Struct("User".into())))
+            );
+        }
+        _ => panic!("Expected an impl statement"),
+    }
+
+    // The receiver is the first argument, by address or dereferenced
+    match &ast[2] {
+        Statement::Function(f) => {
+            let statements = &f.block.as_ref().unwrap().statements;
+            match &statements[0] {
+                Statement::VariableDeclaration(v) => match &v.init_expr {
+                    Some(Expression::Call(call)) => assert_eq!(call.name, "User::new"),
+                    _ => panic!("Expected a call"),
+                },
+                _ => panic!("Expected a variable declaration"),
+            }
+            match &statements[1] {
+                Statement::Expression(Expression::Call(call)) => {
+                    assert_eq!(call.name, "User::birthday");
+                    assert!(matches!(
+                        call.args.as_ref().unwrap()[0],
+                        Expression::AddressOf(_)
+                    ));
+                }
+                _ => panic!("Expected a method call"),
+            }
+        }
+        _ => panic!("Expected a function statement"),
+    }
+
+    match returned_expr(&ast[3]) {
+        Expression::Call(call) => {
+            assert_eq!(call.name, "User::age");
+            assert!(matches!(
+                call.args.as_ref().unwrap()[0],
+                Expression::DeReference(_)
+            ));
+        }
+        _ => panic!("Expected a method call"),
+    }
+}
+
+#[test]
+fn method_call_errors() {
+    let source = format!("{} fn f(u: User): number {{ return u.name(); }}", USER);
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Struct 'User' has no method 'name'."));
+
+    let source = format!("{} fn f(u: User): User {{ return u.new(1); }}", USER);
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Function 'User::new' has no 'self' parameter"));
+
+    let source = format!("{} fn f(n: number): number {{ return n.age(); }}", USER);
+    let err = check(&source).err().unwrap();
+    assert!(err.contains("Method 'age' called on a value of type 'number'"));
+
+    let source = format!("{} fn f(u: User): number {{ return u.age(1); }}", USER);
+    assert!(check(&source).is_err());
+}
+
+#[test]
+fn impl_declaration_errors() {
+    let source = "impl User { fn f(): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'User' is not a declared struct."));
+
+    let source = "struct P { x: number; } impl P { fn f(): void {} } impl P { fn f(): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Method 'f' is declared more than once for struct 'P'."));
+
+    let source = "struct P { x: number; } impl P { fn f(a: number, self: P): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'self' must be the first parameter of method 'f'."));
+
+    let source = "struct P { x: number; } impl P { fn f(self: number): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("'self' of method 'f' has type 'number' but 'P' or 'ptr P' is required."));
+
+    let source = "struct Box<T> { value: T; } impl Box { fn f(): void {} }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Methods can't be declared for generic struct 'Box'."));
+}
+
+#[test]
+fn generic_method() {
+    let source = "struct P { x: number; }
+                  impl P { fn with<T>(self: P, value: T): T { return value; } }
+                  fn f(p: P): real { return p.with(1.5); }";
+    let ast = check(source).unwrap();
+
+    match returned_expr(&ast[3]) {
+        Expression::Call(call) => assert_eq!(call.name, "P::with<real>"),
+        _ => panic!("Expected a method call"),
+    }
+}
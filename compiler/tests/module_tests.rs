@@ -1,4 +1,57 @@
-use compiler::{lexer::Token, parser::parser::Parser};
+use std::fs;
+
+use compiler::{
+    desugar::import_resolver::ImportResolver,
+    lexer::Token,
+    parser::{parser::Parser, visitors::Statement},
+    type_system::type_check::TypeChecker,
+};
+
+/// Writes `modules` in a directory named after the test, then resolves the
+/// imports of `source` and type checks it.
+fn resolve_and_check(
+    test_name: &str,
+    modules: &[(&str, &str)],
+    source: &str,
+) -> Result<Vec<Statement>, String> {
+    let dir = std::env::temp_dir().join(format!("light_module_test_{}", test_name));
+    fs::create_dir_all(&dir).unwrap();
+
+    for (name, content) in modules {
+        fs::write(dir.join(format!("{}.lht", name)), content).unwrap();
+    }
+
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, dir.to_str().unwrap(), "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let mut resolver = ImportResolver::new();
+    let mut ast = resolver.resolve_imports(&ast, "main.lht")?;
+
+    let mut type_check = TypeChecker::new();
+    type_check.check_ast_type(&mut ast)?;
+
+    Ok(ast)
+}
+
+fn find_function<'a>(ast: &'a [Statement], name: &str) -> Option<&'a Statement> {
+    ast.iter().find(|stmt| match stmt {
+        Statement::Function(f) => f.callee == name,
+        _ => false,
+    })
+}
+
+fn mangled_name(stmt: Option<&Statement>) -> Option<String> {
+    match stmt {
+        Some(Statement::Function(f)) => f.mangled_name.clone(),
+        _ => None,
+    }
+}
+
+/// Symbol of `item` defined in `module` of the test directory
+fn symbol(module: &str, item: &str) -> String {
+    format!("{}::{}", module, item)
+}
 
 #[test]
 fn import_statement_parser() {
@@ -8,7 +61,7 @@ fn import_statement_parser() {
     let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
 }
 
 #[test]
@@ -19,7 +72,7 @@ fn import_statement_parser_missing_quote() {
     let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_none());
+    assert!(ast_opt.is_err());
 }
 
 #[test]
@@ -30,16 +83,736 @@ fn import_statement_parser_missing_semicolon() {
     let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_none());
-}
-
-// #[test]
-// fn module_access_parsing() {
-//     let source = "module::function();";
-//     let tokens = Token::lex_string(source);
-//
-//     let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
-//
-//     let ast_opt = parser.parse();
-//     assert!(ast_opt.is_some());
-// }
+    assert!(ast_opt.is_err());
+}
+
+#[test]
+fn import_statement_parser_alias() {
+    let source = "import \"stdio\" as io;";
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
+
+    let ast_opt = parser.parse();
+    assert!(ast_opt.is_ok());
+}
+
+#[test]
+fn import_statement_parser_missing_alias() {
+    let source = "import \"stdio\" as;";
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
+
+    let ast_opt = parser.parse();
+    assert!(ast_opt.is_err());
+}
+
+#[test]
+fn selective_import_parser() {
+    for source in [
+        "import { println } from \"stdio\";",
+        "import { println, print_number, } from \"stdio\";",
+        "export import \"memory\";",
+        "export import { malloc } from \"memory\";",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
+        assert!(parser.parse().is_ok(), "{}", source);
+    }
+
+    for source in [
+        "import { println } \"stdio\";",
+        "import { println from \"stdio\";",
+        "import { } from \"stdio\";",
+        "import { println } from \"stdio\" as io;",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn module_access_parsing() {
+    let source = "fn f(): void { module::function(); let p: module::Point = module::origin; }";
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
+
+    let ast_opt = parser.parse();
+    assert!(ast_opt.is_ok());
+}
+
+#[test]
+fn namespaced_functions_dont_collide() {
+    let modules = [
+        ("first", "export fn init(): number { return 1; }"),
+        ("second", "export fn init(): number { return 2; }"),
+    ];
+    let source = "import \"first\"; import \"second\";
+                  fn main(): number { return first::init() + second::init(); }";
+
+    let ast = resolve_and_check("collide", &modules, source).unwrap();
+
+    assert_eq!(
+        mangled_name(find_function(&ast, "first::init")),
+        Some(symbol("first", "init"))
+    );
+    assert_eq!(
+        mangled_name(find_function(&ast, "second::init")),
+        Some(symbol("second", "init"))
+    );
+    // The entry point isn't mangled
+    assert_eq!(mangled_name(find_function(&ast, "main")), None);
+}
+
+#[test]
+fn modules_with_the_same_file_name_dont_collide() {
+    let dir = test_dir("same_file_name");
+    for sub in ["net", "fs"] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+        fs::write(
+            dir.join(sub).join("util.lht"),
+            "export fn init(): number { return 1; }",
+        )
+        .unwrap();
+    }
+
+    let source = "import \"net/util\"; import \"fs/util\" as fs_util;
+                  fn main(): number { return util::init() + fs_util::init(); }";
+    let ast = resolve_and_check("same_file_name", &[], source).unwrap();
+
+    assert_eq!(
+        mangled_name(find_function(&ast, "util::init")),
+        Some(symbol("net/util", "init"))
+    );
+    assert_eq!(
+        mangled_name(find_function(&ast, "fs_util::init")),
+        Some(symbol("fs/util", "init"))
+    );
+}
+
+#[test]
+fn imported_function_needs_namespace() {
+    let modules = [("first", "export fn init(): number { return 1; }")];
+    let source = "import \"first\"; fn f(): number { return init(); }";
+
+    assert!(resolve_and_check("unqualified", &modules, source).is_err());
+}
+
+#[test]
+fn import_alias() {
+    let modules = [("stdio", "export fn println(msg: string): void {}")];
+
+    let source = "import \"stdio\" as io; fn f(): void { io::println(\"hello\"); }";
+    let ast = resolve_and_check("alias", &modules, source).unwrap();
+    // The symbol is the one of the definition
+    assert_eq!(
+        mangled_name(find_function(&ast, "io::println")),
+        Some(symbol("stdio", "println"))
+    );
+
+    let source = "import \"stdio\" as io; fn f(): void { stdio::println(\"hello\"); }";
+    let err = resolve_and_check("alias", &modules, source).err().unwrap();
+    assert!(err.contains("Module 'stdio' is not imported."));
+}
+
+#[test]
+fn namespace_imported_twice() {
+    let modules = [
+        ("first", "export fn init(): number { return 1; }"),
+        ("second", "export fn init(): number { return 2; }"),
+    ];
+    let source = "import \"first\"; import \"second\" as first;";
+
+    let err = resolve_and_check("twice", &modules, source).err().unwrap();
+    assert!(err.contains("Namespace 'first' is already used by another import."));
+}
+
+#[test]
+fn c_functions_are_not_mangled() {
+    let modules = [(
+        "memory",
+        "export fn malloc(size: number): ptr void; export fn zero(): number { return 0; }",
+    )];
+    let source = "import \"memory\";
+                  fn alloc(): ptr void { return memory::malloc(memory::zero()); }";
+
+    let ast = resolve_and_check("c_functions", &modules, source).unwrap();
+
+    assert_eq!(
+        mangled_name(find_function(&ast, "memory::malloc")),
+        Some("malloc".to_string())
+    );
+    // Functions defined in the module are mangled with its name
+    assert_eq!(
+        mangled_name(find_function(&ast, "alloc")),
+        Some("main::alloc".to_string())
+    );
+}
+
+#[test]
+fn imported_structs() {
+    let modules = [(
+        "geometry",
+        "export struct Point { x: number; y: number = 0; }
+         export fn origin(): Point { return Point { x: 0 }; }
+         export fn norm(point: ptr Point): number { return 0; }",
+    )];
+    let source = "import \"geometry\" as geo;
+                  fn f(): number {
+                      let o: geo::Point = geo::origin();
+                      let p = geo::Point { x: 1 };
+                      let q = struct geo::Point { 1, 2 };
+                      let x = q.x;
+                      return geo::norm(addrof p) + o.y;
+                  }";
+
+    resolve_and_check("structs", &modules, source).unwrap();
+
+    let source = "import \"geometry\"; fn f(): void { let p = Point { x: 1 }; }";
+    assert!(resolve_and_check("structs", &modules, source).is_err());
+}
+
+#[test]
+fn imported_enums() {
+    let modules = [(
+        "colors",
+        "export enum Color { Red, Green = 4 }
+         export fn mix(a: Color, b: Color): Color { return a; }",
+    )];
+    let source = "import \"colors\";
+                  fn f(): colors::Color {
+                      let c: colors::Color = colors::mix(colors::Color::Red, colors::Color::Green);
+                      match c { colors::Color::Red => {} _ => {} }
+                      return c;
+                  }";
+    resolve_and_check("enums", &modules, source).unwrap();
+
+    let source = "import { Color, mix } from \"colors\";
+                  fn f(): Color { return mix(Color::Red, Color::Green); }";
+    resolve_and_check("enums", &modules, source).unwrap();
+
+    let source = "import \"colors\"; fn f(): void { let c = Color::Red; }";
+    assert!(resolve_and_check("enums", &modules, source).is_err());
+}
+
+#[test]
+fn imported_unions() {
+    let modules = [(
+        "results",
+        "export struct Error { code: number; }
+         export union Result { Ok(number), Err(Error) }
+         export fn parse(s: string): Result { return Result::Err(Error { code: 1 }); }",
+    )];
+    let source = "import \"results\";
+                  fn f(): results::Result {
+                      let r = results::parse(\"1\");
+                      match r {
+                          results::Result::Ok(n) => { return results::Result::Ok(n + 1); }
+                          results::Result::Err(e) => { let code: number = e.code; }
+                      }
+                      return r;
+                  }";
+    resolve_and_check("unions", &modules, source).unwrap();
+
+    let source = "import { Result, Error } from \"results\";
+                  fn f(): Result { return Result::Err(Error { code: 2 }); }";
+    resolve_and_check("unions", &modules, source).unwrap();
+
+    let source = "import \"results\"; fn f(): void { let r = Result::Ok(1); }";
+    assert!(resolve_and_check("unions", &modules, source).is_err());
+}
+
+#[test]
+fn imported_generics() {
+    let modules = [(
+        "pairs",
+        "export struct Pair<A, B> { first: A; second: B; }
+         export fn swap<A, B>(p: Pair<A, B>): Pair<B, A> { \
+         return Pair { first: p.second, second: p.first }; }",
+    )];
+    let source = "import \"pairs\";
+                  fn f(): bool {
+                      let p: pairs::Pair<number, bool> = pairs::Pair { first: 1, second: true };
+                      let q = pairs::swap(p);
+                      return q.first;
+                  }";
+    let ast = resolve_and_check("generics", &modules, source).unwrap();
+
+    // Instantiated in the importing module, the symbol keeps the module name
+    assert_eq!(
+        mangled_name(find_function(&ast, "pairs::swap<number, bool>")),
+        Some(symbol("pairs", "swap<number, bool>"))
+    );
+
+    let source = "import { Pair, swap } from \"pairs\";
+                  fn f(p: Pair<real, real>): real { let q = swap(p); return q.first; }";
+    resolve_and_check("generics", &modules, source).unwrap();
+}
+
+#[test]
+fn generic_body_uses_its_module() {
+    let modules = [(
+        "math",
+        "let calls: number = 0;
+         fn sign(n: number): number { if (n < 0) { return -1; } return 1; }
+         export fn pow<T>(base: T, exp: number): T {
+             calls = calls + 1;
+             let result = base;
+             for let i = 1; i < exp * sign(exp); i = i + 1; { result = result * base; }
+             return result;
+         }
+         export fn square<T>(n: T): T { return pow(n, 2); }",
+    )];
+    let source = "import \"math\"; fn f(): number { return math::square(3); }";
+    let ast = resolve_and_check("generic_body", &modules, source).unwrap();
+
+    // Declared with their symbol, which can't be written in the source
+    let sign = symbol("math", "sign");
+    assert_eq!(mangled_name(find_function(&ast, &sign)), Some(sign.clone()));
+    let calls = symbol("math", "calls");
+    assert!(ast.iter().any(|stmt| match stmt {
+        Statement::VariableDeclaration(v) => v.identifier == calls && v.init_expr.is_none(),
+        _ => false,
+    }));
+
+    // The defining module exports them to its importers
+    let tokens = Token::lex_string(modules[0].1);
+    let mut parser = Parser::new(tokens, "", "math.lht");
+    let ast = parser.parse().expect("Parser failed!");
+    let ast = ImportResolver::new()
+        .resolve_imports(&ast, "math.lht")
+        .unwrap();
+    for stmt in &ast {
+        match stmt {
+            Statement::Function(f) => assert!(f.is_exported),
+            Statement::VariableDeclaration(v) => {
+                assert!(v.is_exported);
+                assert_eq!(v.mangled_name.as_deref(), Some("math::calls"));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn generic_body_local_in_inner_block() {
+    let modules = [(
+        "counter",
+        "let count: number = 0;
+         export fn tick<T>(x: T): T {
+             if (count > 0) { let count = 1; count = count + 1; }
+             for let count = 0; count < 2; count = count + 1; {}
+             count = count + 1;
+             return x;
+         }",
+    )];
+    let source = "import \"counter\"; fn f(): number { return counter::tick(3); }";
+
+    // The local only hides the global until the end of its block
+    assert!(resolve_and_check("generic_inner_block", &modules, source).is_ok());
+}
+
+#[test]
+fn imported_methods() {
+    let modules = [(
+        "geo",
+        "export struct Point { x: number; }
+         impl Point {
+             fn new(x: number): Point { return Point { x: x }; }
+             fn moved(self: Point, dx: number): Point { return Point::new(self.x + dx); }
+         }
+         struct Hidden { a: number; }
+         impl Hidden { fn get(self: Hidden): number { return self.a; } }",
+    )];
+    let source = "import \"geo\";
+                  fn f(): number { let p = geo::Point::new(1); let q = p.moved(2); return q.x; }";
+    let ast = resolve_and_check("methods", &modules, source).unwrap();
+
+    // Declared with their struct, the symbol keeps the module name
+    let methods = ast
+        .iter()
+        .find_map(|stmt| match stmt {
+            Statement::Impl(i) if i.type_name == "geo::Point" => Some(&i.methods),
+            Statement::Impl(i) => panic!("Unexpected methods of '{}'", i.type_name),
+            _ => None,
+        })
+        .unwrap();
+    let names: Vec<(&str, Option<String>)> = methods
+        .iter()
+        .map(|m| (m.callee.as_str(), m.mangled_name.clone()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("geo::Point::new", Some(symbol("geo", "Point::new"))),
+            ("geo::Point::moved", Some(symbol("geo", "Point::moved")))
+        ]
+    );
+
+    let source = "import { Point } from \"geo\";
+                  fn f(p: Point): Point { return p.moved(1); }";
+    resolve_and_check("methods", &modules, source).unwrap();
+}
+
+#[test]
+fn imported_interfaces() {
+    let modules = [(
+        "shapes",
+        "export interface Shape { fn area(self: ptr Self): number; }
+         export struct Square { side: number; }
+         impl Shape for Square { fn area(self: ptr Square): number { return self.side * self.side; } }",
+    )];
+    let source = "import \"shapes\";
+                  struct Circle { r: number; }
+                  impl shapes::Shape for Circle { fn area(self: ptr Circle): number { return 3 * self.r * self.r; } }
+                  fn area(s: dyn shapes::Shape): number { return s.area(); }
+                  fn f(): number {
+                      let sq = shapes::Square { side: 2 };
+                      let c = Circle { r: 1 };
+                      return area(addrof sq) + area(addrof c);
+                  }";
+    let ast = resolve_and_check("interfaces", &modules, source).unwrap();
+
+    // The implementation comes with the struct
+    assert!(ast.iter().any(|stmt| match stmt {
+        Statement::Impl(i) => {
+            i.type_name == "shapes::Square" && i.interface.as_deref() == Some("shapes::Shape")
+        }
+        _ => false,
+    }));
+
+    let source = "import { Shape, Square } from \"shapes\";
+                  fn area(s: ptr Square): number { let d: dyn Shape = s; return d.area(); }";
+    resolve_and_check("interfaces", &modules, source).unwrap();
+}
+
+#[test]
+fn imported_globals() {
+    let modules = [(
+        "config",
+        "export let counter: number = 0; export const SIZE: number = 4;",
+    )];
+
+    let source = "import \"config\"; fn f(): void { config::counter = config::SIZE; }";
+    assert!(resolve_and_check("globals", &modules, source).is_ok());
+
+    let source = "import \"config\"; fn f(): void { config::SIZE = 2; }";
+    let err = resolve_and_check("globals", &modules, source)
+        .err()
+        .unwrap();
+    assert!(err.contains("Cannot assign to constant 'config::SIZE'."));
+}
+
+#[test]
+fn transitive_imports() {
+    let modules = [
+        ("shapes", "export struct Point { x: number; }"),
+        (
+            "geometry",
+            "import \"shapes\";
+             export fn origin(): shapes::Point { return shapes::Point { x: 0 }; }",
+        ),
+    ];
+    let source = "import \"geometry\"; fn f(): number { let p = geometry::origin(); return p.x; }";
+
+    let ast = resolve_and_check("transitive", &modules, source).unwrap();
+
+    // Not imported directly, the struct is in the namespace of its importer
+    assert!(ast.iter().any(|stmt| match stmt {
+        Statement::Struct(s) => s.type_name == "geometry::shapes::Point",
+        _ => false,
+    }));
+}
+
+#[test]
+fn shared_dependency_declared_once() {
+    let modules = [
+        ("shapes", "export struct Point { x: number; }"),
+        (
+            "geometry",
+            "import \"shapes\" as s;
+             export fn origin(): s::Point { return s::Point { x: 0 }; }",
+        ),
+    ];
+    let source = "import \"geometry\"; import \"shapes\";
+                  fn f(): number { let p: shapes::Point = geometry::origin(); return p.x; }";
+
+    let ast = resolve_and_check("shared", &modules, source).unwrap();
+
+    let points = ast
+        .iter()
+        .filter(|stmt| match stmt {
+            Statement::Struct(s) => s.type_name.ends_with("Point"),
+            _ => false,
+        })
+        .count();
+    assert_eq!(points, 1);
+}
+
+#[test]
+fn same_import_twice() {
+    let modules = [("first", "export fn init(): number { return 1; }")];
+
+    let source = "import \"first\"; import \"first\"; fn f(): number { return first::init(); }";
+    let ast = resolve_and_check("same_twice", &modules, source).unwrap();
+    assert_eq!(
+        ast.iter()
+            .filter(|stmt| matches!(stmt, Statement::Function(f) if f.callee == "first::init"))
+            .count(),
+        1
+    );
+
+    let source = "import \"first\"; import \"first\" as other;";
+    let err = resolve_and_check("same_twice", &modules, source)
+        .err()
+        .unwrap();
+    assert!(err.contains("Module 'first' is already imported as 'first'."));
+}
+
+#[test]
+fn import_cycle() {
+    let modules = [
+        ("a", "import \"b\"; export fn f(): void {}"),
+        ("b", "import \"c\"; export fn g(): void {}"),
+        ("c", "import \"a\"; export fn h(): void {}"),
+    ];
+    let source = "import \"a\";";
+
+    let err = resolve_and_check("cycle", &modules, source).err().unwrap();
+    assert!(err.contains("Import cycle detected"));
+    assert!(err.contains("a.lht -> "));
+    assert!(err.contains("b.lht -> "));
+    assert!(err.contains("c.lht -> "));
+}
+
+#[test]
+fn imported_modules_are_parsed_once() {
+    let dir = std::env::temp_dir().join("light_module_test_cache");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("lib.lht"), "export fn first(): void {}").unwrap();
+
+    let mut resolver = ImportResolver::new();
+    let resolve = |resolver: &mut ImportResolver| {
+        let tokens = Token::lex_string("import \"lib\";");
+        let mut parser = Parser::new(tokens, dir.to_str().unwrap(), "main.lht");
+        let ast = parser.parse().expect("Parser failed!");
+        resolver.resolve_imports(&ast, "main.lht").unwrap()
+    };
+
+    assert!(find_function(&resolve(&mut resolver), "lib::first").is_some());
+
+    // The module is cached by the resolver
+    fs::write(dir.join("lib.lht"), "export fn second(): void {}").unwrap();
+    assert!(find_function(&resolve(&mut resolver), "lib::first").is_some());
+    assert!(find_function(&resolve(&mut ImportResolver::new()), "lib::second").is_some());
+}
+
+#[test]
+fn selective_import() {
+    let modules = [(
+        "stdio",
+        "export fn println(): void {} export fn print_number(n: number): void {} \
+         export struct Stream { fd: number; } export const STDOUT: number = 1;",
+    )];
+
+    let source = "import { println, Stream, STDOUT } from \"stdio\"; \
+                  fn f(): void { println(); let s: Stream = struct Stream { fd: STDOUT }; }";
+    let ast = resolve_and_check("selective", &modules, source).unwrap();
+    assert_eq!(
+        mangled_name(find_function(&ast, "println")),
+        Some(symbol("stdio", "println"))
+    );
+    assert!(find_function(&ast, "print_number").is_none());
+    assert!(find_function(&ast, "stdio::print_number").is_none());
+
+    // Only the named items are in scope
+    let source = "import { println } from \"stdio\"; fn f(): void { print_number(1); }";
+    assert!(resolve_and_check("selective", &modules, source).is_err());
+
+    let source = "import { println } from \"stdio\"; fn f(): void { stdio::println(); }";
+    assert!(resolve_and_check("selective", &modules, source).is_err());
+}
+
+#[test]
+fn selective_import_unknown_item() {
+    let modules = [("stdio", "export fn println(): void {} fn hidden(): void {}")];
+
+    let source = "import { println, hidden } from \"stdio\";";
+    let err = resolve_and_check("selective_unknown", &modules, source)
+        .err()
+        .unwrap();
+    assert!(err.contains("Module 'stdio' doesn't export 'hidden'."));
+}
+
+#[test]
+fn reexported_module() {
+    let modules = [
+        (
+            "memory",
+            "export struct Block { size: number; } \
+             export fn allocate(size: number): Block { return struct Block { size: size }; } \
+             fn internal(): void {}",
+        ),
+        (
+            "facade",
+            "export import \"memory\"; import \"stdio\"; export fn init(): void {}",
+        ),
+        ("stdio", "export fn println(): void {}"),
+    ];
+
+    let source = "import \"facade\"; \
+                  fn f(): number { facade::init(); let b: facade::Block = facade::allocate(8); return b.size; }";
+    let ast = resolve_and_check("reexport", &modules, source).unwrap();
+    assert_eq!(
+        mangled_name(find_function(&ast, "facade::allocate")),
+        Some(symbol("memory", "allocate"))
+    );
+
+    // Plain imports of the facade aren't re-exported
+    assert!(find_function(&ast, "facade::println").is_none());
+    assert!(find_function(&ast, "facade::internal").is_none());
+
+    let source = "import { allocate, Block } from \"facade\"; \
+                  fn f(): number { let b: Block = allocate(8); return b.size; }";
+    let ast = resolve_and_check("reexport", &modules, source).unwrap();
+    assert_eq!(
+        mangled_name(find_function(&ast, "allocate")),
+        Some(symbol("memory", "allocate"))
+    );
+}
+
+#[test]
+fn selective_reexport() {
+    let modules = [
+        (
+            "memory",
+            "export fn malloc(size: number): void {} export fn free(): void {}",
+        ),
+        ("facade", "export import { malloc } from \"memory\";"),
+    ];
+
+    let source = "import \"facade\"; fn f(): void { facade::malloc(8); }";
+    let ast = resolve_and_check("selective_reexport", &modules, source).unwrap();
+    assert!(find_function(&ast, "facade::free").is_none());
+
+    let source = "import { free } from \"facade\";";
+    let err = resolve_and_check("selective_reexport", &modules, source)
+        .err()
+        .unwrap();
+    assert!(err.contains("Module 'facade' doesn't export 'free'."));
+}
+
+/// Creates an empty directory for a test
+fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("light_module_test_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn search_paths() {
+    let project = test_dir("search_project");
+    let library = test_dir("search_library");
+    let stdlib = test_dir("search_stdlib");
+    fs::write(
+        library.join("util.lht"),
+        "export fn from_library(): void {}",
+    )
+    .unwrap();
+    fs::write(stdlib.join("util.lht"), "export fn from_stdlib(): void {}").unwrap();
+    fs::write(stdlib.join("io.lht"), "export fn from_stdlib(): void {}").unwrap();
+
+    let mut resolver = ImportResolver::new();
+    resolver.add_search_path(&library);
+    resolver.add_search_path(&stdlib);
+
+    let tokens = Token::lex_string("import \"util\"; import \"io\";");
+    let mut parser = Parser::new(tokens, project.to_str().unwrap(), "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    // The first search path containing the module is used
+    let resolved = resolver.resolve_imports(&ast, "main.lht").unwrap();
+    assert!(find_function(&resolved, "util::from_library").is_some());
+    assert!(find_function(&resolved, "io::from_stdlib").is_some());
+
+    // Modules next to the importer come first
+    fs::write(
+        project.join("util.lht"),
+        "export fn from_project(): void {}",
+    )
+    .unwrap();
+    let mut resolver = ImportResolver::new();
+    resolver.add_search_path(&library);
+
+    let resolved = resolver
+        .resolve_imports(&ast[..1].to_vec(), "main.lht")
+        .unwrap();
+    assert!(find_function(&resolved, "util::from_project").is_some());
+}
+
+#[test]
+fn module_names_are_relative_to_their_root() {
+    let project = test_dir("root_project");
+    let library = test_dir("root_library");
+    fs::create_dir_all(library.join("net")).unwrap();
+    fs::write(
+        library.join("net").join("socket.lht"),
+        "export import \"../io\"; export fn open(): void {}",
+    )
+    .unwrap();
+    fs::write(library.join("io.lht"), "export fn read(): void {}").unwrap();
+
+    let mut resolver = ImportResolver::new();
+    resolver.add_search_path(&library);
+
+    let tokens = Token::lex_string("import \"net/socket\"; fn f(): void { socket::read(); }");
+    let mut parser = Parser::new(tokens, project.to_str().unwrap(), "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    // A module next to its importer is named from the root of the importer
+    let resolved = resolver.resolve_imports(&ast, "main.lht").unwrap();
+    assert_eq!(
+        mangled_name(find_function(&resolved, "socket::open")),
+        Some(symbol("net/socket", "open"))
+    );
+    assert_eq!(
+        mangled_name(find_function(&resolved, "socket::read")),
+        Some(symbol("io", "read"))
+    );
+
+    // A module given to the compiler is named like its importers name it
+    let socket = library.join("net").join("socket.lht");
+    let tokens = Token::lex_string("export fn open(): void {}");
+    let mut parser = Parser::new(tokens, socket.parent().unwrap().to_str().unwrap(), "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let resolved = resolver
+        .resolve_imports(&ast, socket.to_str().unwrap())
+        .unwrap();
+    assert_eq!(
+        mangled_name(find_function(&resolved, "open")),
+        Some(symbol("net/socket", "open"))
+    );
+}
+
+#[test]
+fn module_not_found() {
+    let project = test_dir("not_found_project");
+    let library = test_dir("not_found_library");
+
+    let mut resolver = ImportResolver::new();
+    resolver.add_search_path(&library);
+
+    let tokens = Token::lex_string("import \"missing\";");
+    let mut parser = Parser::new(tokens, project.to_str().unwrap(), "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let err = resolver.resolve_imports(&ast, "main.lht").err().unwrap();
+    assert!(err.contains("Module 'missing' not found"));
+    assert!(err.contains(project.join("missing.lht").to_str().unwrap()));
+    assert!(err.contains(library.join("missing.lht").to_str().unwrap()));
+}
@@ -0,0 +1,80 @@
+use compiler::{
+    lexer::{LogosToken, Token},
+    parser::{parse_error::ParseError, parser::Parser},
+};
+
+fn parse_errors(source: &str) -> Vec<ParseError> {
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "module.lht");
+
+    parser.parse().err().expect("Expected parse errors")
+}
+
+#[test]
+fn every_error_is_reported() {
+    let source = "fn a(): void {\n    let x: number = ;\n    let y: number = 1\n}\n\
+                  fn b(): number { return 1 }\n\
+                  fn c(): number { return 2; }";
+    let errors = parse_errors(source);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].message, "Expected expression.");
+    assert_eq!((errors[0].line, errors[0].column), (2, 20));
+    assert_eq!(errors[1].line, 4);
+    assert_eq!(errors[2].line, 5);
+}
+
+#[test]
+fn recovery_at_top_level() {
+    let source = "fn a(: void {}\nfn b(): void { return 1 }\nstruct S { 1 }";
+    let errors = parse_errors(source);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].line, 1);
+    assert_eq!(errors[1].line, 2);
+    assert_eq!(errors[2].line, 3);
+}
+
+#[test]
+fn recovery_before_every_top_level_item() {
+    let source = "const A: number = 1 2\nlet b: number = 3 4\n\
+                  impl S { fn f(self: S): void { return 1 } }\n\
+                  const C: number = 5 6\ninterface I { fn g(self: I): }";
+    let errors = parse_errors(source);
+
+    let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn error_expected_tokens() {
+    let errors = parse_errors("import module;");
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].expected, vec![LogosToken::Quote(String::new())]);
+    assert_eq!(errors[0].span.start, 7);
+    assert_eq!(errors[0].span.end, 13);
+    assert_eq!(
+        errors[0].to_string(),
+        "module.lht:1:7 Error: Expected a string literal after 'import' keyword."
+    );
+}
+
+#[test]
+fn lexer_errors_are_reported() {
+    let source =
+        "fn a(): void { let s: string = \"\\q\"; let c: char = ''; let d: number = 1 $ 2; }";
+    let errors = parse_errors(source);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].column, 32);
+    assert_eq!(errors[1].message, "Empty char literal.");
+    assert_eq!(errors[2].message, "Unexpected character '$'.");
+}
+
+#[test]
+fn unclosed_block() {
+    let errors = parse_errors("fn a(): void { let x: number = 1;");
+
+    assert_eq!(errors.len(), 1);
+}
@@ -9,7 +9,7 @@ fn simple_pointer_declaration() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -23,7 +23,7 @@ fn simple_pointer_addrof() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -37,7 +37,7 @@ fn wrong_pointer_addrof() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail.");
 }
@@ -51,7 +51,7 @@ fn addrof_of_non_pointer_type() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail.");
 }
@@ -66,7 +66,7 @@ fn pointer_dereference_assignment() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -81,7 +81,7 @@ fn wrong_type_pointer_dereference_assignment() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type checking should fail.");
 }
@@ -98,7 +98,7 @@ fn pointer_array_subscript() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -112,7 +112,7 @@ fn void_pointer_init() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -126,7 +126,7 @@ fn pointer_init_from_void() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -140,7 +140,7 @@ fn malloc_array_and_assign() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -154,7 +154,7 @@ fn valid_deref_lvalue_number() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -169,7 +169,7 @@ fn dynamic_string_allocation() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -184,7 +184,7 @@ fn dynamic_string_array_access() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -199,7 +199,7 @@ fn dynamic_string_free() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -0,0 +1,89 @@
+mod common;
+
+use common::check;
+
+#[test]
+fn every_path_returns() {
+    for source in [
+        "fn f(x: number): number { if (x > 0) { return 1; } else { return 2; } }",
+        "fn f(x: number): number { { { return x; } } }",
+        "fn f(x: number): number { if (x > 0) { if (x > 1) { return 2; } return 1; } else { return 0; } }",
+        "fn f(x: number): number { return x; x = 2; }",
+        "fn f(x: number): number { while (x > 0) { x = x - 1; } return x; }",
+        "enum Color { Red, Green } \
+         fn f(c: Color): number { match c { Color::Red => { return 1; } Color::Green => { return 2; } } }",
+        "fn f(x: number): number { match x { 0 => { return 1; } _ => { return 2; } } }",
+    ] {
+        assert!(check(source).is_ok(), "{}", source);
+    }
+}
+
+#[test]
+fn loops_without_break_diverge() {
+    for source in [
+        "fn f(): number { loop {} }",
+        "fn f(x: number): number { loop { if (x > 10) { return x; } x = x + 1; } }",
+        "fn f(x: number): number { loop { x = x + 1; if (x < 10) { continue; } return x; } }",
+        // The break leaves the inner loop only
+        "fn f(x: number): number { loop { while (x > 0) { break; } } }",
+    ] {
+        assert!(check(source).is_ok(), "{}", source);
+    }
+}
+
+#[test]
+fn missing_return() {
+    // Reported at the last statement run by the path falling through
+    let source = "fn f(x: number): number {
+                      if (x > 0) {
+                          return 1;
+                      }
+                  }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains(":2:22 Error: missing return in function 'f'."));
+
+    let source = "fn f(x: number): number {
+                      if (x > 0) {
+                          return 1;
+                      } else {
+                          x = 2;
+                      }
+                  }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains(":5:26 Error: missing return in function 'f'."));
+
+    let source = "fn f(x: number): number {
+                      loop {
+                          if (x > 10) {
+                              break;
+                          }
+                          x = x + 1;
+                      }
+                  }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains(":4:30 Error: missing return in function 'f'."));
+
+    let source = "fn f(x: number): number {
+                      while (x > 0) {
+                          return x;
+                      }
+                  }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains(":2:22 Error: missing return in function 'f'."));
+
+    let source = "fn f(x: number): number { match x { 0 => { return 1; } _ => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("missing return in function 'f'."));
+
+    let source = "fn f(): number {}";
+    let err = check(source).err().unwrap();
+    assert!(err.contains(":1:0 Error: missing return in function 'f'."));
+}
+
+#[test]
+fn missing_return_in_methods() {
+    let source = "struct P { x: number; }
+                  impl P { fn get(self: P): number { if (self.x > 0) { return self.x; } } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("missing return in function 'P::get'."));
+}
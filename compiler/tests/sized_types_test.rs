@@ -0,0 +1,90 @@
+use compiler::{
+    parser::visitors::{Expression, Statement},
+    type_system::value_type::ValueType,
+};
+
+mod common;
+
+use common::check;
+
+#[test]
+fn sized_declarations() {
+    let source = "fn f(): void { \
+                  let a: i8 = -128; let b: i16 = 1000; let c: i32 = -1; let d: i64 = 1; \
+                  let e: u8 = 255; let g: u16 = 65535; let h: u32 = 7; let i: u64 = 8; \
+                  let x: f32 = 1.5; let y: f64 = 2.5; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn number_and_real_are_aliases() {
+    let source = "fn f(a: i64, b: f64): void { let n: number = a; let r: real = b; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn literal_out_of_range() {
+    let sources = [
+        ("fn f(): void { let a: u8 = 256; }", "Literal 256 doesn't fit in type 'u8'."),
+        ("fn f(): void { let a: i8 = -129; }", "Literal -129 doesn't fit in type 'i8'."),
+        ("fn f(): void { let a: u32 = -1; }", "Literal -1 doesn't fit in type 'u32'."),
+    ];
+
+    for (source, message) in sources {
+        let err = check(source).err().unwrap();
+        assert!(err.contains(message), "{}", err);
+    }
+}
+
+#[test]
+fn literals_take_the_expected_type() {
+    let source = "struct S { small: u8; ratio: f32 = 0.5; } \
+                  fn g(value: i16): u32 { return 3; } \
+                  fn f(x: i32): void { \
+                  let y = x + 1; let z = 2 * x; let b = x < 10; \
+                  g(4); let s = struct S { small: 1 }; s.small = 2; y = 3; }";
+    let ast = check(source).unwrap();
+
+    match ast.last() {
+        Some(Statement::Function(f)) => match &f.block.as_ref().unwrap().statements[0] {
+            Statement::VariableDeclaration(v) => {
+                assert_eq!(v.variable_type, Some(ValueType::I32));
+                match &v.init_expr {
+                    Some(Expression::Binary(_)) => (),
+                    _ => panic!("Expected a binary expression"),
+                }
+            }
+            _ => panic!("Expected a variable declaration"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn sized_types_dont_mix() {
+    let sources = [
+        "fn f(a: i32, b: i64): void { let c = a + b; }",
+        "fn f(a: u8): void { let b: i8 = a; }",
+        "fn f(a: f32, b: real): void { let c = a * b; }",
+        "fn f(a: number): i32 { return a; }",
+        "fn f(): void { let a: f32 = 1; }",
+    ];
+
+    for source in sources {
+        assert!(check(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn sized_casts() {
+    let source = "fn f(a: i32, b: u8, c: f32): void { \
+                  let d = a as u64; let e = b as i8; let g = c as real; \
+                  let h = a as f32; let i = c as u16; let j = 'a' as u8; let k = b as bool; }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn sized_pointers() {
+    let source = "fn f(): void { let a: u16 = 1; let p = addrof a; deref p = 2; }";
+    assert!(check(source).is_ok());
+}
@@ -0,0 +1,73 @@
+use compiler::{
+    debug::{LineDebugInfo, SpanDebugInfo},
+    lexer::Token,
+    parser::{parser::Parser, visitors::Statement},
+};
+
+fn parse(source: &str) -> Vec<Statement> {
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+
+    parser.parse().expect("Parser failed!")
+}
+
+fn first_statement_of(function: &Statement) -> Statement {
+    match function {
+        Statement::Function(f) => f.block.as_ref().unwrap().statements[0].clone(),
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn binary_expression_span() {
+    let source = "fn dummy(): number {\n    return 1 + 22 * 3;\n}";
+    let ast = parse(source);
+
+    match first_statement_of(&ast[0]) {
+        Statement::Return(r) => {
+            assert_eq!(&source[r.byte_range()], "return 1 + 22 * 3;");
+            assert_eq!(&source[r.expr.byte_range()], "1 + 22 * 3");
+            assert_eq!(r.expr.line(), 2);
+            assert_eq!(r.expr.column(), 11);
+            assert_eq!(r.expr.end_column(), 21);
+        }
+        _ => assert!(false, "Expected a return statement"),
+    }
+}
+
+#[test]
+fn call_expression_span() {
+    let source = "fn dummy(): void { print(1, (2)); }";
+    let ast = parse(source);
+
+    match first_statement_of(&ast[0]) {
+        Statement::Expression(e) => assert_eq!(&source[e.byte_range()], "print(1, (2))"),
+        _ => assert!(false, "Expected an expression statement"),
+    }
+}
+
+#[test]
+fn multiline_function_span() {
+    let source = "\n/// Doc\nfn dummy(): void {\n    let a: number = 1;\n}\n";
+    let ast = parse(source);
+
+    assert_eq!(ast[0].begin_line(), 3);
+    assert_eq!(ast[0].end_line(), 5);
+    assert!(source[ast[0].byte_range()].starts_with("fn dummy"));
+    assert!(source[ast[0].byte_range()].ends_with('}'));
+}
+
+#[test]
+fn unary_expression_span() {
+    let source = "fn dummy(): number {\n    return -(1 + 2);\n}";
+    let ast = parse(source);
+
+    match first_statement_of(&ast[0]) {
+        Statement::Return(r) => {
+            assert_eq!(&source[r.expr.byte_range()], "-(1 + 2)");
+            assert_eq!(r.expr.line(), 2);
+            assert_eq!(r.expr.column(), 11);
+        }
+        _ => assert!(false, "Expected a return statement"),
+    }
+}
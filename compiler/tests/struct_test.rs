@@ -1,4 +1,15 @@
-use compiler::{lexer::Token, parser::parser::Parser, type_system::type_check::TypeChecker};
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Literal, Statement},
+    },
+    type_system::type_check::TypeChecker,
+};
+
+mod common;
+
+use common::check;
 
 #[test]
 fn simple_struct_declaration() {
@@ -9,7 +20,7 @@ fn simple_struct_declaration() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -23,7 +34,7 @@ fn simple_struct_init() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -37,7 +48,7 @@ fn struct_init_undeclared_type() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -51,7 +62,7 @@ fn struct_init_bad_type() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -65,7 +76,7 @@ fn struct_init_no_init_exps() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -79,7 +90,7 @@ fn struct_init_bad_type_init_exp() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -94,7 +105,7 @@ fn struct_init_too_much_exps() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -109,7 +120,7 @@ fn valid_struct_member_access() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
@@ -124,7 +135,7 @@ fn struct_member_access_wrong_field() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_err(), "Type check should fail!");
 }
@@ -137,7 +148,7 @@ fn struct_member_access_wrong_type() {
     let mut parser = Parser::new(tokens, "", "");
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_none());
+    assert!(ast_opt.is_err());
 }
 
 #[test]
@@ -150,7 +161,108 @@ fn struct_member_assign() {
     let mut type_check = TypeChecker::new();
 
     let ast_opt = parser.parse();
-    assert!(ast_opt.is_some());
+    assert!(ast_opt.is_ok());
     let tc_result = type_check.check_ast_type(&mut ast_opt.unwrap());
     assert!(tc_result.is_ok(), "Type error: {}", tc_result.unwrap_err());
 }
+
+const USER: &str = "struct User { name: string; admin: bool = false; age: number = 18; }";
+
+#[test]
+fn struct_named_fields_init() {
+    let source = format!(
+        "{} fn main(): void {{ \
+           let bob = User {{ age: 20, name: \"Bob\", admin: true }}; \
+           let alice: User = struct User {{ name: \"Alice\" }}; \
+           if bob.admin {{ x: 1; }} \
+         }}",
+        USER
+    );
+    // `if bob.admin { x: 1; }` is not a struct literal but `x: 1` isn't a statement
+    assert!(Parser::new(Token::lex_string(&source), "", "")
+        .parse()
+        .is_err());
+
+    let source = source.replace(" if bob.admin { x: 1; }", "");
+    let ast = check(&source).unwrap();
+
+    // Named fields are put back in the declaration order, defaults included
+    let body = match &ast[1] {
+        Statement::Function(f) => f.block.as_ref().unwrap().statements.clone(),
+        _ => panic!("Expected a function statement"),
+    };
+
+    for stmt in body {
+        match stmt {
+            Statement::VariableDeclaration(v) => match v.init_expr.unwrap() {
+                Expression::Literal(Literal::StructLiteral(s)) => {
+                    assert!(s.field_names.is_none());
+                    assert_eq!(s.expressions.len(), 3);
+                    assert!(matches!(
+                        s.expressions[0],
+                        Expression::Literal(Literal::StringLiteral(_))
+                    ));
+                }
+                _ => panic!("Expected a struct literal"),
+            },
+            _ => panic!("Expected a variable declaration"),
+        }
+    }
+}
+
+#[test]
+fn struct_named_fields_errors() {
+    let missing = check(&format!(
+        "{} fn main(): void {{ let u = User {{ age: 1 }}; }}",
+        USER
+    ));
+    assert!(missing
+        .err()
+        .unwrap()
+        .ends_with("Missing field 'name' in struct 'User' literal."));
+
+    let duplicate = check(&format!(
+        "{} fn main(): void {{ let u = User {{ name: \"a\", name: \"b\" }}; }}",
+        USER
+    ));
+    assert!(duplicate
+        .err()
+        .unwrap()
+        .contains("Field 'name' is initialised more than once"));
+
+    let unknown = check(&format!(
+        "{} fn main(): void {{ let u = User {{ name: \"a\", email: \"b\" }}; }}",
+        USER
+    ));
+    assert!(unknown
+        .err()
+        .unwrap()
+        .contains("Struct 'User' has no field 'email'."));
+
+    let wrong_type = check(&format!(
+        "{} fn main(): void {{ let u = User {{ name: \"a\", age: true }}; }}",
+        USER
+    ));
+    assert!(wrong_type.is_err());
+}
+
+#[test]
+fn struct_field_default_errors() {
+    assert!(check("struct S { count: number = true; }").is_err());
+    assert!(check("fn one(): number { return 1; } struct S { count: number = one(); }").is_err());
+    assert!(
+        Parser::new(Token::lex_string("struct S { count: number = 1 }"), "", "")
+            .parse()
+            .is_err()
+    );
+}
+
+#[test]
+fn struct_positional_init_requires_all_fields() {
+    let source = format!(
+        "{} fn main(): void {{ let u = struct User {{ \"a\" }}; }}",
+        USER
+    );
+
+    assert!(check(&source).is_err());
+}
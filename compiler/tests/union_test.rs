@@ -0,0 +1,256 @@
+use compiler::{
+    lexer::Token,
+    parser::{
+        parser::Parser,
+        visitors::{Expression, Pattern, Statement},
+    },
+    type_system::{type_check::TypeChecker, value_type::ValueType},
+};
+
+mod common;
+
+use common::{check, check_with_warnings};
+
+#[test]
+fn parse_union_variants() {
+    let source = "union Shape { Circle(real), Rect(real, real), Empty, }";
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, "", "");
+    let ast = parser.parse().expect("Parser failed!");
+
+    match &ast[0] {
+        Statement::Union(u) => {
+            let variants: Vec<(&str, usize)> = u
+                .variants
+                .iter()
+                .map(|(name, payload, _)| (name.as_str(), payload.len()))
+                .collect();
+            assert_eq!(variants, vec![("Circle", 1), ("Rect", 2), ("Empty", 0)]);
+            assert_eq!(u.variants[1].1, vec![ValueType::Real, ValueType::Real]);
+        }
+        _ => panic!("Expected a union statement"),
+    }
+}
+
+#[test]
+fn parse_union_errors() {
+    for source in [
+        "union Shape { Circle(real) Rect }",
+        "union Shape { Circle(real, }",
+        "union Shape { Circle() }",
+        "union { Empty }",
+    ] {
+        let tokens = Token::lex_string(source);
+        let mut parser = Parser::new(tokens, "", "");
+        assert!(parser.parse().is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn union_construction() {
+    let source = "union Shape { Circle(real), Rect(real, real), Empty }
+                  fn f(): Shape { let s: Shape = Shape::Rect(1.0, 2.0); let e = Shape::Empty; \
+                  return Shape::Circle(3.0); }";
+    let ast = check(source).unwrap();
+
+    match &ast[1] {
+        Statement::Function(f) => {
+            assert_eq!(f.return_type, ValueType::Union("Shape".into()));
+
+            match &f.block.as_ref().unwrap().statements[0] {
+                Statement::VariableDeclaration(v) => {
+                    assert_eq!(v.variable_type, Some(ValueType::Union("Shape".into())));
+                    match &v.init_expr {
+                        Some(Expression::UnionVariant(variant)) => {
+                            assert_eq!(variant.tag, 1);
+                            assert_eq!(variant.payload.len(), 2);
+                        }
+                        _ => panic!("Expected a union variant"),
+                    }
+                }
+                _ => panic!("Expected a variable declaration"),
+            }
+        }
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn union_construction_errors() {
+    let source = "union Shape { Circle(real) } fn f(): void { let s = Shape::Circle(1.0, 2.0); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variant 'Shape::Circle' holds 1 value but 2 were given."));
+
+    let source = "union Shape { Circle(real) } fn f(): void { let s = Shape::Circle; }";
+    assert!(check(source).is_err());
+
+    let source = "union Shape { Circle(real) } fn f(): void { let s = Shape::Circle(true); }";
+    let err = check(source).err().unwrap();
+    assert!(
+        err.contains("Value 1 of variant 'Shape::Circle' has type 'Bool' but 'Real' is required.")
+    );
+
+    let source = "union Shape { Circle(real) } fn f(): void { let s = Shape::Square(1.0); }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Union 'Shape' has no variant 'Square'."));
+
+    // Literals take the type of the payload
+    let source = "union Value { Byte(u8) } fn f(): void { let v = Value::Byte(255); }";
+    assert!(check(source).is_ok());
+
+    let source = "union Value { Byte(u8) } fn f(): void { let v = Value::Byte(256); }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn union_declaration_errors() {
+    let source = "union Shape { Circle(real), Circle }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variant 'Circle' is declared more than once in union 'Shape'."));
+
+    let source = "union Shape {}";
+    assert!(check(source).is_err());
+
+    let source = "struct Shape { a: number; } union Shape { Empty }";
+    assert!(check(source).is_err());
+
+    let source = "union List { Cons(number, List), Nil }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Payload of variant 'Cons' in union 'List' can't have type 'Union List'."));
+
+    // Through a pointer the payload has a fixed size
+    let source = "union List { Cons(number, ptr List), Nil }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn union_named_like_an_interface() {
+    // The interface is already declared when the union is checked
+    let mut type_check = TypeChecker::new();
+    let mut check_next = |source: &str| {
+        let mut parser = Parser::new(Token::lex_string(source), "", "");
+        let mut ast = parser.parse().expect("Parser failed!");
+        type_check.check_ast_type(&mut ast)
+    };
+
+    assert!(check_next("interface Shape { fn f(self: ptr Self): void; }").is_ok());
+    let err = check_next("union Shape { Empty }").err().unwrap();
+    assert!(err.contains("Redefinition of union 'Shape'."));
+}
+
+#[test]
+fn union_used_before_declaration() {
+    let source = "fn area(s: Shape): real { match s { Shape::Circle(r) => { return r * r; } \
+                  _ => { return 0.0; } } return 0.0; }
+                  union Shape { Circle(real), Empty, Nested(Inner) }
+                  union Inner { Value(Color) }
+                  enum Color { Red }";
+    assert!(check(source).is_ok());
+}
+
+#[test]
+fn match_union_bindings() {
+    let source = "union Shape { Circle(real), Rect(real, real), Empty }
+                  fn area(s: Shape): real { let a = 0.0; match s { \
+                  Shape::Circle(r) => { a = 3.14 * r * r; } \
+                  Shape::Rect(w, h) => { a = w * h; } \
+                  Shape::Empty => {} } return a; }";
+    let (result, warnings) = check_with_warnings(source);
+    let ast = result.unwrap();
+    assert!(warnings.is_empty());
+
+    match &ast[1] {
+        Statement::Function(f) => match &f.block.as_ref().unwrap().statements[1] {
+            Statement::MatchStatement(m) => match &m.arms[1].patterns[0] {
+                Pattern::EnumVariant(p) => {
+                    assert_eq!(p.value, Some(1));
+                    assert_eq!(p.bindings, vec![Some("w".into()), Some("h".into())]);
+                }
+                _ => panic!("Expected a variant pattern"),
+            },
+            _ => panic!("Expected a match statement"),
+        },
+        _ => panic!("Expected a function statement"),
+    }
+}
+
+#[test]
+fn match_union_binding_scope() {
+    // The bindings are only visible in their arm
+    let source = "union Shape { Circle(real), Empty }
+                  fn f(s: Shape): real { match s { Shape::Circle(r) => {} _ => {} } return r; }";
+    assert!(check(source).is_err());
+
+    // `_` ignores a value of the payload
+    let source = "union Shape { Rect(real, real) }
+                  fn f(s: Shape): real { match s { Shape::Rect(w, _) => { return w; } } return 0.0; }";
+    assert!(check(source).is_ok());
+
+    let source = "union Shape { Rect(real, real) }
+                  fn f(s: Shape): bool { match s { Shape::Rect(w, _) => { return w; } } return true; }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_union_pattern_errors() {
+    let source = "union Shape { Rect(real, real) }
+                  fn f(s: Shape): void { match s { Shape::Rect(w) => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Pattern 'Shape::Rect(w)' binds 1 value but variant 'Rect' holds 2."));
+
+    let source = "union Shape { Rect(real, real) }
+                  fn f(s: Shape): void { match s { Shape::Rect(w, w) => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variable 'w' is bound more than once in pattern 'Shape::Rect(w, w)'."));
+
+    let source = "union Shape { Circle(real), Square(real) }
+                  fn f(s: Shape): void { match s { Shape::Circle(r) | Shape::Square(r) => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("it can't be combined with other patterns."));
+
+    let source = "enum Color { Red }
+                  fn f(c: Color): void { match c { Color::Red(x) => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Variant 'Red' of enum 'Color' has no payload to bind."));
+
+    let source = "union Shape { Circle(real) } enum Color { Red }
+                  fn f(s: Shape): void { match s { Color::Red => {} _ => {} } }";
+    assert!(check(source).is_err());
+}
+
+#[test]
+fn match_union_exhaustiveness() {
+    let source = "union Shape { Circle(real), Rect(real, real), Empty }
+                  fn f(s: Shape): void { match s { Shape::Rect(w, h) => {} } }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("is not exhaustive, missing variants 'Circle', 'Empty'."));
+
+    // The payload can be ignored
+    let source = "union Shape { Circle(real), Rect(real, real) }
+                  fn f(s: Shape): void { match s { Shape::Circle | Shape::Rect => {} } }";
+    assert!(check(source).is_ok());
+
+    let source = "union Shape { Circle(real), Empty }
+                  fn f(s: Shape): void { match s { Shape::Circle(r) => {} _ => {} Shape::Empty => {} } }";
+    let (result, warnings) = check_with_warnings(source);
+    assert!(result.is_ok());
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].ends_with("Warning: Unreachable match arm."));
+}
+
+#[test]
+fn union_payload_requires_pattern() {
+    let source = "union Shape { Circle(real) }
+                  fn f(s: Shape): real { return s.radius; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("The payload of union 'Shape' can only be accessed with a match pattern."));
+
+    let source = "union Shape { Circle(real) }
+                  fn f(s: ptr Shape): real { return s.radius; }";
+    assert!(check(source).is_err());
+
+    let source = "union Shape { Circle(real) }
+                  fn f(a: Shape, b: Shape): bool { return a == b; }";
+    let err = check(source).err().unwrap();
+    assert!(err.contains("Union 'Shape' values can't be compared, match on them instead."));
+}
@@ -7,7 +7,7 @@ fn declare_number() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -22,7 +22,7 @@ fn declare_real() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -37,7 +37,7 @@ fn declare_bool() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -52,7 +52,7 @@ fn declare_init_type_mismatch() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -67,7 +67,15 @@ fn declare_no_init() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
+
+    // A local can be assigned later
+    let source = "fn f(): bool { let a: bool; a = true; return a; }";
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
@@ -77,7 +85,18 @@ fn declare_no_type() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    // The type is inferred from the init expression
+    assert!(parser.parse().is_ok());
+}
+
+#[test]
+fn declare_no_type_no_init() {
+    let source = "let a;";
+    let tokens = Token::lex_string(source);
+
+    let mut parser = Parser::new(tokens, "", "");
+
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -87,7 +106,7 @@ fn assign_valid_number() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -102,7 +121,7 @@ fn assign_valid_real() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -117,7 +136,7 @@ fn assign_valid_bool() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -132,7 +151,7 @@ fn assign_type_mismatch() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -147,7 +166,7 @@ fn undeclared_variable() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -162,7 +181,7 @@ fn parse_valid_int_array_declaration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_some());
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
@@ -172,7 +191,7 @@ fn parse_invalid_type_position_array_declaration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -182,7 +201,7 @@ fn parse_unclosed_array_declaration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -192,7 +211,7 @@ fn parse_missing_semicolon_array_declaration() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -202,7 +221,7 @@ fn parse_valid_array_access() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_some());
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
@@ -212,7 +231,7 @@ fn parse_unclosed_bracket_array_access() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    assert!(parser.parse().is_none());
+    assert!(parser.parse().is_err());
 }
 
 #[test]
@@ -222,7 +241,7 @@ fn array_init_valid_type_check() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -237,7 +256,7 @@ fn array_init_valid_type_check_real() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -252,7 +271,7 @@ fn array_init_invalid_type_check() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -262,12 +281,13 @@ fn array_init_invalid_type_check() {
 
 #[test]
 fn array_valid_type_check_access() {
-    let source = "let arr: [real; 10] = 10.2; let b: real = arr[0];";
+    // Globals need constant init expressions so the access is done in a function
+    let source = "fn f(): void { let arr: [real; 10] = 10.2; let b: real = arr[0]; }";
     let tokens = Token::lex_string(source);
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -282,7 +302,7 @@ fn array_valid_type_check_assign() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -297,7 +317,7 @@ fn array_invalid_type_check_access() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -312,7 +332,7 @@ fn array_invalid_type_check_assign() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_err());
     } else {
@@ -327,7 +347,7 @@ fn string_declaration_valid() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -342,7 +362,7 @@ fn char_declaration_valid() {
 
     let mut parser = Parser::new(tokens, "", "");
 
-    if let Some(mut ast) = parser.parse() {
+    if let Ok(mut ast) = parser.parse() {
         let mut type_check = TypeChecker::new();
         assert!(type_check.check_ast_type(&mut ast).is_ok());
     } else {
@@ -0,0 +1,241 @@
+use std::fs;
+
+use compiler::{
+    desugar::import_resolver::ImportResolver,
+    lexer::Token,
+    lint::{Lint, LintLevel, LintLevels, Warning},
+    parser::parser::Parser,
+    type_system::type_check::TypeChecker,
+};
+
+mod common;
+
+use common::{check_warnings, messages};
+
+/// Writes `modules` in a directory named after the test and returns the
+/// warnings of the imports of `source`
+fn resolve(test_name: &str, modules: &[(&str, &str)], source: &str) -> Vec<Warning> {
+    let dir = std::env::temp_dir().join(format!("light_warning_test_{}", test_name));
+    fs::create_dir_all(&dir).unwrap();
+
+    for (name, content) in modules {
+        fs::write(dir.join(format!("{}.lht", name)), content).unwrap();
+    }
+
+    let tokens = Token::lex_string(source);
+    let mut parser = Parser::new(tokens, dir.to_str().unwrap(), "main.lht");
+    let ast = parser.parse().expect("Parser failed!");
+
+    let mut resolver = ImportResolver::new();
+    resolver.resolve_imports(&ast, "main.lht").unwrap();
+    resolver.get_warnings().clone()
+}
+
+#[test]
+fn unused_variables() {
+    let source = "fn f(a: number, b: number, _c: number): number {
+                      let x = 1;
+                      let y = 2;
+                      y = 3;
+                      let _z = 4;
+                      return a;
+                  }";
+    let warnings = check_warnings(source);
+    assert_eq!(
+        messages(&warnings),
+        vec![
+            ":2:22 Warning: Unused variable 'x'.",
+            ":3:22 Warning: Unused variable 'y'.",
+            ":1:0 Warning: Unused parameter 'b'.",
+        ]
+    );
+    assert!(warnings.iter().all(|w| w.lint == Lint::UnusedVariables));
+
+    // Reads through a pointer, a member or the assigned value are uses
+    let source = "struct P { x: number; }
+                  fn f(p: ptr P, q: ptr number, n: number): void { p.x = 1; deref q = n; }
+                  fn g(): number { let v = 0; v = v + 1; let s = struct P { x: 1 }; return s.x; }
+                  impl P { fn get(self: P): number { return 0; } }";
+    assert!(check_warnings(source).is_empty());
+
+    // Globals are used by the other modules
+    assert!(check_warnings("let g = 1; const C: number = 2;").is_empty());
+}
+
+#[test]
+fn unused_parameters_of_generic_instances() {
+    // Reported once for all the instances
+    let source = "fn id<T>(x: T, unused: T): T { return x; }
+                  fn f(): number { let a = id(1.0, 2.0); return id(3, 4); }";
+    let warnings = messages(&check_warnings(source));
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.contains(&":1:0 Warning: Unused parameter 'unused'.".to_string()));
+    assert!(warnings.contains(&":2:35 Warning: Unused variable 'a'.".to_string()));
+}
+
+#[test]
+fn unreachable_code() {
+    let source = "fn f(x: number): number {
+                      while (x > 0) {
+                          break;
+                          x = x - 1;
+                      }
+                      return x;
+                      x = 2;
+                      x = 3;
+                  }";
+    let warnings = check_warnings(source);
+    assert_eq!(
+        messages(&warnings),
+        vec![
+            ":4:26 Warning: Unreachable statement.",
+            ":7:22 Warning: Unreachable statement.",
+        ]
+    );
+    assert!(warnings.iter().all(|w| w.lint == Lint::UnreachableCode));
+
+    // The statements following an if returning on both branches
+    let source = "fn f(x: number): number {
+                      if (x > 0) { return 1; } else { return 2; }
+                      while (x > 0) { x = x - 1; }
+                      return x;
+                  }";
+    assert_eq!(
+        messages(&check_warnings(source)),
+        vec![":3:22 Warning: Unreachable statement."]
+    );
+
+    let source = "fn f(x: number): void { loop { x = x + 1; if (x > 3) { continue; } } }";
+    assert!(check_warnings(source).is_empty());
+}
+
+#[test]
+fn shadowing() {
+    let source = "let g = 1; fn f(g: number): number { return g; }";
+    let warnings = check_warnings(source);
+    assert_eq!(
+        messages(&warnings),
+        vec![":1:11 Warning: 'g' shadows a variable of an enclosing scope."]
+    );
+    assert_eq!(warnings[0].lint, Lint::Shadowing);
+
+    let source = "union U { A(number), B }
+                  fn f(u: U, n: number): number { match u { U::A(n) => { return n; } _ => { return 0; } } }";
+    let warnings = messages(&check_warnings(source));
+    assert!(warnings
+        .contains(&":2:60 Warning: 'n' shadows a variable of an enclosing scope.".to_string()));
+    assert!(warnings.contains(&":2:18 Warning: Unused parameter 'n'.".to_string()));
+
+    // Functions aren't variables
+    let source = "fn len(): number { return 0; } fn f(len: number): number { return len; }";
+    assert!(check_warnings(source).is_empty());
+
+    let source = "fn f(x: number): number {
+                      if (x > 0) { let x = 2; return x; }
+                      for let x = 0; x < 3; x = x + 1; { }
+                      return x;
+                  }";
+    assert_eq!(
+        messages(&check_warnings(source)),
+        vec![
+            ":2:35 Warning: 'x' shadows a variable of an enclosing scope.",
+            ":3:26 Warning: 'x' shadows a variable of an enclosing scope.",
+        ]
+    );
+}
+
+#[test]
+fn redefinition_in_the_same_scope() {
+    let tokens = Token::lex_string("fn f(): void { let x = 1; let x = 2; }");
+    let mut parser = Parser::new(tokens, "", "");
+    let mut ast = parser.parse().expect("Parser failed!");
+
+    let error = TypeChecker::new().check_ast_type(&mut ast).unwrap_err();
+    assert!(error.contains("Redifinition of variable 'x'."));
+}
+
+#[test]
+fn uninitialised_use() {
+    let source = "fn f(c: bool): number {
+                      let x: number;
+                      let y: number;
+                      let z: number;
+                      if (c) { x = 1; y = 1; } else { y = 2; }
+                      loop { z = 3; break; }
+                      return x + x + y + z;
+                  }";
+    let warnings = check_warnings(source);
+    assert_eq!(
+        messages(&warnings),
+        vec![":7:29 Warning: Variable 'x' is used before being initialised."]
+    );
+    assert_eq!(warnings[0].lint, Lint::UninitialisedUse);
+
+    // A branch leaving the function doesn't reach the read
+    let source = "fn f(c: bool): number {
+                      let x: number;
+                      if (c) { return 0; } else { x = 1; }
+                      while (c) { x = x + 1; }
+                      return x;
+                  }";
+    assert!(check_warnings(source).is_empty());
+
+    let source = "fn f(c: bool): number {
+                      let x: number;
+                      while (c) { x = 1; }
+                      return x;
+                  }";
+    assert_eq!(
+        messages(&check_warnings(source)),
+        vec![":4:29 Warning: Variable 'x' is used before being initialised."]
+    );
+}
+
+#[test]
+fn unused_imports() {
+    let modules = [
+        (
+            "stdio",
+            "export fn println(): void {} export struct Stream { fd: number; } \
+             export const STDOUT: number = 1;",
+        ),
+        ("math", "export fn abs(n: number): number { return n; }"),
+        ("memory", "export fn free(): void {}"),
+    ];
+
+    let source = "import { println, Stream, STDOUT } from \"stdio\";
+                  import \"math\";
+                  import \"memory\" as mem;
+                  fn f(): number { println(); return math::abs(STDOUT); }";
+    let warnings = resolve("unused_imports", &modules, source);
+    assert_eq!(
+        messages(&warnings),
+        vec![
+            "main.lht:1:0 Warning: Unused import 'Stream' from 'stdio'.",
+            "main.lht:3:18 Warning: Unused import 'mem'.",
+        ]
+    );
+    assert!(warnings.iter().all(|w| w.lint == Lint::UnusedImports));
+
+    // Types, namespaces and re-exports are uses
+    let source = "import { Stream } from \"stdio\";
+                  import \"math\" as m;
+                  export import \"memory\";
+                  fn f(s: ptr Stream): void { m::abs(1); }";
+    assert!(resolve("unused_imports", &modules, source).is_empty());
+}
+
+#[test]
+fn lint_levels() {
+    assert_eq!("unused-imports".parse(), Ok(Lint::UnusedImports));
+    assert!("unused".parse::<Lint>().is_err());
+
+    let mut levels = LintLevels::new();
+    assert_eq!(levels.get(Lint::Shadowing), LintLevel::Warn);
+    levels.set(Lint::Shadowing, LintLevel::Deny);
+    assert_eq!(levels.get(Lint::Shadowing), LintLevel::Deny);
+    assert_eq!(levels.get(Lint::UnreachableCode), LintLevel::Warn);
+
+    let warnings = check_warnings("fn f(x: number): void {}");
+    assert_eq!(warnings[0].as_error(), ":1:0 Error: Unused parameter 'x'.");
+}
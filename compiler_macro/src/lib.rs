@@ -1,7 +1,7 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::Span as MacroSpan;
 use quote::quote;
 use syn::{parse_macro_input, Data, DataEnum, DataStruct, DeriveInput, Ident};
 
@@ -18,6 +18,7 @@ fn expand_debug_info_struct(input: &DeriveInput, strct: &DataStruct) -> TokenStr
                 pub line: usize,
                 pub column: usize,
                 pub filename: String,
+                pub span: Span,
             }
 
             impl LineDebugInfo for #ident {
@@ -33,6 +34,12 @@ fn expand_debug_info_struct(input: &DeriveInput, strct: &DataStruct) -> TokenStr
                     &self.filename
                 }
             }
+
+            impl SpanDebugInfo for #ident {
+                fn span(&self) -> Span {
+                    self.span
+                }
+            }
         }
     } else {
         quote! {
@@ -41,6 +48,7 @@ fn expand_debug_info_struct(input: &DeriveInput, strct: &DataStruct) -> TokenStr
                 pub line: usize,
                 pub column: usize,
                 pub filename: String,
+                pub span: Span,
             }
 
             impl LineDebugInfo for #ident {
@@ -56,6 +64,12 @@ fn expand_debug_info_struct(input: &DeriveInput, strct: &DataStruct) -> TokenStr
                     &self.filename
                 }
             }
+
+            impl SpanDebugInfo for #ident {
+                fn span(&self) -> Span {
+                    self.span
+                }
+            }
         }
     };
 
@@ -65,6 +79,7 @@ fn expand_debug_info_struct(input: &DeriveInput, strct: &DataStruct) -> TokenStr
 fn expand_debug_info_enum(input: &DeriveInput, en: &DataEnum) -> TokenStream {
     let ident = &input.ident;
     let mut arms = Vec::new();
+    let mut span_arms = Vec::new();
 
     for v in &en.variants {
         let v_ident = &v.ident;
@@ -72,7 +87,12 @@ fn expand_debug_info_enum(input: &DeriveInput, en: &DataEnum) -> TokenStream {
             .fields
             .iter()
             .enumerate()
-            .map(|(i, _)| Ident::new(format!("enum_variant_{}", i).as_str(), Span::call_site()))
+            .map(|(i, _)| {
+                Ident::new(
+                    format!("enum_variant_{}", i).as_str(),
+                    MacroSpan::call_site(),
+                )
+            })
             .collect();
         let args = quote! {
             #(#f),*
@@ -87,7 +107,19 @@ fn expand_debug_info_enum(input: &DeriveInput, en: &DataEnum) -> TokenStream {
                 #ident::#v_ident => 0usize
             }
         };
+        // The span of a variant goes from its first field to its last one, so
+        // binary expressions cover both of their operands.
+        let span_arm = match (f.first(), f.last()) {
+            (Some(first_name), Some(last_name)) => quote! {
+                #ident::#v_ident(#args) => #first_name.span().to(&#last_name.span())
+            },
+            _ => quote! {
+                #ident::#v_ident => Span::default()
+            },
+        };
+
         arms.push(arm);
+        span_arms.push(span_arm);
     }
 
     let expand = quote! {
@@ -113,6 +145,14 @@ fn expand_debug_info_enum(input: &DeriveInput, en: &DataEnum) -> TokenStream {
                 }
             }
         }
+
+        impl SpanDebugInfo for #ident {
+            fn span(&self) -> Span {
+                match self {
+                    #(#span_arms),*
+                }
+            }
+        }
     };
 
     TokenStream::from(expand)
@@ -28,32 +28,37 @@ pub fn repl_loop() {
             let mut parser = Parser::new(tokens, "./module.lht", "./module.lht");
             let mut import_resolver = ImportResolver::new();
 
-            if let Some(mut stmts) = parser.parse() {
-                print_ast(&stmts);
-                match import_resolver.resolve_imports(&mut stmts, "./module.lht") {
-                    Ok(r) => stmts = r,
-                    Err(msg) => {
-                        eprintln!("{}", msg);
-                        continue;
+            match parser.parse() {
+                Ok(mut stmts) => {
+                    print_ast(&stmts);
+                    match import_resolver.resolve_imports(&mut stmts, "./module.lht") {
+                        Ok(r) => stmts = r,
+                        Err(msg) => {
+                            eprintln!("{}", msg);
+                            continue;
+                        }
                     }
-                }
 
-                if let Err(msg) = type_check.check_ast_type(&mut stmts) {
-                    println!("Error: {}", msg);
-                } else {
-                    let mut generator =
-                        create_generator(&context, "main", &type_check.get_type_table());
-                    let engine = generator
-                        .module
-                        .create_jit_execution_engine(OptimizationLevel::None)
-                        .unwrap();
-                    desugar_ast(&mut stmts);
-                    print_ast(&stmts);
-                    generate_ir_code_jit(&mut generator, &engine, &stmts);
-                    println!("OK");
+                    if let Err(msg) = type_check.check_ast_type(&mut stmts) {
+                        println!("Error: {}", msg);
+                    } else {
+                        let mut generator =
+                            create_generator(&context, "main", &type_check.get_type_table());
+                        let engine = generator
+                            .module
+                            .create_jit_execution_engine(OptimizationLevel::None)
+                            .unwrap();
+                        desugar_ast(&mut stmts);
+                        print_ast(&stmts);
+                        generate_ir_code_jit(&mut generator, &engine, &stmts);
+                        println!("OK");
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        println!("{}:{} Error: {}", error.line, error.column, error.message);
+                    }
                 }
-            } else {
-                println!("Error!");
             }
         } else {
             panic!("{}", line.unwrap_err());
@@ -1,6 +1,9 @@
 mod object_builder;
 
+use std::{env, path::PathBuf};
+
 use clap::Parser;
+use compiler::lint::{Lint, LintLevel};
 use inkwell::context::Context;
 use object_builder::FileBuilder;
 
@@ -22,6 +25,76 @@ struct Args {
     /// Output name
     #[clap(short, long, default_value = "program")]
     pub output: String,
+
+    /// Directory searched for imported modules, can be repeated
+    #[clap(short = 'I', long = "include")]
+    pub include_dirs: Vec<String>,
+
+    /// Report the warnings of a lint, can be repeated
+    #[clap(short = 'W', long = "warn")]
+    pub warn: Vec<String>,
+
+    /// Don't report the warnings of a lint, can be repeated
+    #[clap(short = 'A', long = "allow")]
+    pub allow: Vec<String>,
+
+    /// Report the warnings of a lint as errors, can be repeated
+    #[clap(short = 'D', long = "deny")]
+    pub deny: Vec<String>,
+}
+
+/// Standard library bundled with the compiler. `LIGHT_STDLIB` overrides it,
+/// otherwise it's the `stdlib` directory next to the binary or in one of its
+/// parents, as in an install prefix or the `target` directory of the repository.
+fn stdlib_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("LIGHT_STDLIB") {
+        return Some(PathBuf::from(path)).filter(|path| path.is_dir());
+    }
+
+    let exe = env::current_exe().ok()?;
+    exe.ancestors()
+        .skip(1)
+        .take(3)
+        .map(|dir| dir.join("stdlib"))
+        .find(|path| path.is_dir())
+}
+
+/// Imported modules are searched next to their importer, then in the
+/// `-I` directories, the `LIGHT_PATH` directories and the standard library.
+fn add_search_paths(include_dirs: &Vec<String>, builder: &mut FileBuilder) {
+    for dir in include_dirs {
+        builder.add_search_path(dir);
+    }
+
+    if let Some(light_path) = env::var_os("LIGHT_PATH") {
+        for dir in env::split_paths(&light_path) {
+            if !dir.as_os_str().is_empty() {
+                builder.add_search_path(dir);
+            }
+        }
+    }
+
+    // Without a standard library, its modules are simply not found
+    if let Some(stdlib) = stdlib_path() {
+        builder.add_search_path(stdlib);
+    }
+}
+
+/// Lints named by several flags get the level of the strictest one.
+fn set_lint_levels(args: &Args, builder: &mut FileBuilder) -> Result<(), String> {
+    let flags = [
+        (&args.allow, LintLevel::Allow),
+        (&args.warn, LintLevel::Warn),
+        (&args.deny, LintLevel::Deny),
+    ];
+
+    for (names, level) in flags {
+        for name in names {
+            builder.set_lint_level(name.parse::<Lint>()?, level);
+        }
+    }
+
+    Ok(())
 }
 
 fn build_objects(filenames: &Vec<String>, builder: &mut FileBuilder, print_ir: bool) -> bool {
@@ -48,6 +121,13 @@ fn main() {
         std::process::exit(0);
     }
 
+    add_search_paths(&args.include_dirs, &mut builder);
+
+    if let Err(msg) = set_lint_levels(&args, &mut builder) {
+        eprintln!("{}", msg);
+        std::process::exit(1);
+    }
+
     if !build_objects(&args.files, &mut builder, args.print_ir_code) {
         std::process::exit(1);
     }
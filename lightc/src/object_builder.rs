@@ -1,9 +1,15 @@
-use std::{fs, path::Path, process::Command, str::FromStr};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
 
 use compiler::{
     desugar::{desugar_ast, import_resolver::ImportResolver},
     generation::ir_generator::{create_generator, IRGenerator},
     lexer::Token,
+    lint::{Lint, LintLevel, LintLevels, Warning},
     parser::parser::Parser,
     type_system::type_check::TypeChecker,
 };
@@ -17,6 +23,9 @@ use inkwell::{
 pub struct FileBuilder<'m> {
     pub context: &'m Context,
     pub modules: Vec<(String, IRGenerator<'m>)>,
+    /// Shared by the files so each imported module is parsed once
+    import_resolver: ImportResolver,
+    lint_levels: LintLevels,
 }
 
 impl<'m> FileBuilder<'m> {
@@ -24,9 +33,21 @@ impl<'m> FileBuilder<'m> {
         Self {
             context: ctx,
             modules: Vec::new(),
+            import_resolver: ImportResolver::new(),
+            lint_levels: LintLevels::new(),
         }
     }
 
+    pub fn set_lint_level(&mut self, lint: Lint, level: LintLevel) {
+        self.lint_levels.set(lint, level);
+    }
+
+    /// Directory searched for the imported modules after the directory of
+    /// the importing module, in the order of the calls.
+    pub fn add_search_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.import_resolver.add_search_path(path);
+    }
+
     pub fn generate_module_ir(&mut self, path: &str, print_ir_code: bool) -> bool {
         let content = if let Ok(c) = Self::read_file_content(path) {
             c
@@ -37,46 +58,80 @@ impl<'m> FileBuilder<'m> {
         let tokens = Token::lex_string(&content);
         let module_dir = Self::extract_module_directory(path);
         let mut parser = Parser::new(tokens, &module_dir, path);
-        let mut import_resolve = ImportResolver::new();
 
-        if let Some(mut stmts) = parser.parse() {
-            match import_resolve.resolve_imports(&stmts, path) {
-                Ok(s) => stmts = s,
-                Err(msg) => {
-                    eprintln!("{}", msg);
-                    return false;
+        match parser.parse() {
+            Ok(mut stmts) => {
+                match self.import_resolver.resolve_imports(&stmts, path) {
+                    Ok(s) => stmts = s,
+                    Err(msg) => {
+                        eprintln!("{}", msg);
+                        return false;
+                    }
                 }
-            }
 
-            let mut type_checker = TypeChecker::new();
+                let mut type_checker = TypeChecker::new();
 
-            let t_check = type_checker.check_ast_type(&mut stmts);
-            if let Ok(_) = t_check {
-                let mut generator =
-                    create_generator(self.context, path, &type_checker.get_type_table());
-                generator.module = self
-                    .context
-                    .create_module(Path::new(path).file_name().unwrap().to_str().unwrap());
+                let t_check = type_checker.check_ast_type(&mut stmts);
+                let imports_denied = self.report_warnings(self.import_resolver.get_warnings());
+                let checks_denied = self.report_warnings(type_checker.get_warnings());
 
-                desugar_ast(&mut stmts);
-                generator.generate_ir(&stmts);
+                if imports_denied || checks_denied {
+                    if let Err(msg) = t_check {
+                        eprintln!("{}", msg);
+                    }
+                    return false;
+                }
 
-                if print_ir_code {
-                    generator.print_code();
+                if let Ok(_) = t_check {
+                    let mut generator =
+                        create_generator(self.context, path, &type_checker.get_type_table());
+                    generator.module = self
+                        .context
+                        .create_module(Path::new(path).file_name().unwrap().to_str().unwrap());
+
+                    desugar_ast(&mut stmts);
+                    generator.generate_ir(&stmts);
+
+                    if print_ir_code {
+                        generator.print_code();
+                    }
+                    self.modules
+                        .push((String::from_str(path).unwrap(), generator));
+                } else {
+                    eprintln!("{}", t_check.err().unwrap());
+                    return false;
+                }
+            }
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("{}", error);
                 }
-                self.modules
-                    .push((String::from_str(path).unwrap(), generator));
-            } else {
-                eprintln!("{}", t_check.err().unwrap());
                 return false;
             }
-        } else {
-            return false;
         }
 
         true
     }
 
+    /// Prints the warnings of the lints not allowed, returns true if one of
+    /// them is denied.
+    fn report_warnings(&self, warnings: &Vec<Warning>) -> bool {
+        let mut denied = false;
+
+        for warning in warnings {
+            match self.lint_levels.get(warning.lint) {
+                LintLevel::Allow => {}
+                LintLevel::Warn => eprintln!("{} [-W {}]", warning, warning.lint),
+                LintLevel::Deny => {
+                    eprintln!("{} [-D {}]", warning.as_error(), warning.lint);
+                    denied = true;
+                }
+            }
+        }
+
+        denied
+    }
+
     pub fn build_objects_files(&self) -> bool {
         for (name, generator) in &self.modules {
             Target::initialize_x86(&InitializationConfig::default());
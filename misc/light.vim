@@ -5,10 +5,10 @@ elseif exists("b:current_syntax")
 endif
 
 syn keyword     lightConditional    if else
-syn keyword     lightStatement      import let struct
-syn keyword     lightKeyword        break continue export ptr fn return
+syn keyword     lightStatement      import as from let struct enum union impl interface
+syn keyword     lightKeyword        break continue export ptr dyn fn return
 syn keyword     lightRepeat         for while loop
-syn keyword     lightType           number real bool void string char
+syn keyword     lightType           number real bool void string char i8 i16 i32 i64 u8 u16 u32 u64 f32 f64
 syn keyword     lightOperator       addrof deref and or not + - * / % ::
 syn keyword     lightBoolean        true false null
 syn keyword     lightTodo           TODO FIXME XXX
@@ -18,7 +18,9 @@ syn match       lightFunction "\zs\(\k\w*\)*\s*\ze("
 syn match       lightNumber   '\d\+'
 syn match       lightFloat    '\d\+\.\d+'
 syn match       lightChar     '\'.\''
-syn match       lightComment  "//.*$"
+syn match       lightComment  "//.*$" contains=lightTodo
+syn match       lightDocComment "///.*$" contains=lightTodo
+syn region      lightComment  start="/\*" end="\*/" contains=lightComment,lightTodo
 
 let b:current_syntax = "light"
 
@@ -34,6 +36,7 @@ hi def link      lightNumber          Number
 hi def link      lightFloat           Float
 hi def link      lightChar            Character
 hi def link      lightComment         Comment
+hi def link      lightDocComment      SpecialComment
 hi def link      lightBoolean         Boolean
 hi def link      lightTodo            Todo
 